@@ -20,6 +20,8 @@
  *                                result:i32(+56) }
  */
 
+import { log } from './logger.js';
+
 // Struct sizes
 const SZ_PARAM = 16;
 const SZ_ENTITY = 56;
@@ -44,6 +46,7 @@ const C = {
 	PtOnLine: 100006,
 	EqualLength: 100008,
 	LengthRatio: 100009,
+	EqualAngle: 100012,
 	Symmetric: 100014,
 	SymmetricHoriz: 100015,
 	SymmetricVert: 100016,
@@ -58,7 +61,8 @@ const C = {
 	ArcLineTangent: 100027,
 	EqualRadius: 100029,
 	WhereDragged: 100031,
-	CurveCurveTangent: 100032
+	CurveCurveTangent: 100032,
+	LengthDifference: 100033
 };
 
 // Groups
@@ -108,6 +112,7 @@ export function solveSketch(entities, constraints, positions) {
 	// Handle resolution maps
 	const eMap = new Map(); // sketch entity ID -> slvs entity handle
 	const pMap = new Map(); // slvs param handle -> { id, axis }
+	const centers = new Map(); // circle/arc entity ID -> slvs center point handle
 
 	// === Workplane (Group 1) ===
 	// 3D origin at (0,0,0)
@@ -161,6 +166,7 @@ export function solveSketch(entities, constraints, positions) {
 			const eh = ne++;
 			ents.push(mkE(eh, G_SK, E_CIRCLE, WP, [c, 0, 0, 0], NRM, de, [0, 0, 0, 0]));
 			eMap.set(e.id, eh);
+			centers.set(e.id, c);
 		} else if (e.type === 'Arc') {
 			const c = eMap.get(e.center_id),
 				s = eMap.get(e.start_id),
@@ -171,13 +177,15 @@ export function solveSketch(entities, constraints, positions) {
 				mkE(eh, G_SK, E_ARC_OF_CIRCLE, WP, [c, s, end, 0], NRM, 0, [0, 0, 0, 0])
 			);
 			eMap.set(e.id, eh);
+			centers.set(e.id, c);
 		}
 	}
 
 	// === Map constraints ===
 	for (const c of constraints) {
-		const mapped = mapConstraint(c, nc++, WP, eMap);
-		if (mapped) cons.push(mapped);
+		const mapped = mapConstraint(c, () => nc++, WP, eMap, centers);
+		if (Array.isArray(mapped)) cons.push(...mapped);
+		else if (mapped) cons.push(mapped);
 	}
 
 	// No sketch params to solve
@@ -214,8 +222,32 @@ function mkP(h, g, v) {
 function mkE(h, g, t, w, pt, n, d, p) {
 	return { h, g, t, w, pt, n, d, p };
 }
+function mkC(h, type, wrkpl, fields) {
+	return {
+		h,
+		g: G_SK,
+		type,
+		wrkpl,
+		valA: 0,
+		ptA: 0,
+		ptB: 0,
+		entityA: 0,
+		entityB: 0,
+		entityC: 0,
+		entityD: 0,
+		other: 0,
+		other2: 0,
+		...fields
+	};
+}
 
-function mapConstraint(c, ch, wp, eMap) {
+/**
+ * Map one sketch constraint to slvs constraints: a single one, an array
+ * when it takes several, or null when it can't be mapped.
+ * `nextHandle` allocates a constraint handle; `centers` maps circle and
+ * arc IDs to their center point handles.
+ */
+function mapConstraint(c, nextHandle, wp, eMap, centers) {
 	const pt = (id) => eMap.get(id) || 0;
 	const en = (id) => eMap.get(id) || 0;
 	let type,
@@ -352,12 +384,49 @@ function mapConstraint(c, ch, wp, eMap) {
 			entityB = en(c.entity_b);
 			if (!entityA || !entityB) return null;
 			break;
+		case 'EqualAngles': {
+			// The angle at joint i is between line i and line i+1 (the last
+			// joint wraps to the first line); tie each joint to the next.
+			const lines = (c.lines || []).map(en);
+			if (lines.length < 3) {
+				log('sketch', `EqualAngles needs at least 3 lines, got ${lines.length}`);
+				return null;
+			}
+			if (lines.some((l) => !l)) return null;
+			const n = lines.length;
+			const joints = [];
+			for (let i = 0; i < n - 1; i++) {
+				joints.push(
+					mkC(nextHandle(), C.EqualAngle, wp, {
+						entityA: lines[i],
+						entityB: lines[i + 1],
+						entityC: lines[i + 1],
+						entityD: lines[(i + 2) % n]
+					})
+				);
+			}
+			return joints;
+		}
+		case 'LengthDifference':
+			type = C.LengthDifference;
+			valA = c.value || 0;
+			entityA = en(c.line_a);
+			entityB = en(c.line_b);
+			if (!entityA || !entityB) return null;
+			break;
+		case 'Concentric':
+			// slvs has no concentric constraint; make the centers coincide.
+			type = C.Coincident;
+			ptA = centers.get(c.entity_a) || 0;
+			ptB = centers.get(c.entity_b) || 0;
+			if (!ptA || !ptB) return null;
+			break;
 		default:
 			return null;
 	}
 
 	return {
-		h: ch,
+		h: nextHandle(),
 		g: G_SK,
 		type,
 		wrkpl: wp,
@@ -7,9 +7,9 @@ pub mod undo;
 use std::collections::HashMap;
 use uuid::Uuid;
 
-use modeling_ops::{KernelBundle, OpResult};
+use modeling_ops::{FaceProvenance, KernelBundle, OpResult};
 
-use crate::types::{EngineError, FeatureTree, Operation};
+use crate::types::{EngineError, Feature, FeatureStatus, FeatureTree, Operation};
 use crate::undo::{Command, UndoStack};
 
 /// The parametric modeling engine.
@@ -27,6 +27,8 @@ pub struct Engine {
     pub errors: Vec<(Uuid, String)>,
     /// Undo/redo history.
     undo_stack: UndoStack,
+    /// Features whose results each feature read when it was last evaluated.
+    dependencies: HashMap<Uuid, Vec<Uuid>>,
 }
 
 impl Engine {
@@ -38,9 +40,34 @@ impl Engine {
             warnings: Vec::new(),
             errors: Vec::new(),
             undo_stack: UndoStack::new(),
+            dependencies: HashMap::new(),
         }
     }
 
+    /// Serialize the feature tree (features, their parameters and
+    /// suppression, and the rollback index) to JSON.
+    ///
+    /// Rebuild results and undo history are session state and are left out.
+    /// The output carries no format version, so it is only for moving a tree
+    /// within a session (snapshots, tests); project files go through
+    /// `file_format::save_project`, which is versioned and migrated.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self.tree)
+            .expect("FeatureTree serialization should never fail")
+    }
+
+    /// An engine holding the feature tree saved by [`Engine::to_json`], with
+    /// an empty undo history and no results until it is rebuilt.
+    pub fn from_json(json: &str) -> Result<Self, EngineError> {
+        let tree = serde_json::from_str(json).map_err(|e| EngineError::ParseError {
+            reason: e.to_string(),
+        })?;
+        Ok(Self {
+            tree,
+            ..Self::new()
+        })
+    }
+
     /// Add a feature and rebuild.
     pub fn add_feature(
         &mut self,
@@ -78,7 +105,10 @@ impl Engine {
         Ok(())
     }
 
-    /// Edit a feature's operation and rebuild from that point.
+    /// Edit a feature's operation and rebuild the features it affects.
+    ///
+    /// Features that don't depend on it keep their results. Changing the
+    /// kind of operation rebuilds everything from the feature on.
     pub fn edit_feature(
         &mut self,
         id: Uuid,
@@ -96,6 +126,8 @@ impl Engine {
             .ok_or(EngineError::FeatureNotFound { id })?;
         let old_operation = feature.operation.clone();
         feature.operation = operation.clone();
+        let same_kind =
+            std::mem::discriminant(&old_operation) == std::mem::discriminant(&operation);
 
         self.undo_stack.push(Command::EditFeature {
             feature_id: id,
@@ -103,7 +135,11 @@ impl Engine {
             new_operation: Box::new(operation),
         });
 
-        self.rebuild(kb, pos);
+        if same_kind {
+            self.rebuild_affected(kb, id);
+        } else {
+            self.rebuild(kb, pos);
+        }
         Ok(())
     }
 
@@ -162,6 +198,144 @@ impl Engine {
         Ok(())
     }
 
+    /// Freeze a feature's current result into an [`Operation::StaticSolid`]
+    /// so later rebuilds reuse it instead of re-evaluating the feature and
+    /// its upstream chain.
+    ///
+    /// The upstream chain is every feature it transitively references (as
+    /// reported by [`FeatureTree::describe`]). Those are removed from the
+    /// tree unless a feature outside the chain still references them. The
+    /// baked feature keeps its ID, so GeomRefs to its output still resolve.
+    /// Fails with `NothingToBake` if the feature has no result from the last
+    /// rebuild. Undoable.
+    pub fn bake_feature(
+        &mut self,
+        id: Uuid,
+        kb: &mut dyn KernelBundle,
+    ) -> Result<(), EngineError> {
+        let pos = self
+            .tree
+            .feature_index(id)
+            .ok_or(EngineError::FeatureNotFound { id })?;
+        let result = self
+            .feature_results
+            .get(&id)
+            .cloned()
+            .ok_or(EngineError::NothingToBake { id })?;
+
+        let references: Vec<Vec<usize>> = self
+            .tree
+            .describe()
+            .into_iter()
+            .map(|d| d.references)
+            .collect();
+        let mut in_chain = vec![false; self.tree.features.len()];
+        let mut pending = vec![pos];
+        while let Some(i) = pending.pop() {
+            if !std::mem::replace(&mut in_chain[i], true) {
+                pending.extend(&references[i]);
+            }
+        }
+        let still_needed = |i: usize| {
+            references
+                .iter()
+                .enumerate()
+                .any(|(j, refs)| !in_chain[j] && refs.contains(&i))
+        };
+        let absorbed: Vec<(usize, Feature)> = (0..pos)
+            .filter(|&i| in_chain[i] && !still_needed(i))
+            .map(|i| (i, self.tree.features[i].clone()))
+            .collect();
+
+        let source: Vec<Feature> = self
+            .tree
+            .features
+            .iter()
+            .zip(&in_chain)
+            .filter(|(_, &chained)| chained)
+            .map(|(f, _)| f.clone())
+            .collect();
+        let new_operation = Operation::StaticSolid {
+            result: Some(Box::new(result)),
+            source,
+        };
+        let old_operation = self.tree.features[pos].operation.clone();
+
+        let cmd = Command::BakeFeature {
+            feature_id: id,
+            old_operation: Box::new(old_operation),
+            new_operation: Box::new(new_operation),
+            absorbed,
+        };
+        let rebuild_from = self.apply_forward(&cmd);
+        self.undo_stack.push(cmd);
+        self.rebuild(kb, rebuild_from);
+        Ok(())
+    }
+
+    /// Evaluate the model once per row of a CSV parameter table.
+    ///
+    /// The header row names each column `<feature name>.<parameter>` (see
+    /// [`FeatureTree::set_parameter`]); every later row is one variant.
+    /// Fields are plain comma-separated numbers without quoting. Each row
+    /// yields the feature results of its rebuild, or the first error. The
+    /// tree is restored and rebuilt afterwards. Not undoable.
+    pub fn apply_parameters_csv(
+        &mut self,
+        csv: &str,
+        kb: &mut dyn KernelBundle,
+    ) -> Vec<Result<HashMap<Uuid, OpResult>, EngineError>> {
+        let mut lines = csv.lines().map(str::trim).filter(|line| !line.is_empty());
+        let Some(header) = lines.next() else {
+            return Vec::new();
+        };
+        let columns: Vec<&str> = header.split(',').map(str::trim).collect();
+
+        let original = self.tree.features.clone();
+        let variants: Vec<_> = lines
+            .enumerate()
+            .map(|(row, line)| {
+                let values: Vec<&str> = line.split(',').map(str::trim).collect();
+                if values.len() != columns.len() {
+                    return Err(EngineError::InvalidParameter {
+                        reason: format!(
+                            "row {} has {} values for {} columns",
+                            row + 1,
+                            values.len(),
+                            columns.len()
+                        ),
+                    });
+                }
+                for (column, value) in columns.iter().zip(values) {
+                    let (feature, parameter) = column.rsplit_once('.').ok_or_else(|| {
+                        EngineError::InvalidParameter {
+                            reason: format!("column '{}' is not <feature>.<parameter>", column),
+                        }
+                    })?;
+                    let value = value.parse().map_err(|_| EngineError::InvalidParameter {
+                        reason: format!("row {}: '{}' is not a number", row + 1, value),
+                    })?;
+                    self.tree.set_parameter(feature, parameter, value)?;
+                }
+                self.rebuild(kb, 0);
+                match self.errors.first() {
+                    Some((id, reason)) => Err(EngineError::RebuildFailed {
+                        feature_name: self
+                            .tree
+                            .find_feature(*id)
+                            .map_or_else(|| id.to_string(), |f| f.name.clone()),
+                        reason: reason.clone(),
+                    }),
+                    None => Ok(self.feature_results.clone()),
+                }
+            })
+            .collect();
+
+        self.tree.features = original;
+        self.rebuild(kb, 0);
+        variants
+    }
+
     /// Set rollback index and rebuild. Not undoable.
     pub fn set_rollback(&mut self, index: Option<usize>, kb: &mut dyn KernelBundle) {
         self.tree.set_rollback(index);
@@ -248,6 +422,29 @@ impl Engine {
                 let _ = self.tree.rename_feature(*feature_id, old_name.clone());
                 0 // No rebuild needed for rename
             }
+            Command::BakeFeature {
+                feature_id,
+                old_operation,
+                absorbed,
+                ..
+            } => {
+                if let Some(f) = self.tree.find_feature_mut(*feature_id) {
+                    f.operation = (**old_operation).clone();
+                }
+                for (position, feature) in absorbed {
+                    self.tree.features.insert(*position, feature.clone());
+                    if let Some(ref mut idx) = self.tree.active_index {
+                        if *position <= *idx {
+                            *idx += 1;
+                        }
+                    }
+                }
+                absorbed
+                    .first()
+                    .map(|(position, _)| *position)
+                    .or_else(|| self.tree.feature_index(*feature_id))
+                    .unwrap_or(0)
+            }
         }
     }
 
@@ -306,29 +503,103 @@ impl Engine {
                 let _ = self.tree.rename_feature(*feature_id, new_name.clone());
                 0 // No rebuild needed for rename
             }
+            Command::BakeFeature {
+                feature_id,
+                new_operation,
+                absorbed,
+                ..
+            } => {
+                for (_, feature) in absorbed {
+                    let _ = self.tree.remove_feature(feature.id);
+                    self.feature_results.remove(&feature.id);
+                }
+                if let Some(f) = self.tree.find_feature_mut(*feature_id) {
+                    f.operation = (**new_operation).clone();
+                }
+                self.tree.feature_index(*feature_id).unwrap_or(0)
+            }
         }
     }
 
     /// Rebuild the feature tree from the given index.
     fn rebuild(&mut self, kb: &mut dyn KernelBundle, from_index: usize) {
         // Clear results from the rebuild point onward (active features)
-        let active = self.tree.active_features();
-        for feature in active.iter().skip(from_index) {
+        for feature in self.tree.active_features().iter().skip(from_index) {
             self.feature_results.remove(&feature.id);
         }
+        self.run_rebuild(kb, from_index);
+    }
+
+    /// Rebuild only `id` and the features that depend on it.
+    fn rebuild_affected(&mut self, kb: &mut dyn KernelBundle, id: Uuid) {
+        let affected = self.affected_features(id);
+        for feature in &affected {
+            self.feature_results.remove(feature);
+        }
+        // Everything before the earliest affected feature keeps its result.
+        let from_index = affected
+            .first()
+            .and_then(|&first| self.tree.feature_index(first))
+            .unwrap_or(0);
+        self.run_rebuild(kb, from_index);
+    }
 
+    /// Evaluate every active feature from `from_index` on that has no result.
+    fn run_rebuild(&mut self, kb: &mut dyn KernelBundle, from_index: usize) {
         // Clear results for inactive features (beyond rollback)
-        let active_len = active.len();
+        let active_len = self.tree.active_features().len();
         for feature in self.tree.features.iter().skip(active_len) {
             self.feature_results.remove(&feature.id);
         }
 
+        // What was recorded for deleted features, and for features about to
+        // be re-evaluated, is stale. The rebuild records what it runs afresh.
+        let (tree, results) = (&self.tree, &self.feature_results);
+        self.dependencies.retain(|id, _| {
+            tree.feature_index(*id)
+                .is_some_and(|i| i < from_index || results.contains_key(id))
+        });
+
         let state = rebuild::rebuild(&self.tree, kb, from_index, &self.feature_results);
         self.feature_results.extend(state.feature_results);
+        self.dependencies.extend(state.dependencies);
         self.warnings = state.warnings;
         self.errors = state.errors;
     }
 
+    /// `id` and every feature that, directly or through others, read its
+    /// result when last evaluated, in tree order. Empty if `id` is not in
+    /// the tree.
+    pub fn affected_features(&self, id: Uuid) -> Vec<Uuid> {
+        if self.tree.feature_index(id).is_none() {
+            return Vec::new();
+        }
+        let mut affected = vec![id];
+        // Sweep until nothing new is added, so the answer does not depend on
+        // dependencies pointing only backwards in the tree.
+        loop {
+            let before = affected.len();
+            for feature in &self.tree.features {
+                let reads_affected = self
+                    .dependencies
+                    .get(&feature.id)
+                    .is_some_and(|deps| deps.iter().any(|d| affected.contains(d)));
+                if reads_affected && !affected.contains(&feature.id) {
+                    affected.push(feature.id);
+                }
+            }
+            if affected.len() == before {
+                break;
+            }
+        }
+        self.tree
+            .features
+            .iter()
+            .map(|f| f.id)
+            .filter(|f| affected.contains(f))
+            .collect()
+    }
+
     /// Full rebuild from scratch (clears all results first).
     pub fn rebuild_from_scratch(&mut self, kb: &mut dyn KernelBundle) {
         self.feature_results.clear();
@@ -340,6 +611,69 @@ impl Engine {
         self.feature_results.get(&feature_id)
     }
 
+    /// Which boolean operand a face of a feature's result came from, with
+    /// the role it had there. `face_index` follows `list_faces` order.
+    ///
+    /// `None` if the feature has no result or is not a boolean.
+    pub fn face_provenance(&self, feature_id: Uuid, face_index: usize) -> Option<&FaceProvenance> {
+        self.feature_results
+            .get(&feature_id)?
+            .provenance
+            .face_origins
+            .get(face_index)
+    }
+
+    /// Summarize every feature's state after the last rebuild, in tree order.
+    ///
+    /// Warnings come from the feature's own diagnostics plus any engine
+    /// warnings tagged with the feature's name.
+    pub fn feature_statuses(&self) -> Vec<(Uuid, FeatureStatus)> {
+        let active_len = self.tree.active_features().len();
+        self.tree
+            .features
+            .iter()
+            .enumerate()
+            .map(|(i, feature)| {
+                let status = if i >= active_len {
+                    FeatureStatus::RolledBack
+                } else if feature.suppressed {
+                    FeatureStatus::Suppressed
+                } else if let Some((_, message)) =
+                    self.errors.iter().find(|(id, _)| *id == feature.id)
+                {
+                    FeatureStatus::Error {
+                        message: message.clone(),
+                    }
+                } else if let Some(result) = self.feature_results.get(&feature.id) {
+                    let prefix = format!("Feature '{}':", feature.name);
+                    let messages: Vec<String> = result
+                        .diagnostics
+                        .operation_warnings
+                        .iter()
+                        .map(|w| w.to_string())
+                        .chain(result.diagnostics.warnings.iter().cloned())
+                        .chain(
+                            self.warnings
+                                .iter()
+                                .filter(|w| w.starts_with(&prefix))
+                                .cloned(),
+                        )
+                        .collect();
+                    if messages.is_empty() {
+                        FeatureStatus::Ok
+                    } else {
+                        FeatureStatus::Warning { messages }
+                    }
+                } else {
+                    FeatureStatus::Error {
+                        message: "no result from the last rebuild".to_string(),
+                    }
+                };
+                (feature.id, status)
+            })
+            .collect()
+    }
+
     /// Whether undo is available.
     pub fn can_undo(&self) -> bool {
         self.undo_stack.can_undo()
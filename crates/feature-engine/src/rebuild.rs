@@ -1,15 +1,16 @@
 use std::collections::HashMap;
 
 use modeling_ops::{
-    execute_boolean, execute_chamfer, execute_extrude, execute_fillet, execute_revolve,
-    execute_shell, BooleanKind, OpResult,
+    execute_boolean_with_operand_roles, execute_chamfer, execute_extrude, execute_fillet,
+    execute_revolve, execute_shell, normalize_profile_winding, BooleanKind, OpResult,
 };
 use uuid::Uuid;
 
 use crate::resolve::resolve_with_fallback;
-use crate::types::{BooleanOp, EngineError, Feature, FeatureTree, Operation};
+use crate::types::{AutoFilletParams, BooleanOp, EngineError, Feature, FeatureTree, Operation};
+use kernel_fork::{Convexity, KernelId, KernelIntrospect, KernelSolidHandle};
 use modeling_ops::KernelBundle;
-use waffle_types::{OutputKey, Sketch};
+use waffle_types::{Anchor, GeomRef, OutputKey, Sketch, TopoKind};
 
 /// State of the engine after a rebuild.
 #[derive(Debug)]
@@ -20,11 +21,16 @@ pub struct RebuildState {
     pub warnings: Vec<String>,
     /// Features that failed to rebuild, with error messages.
     pub errors: Vec<(Uuid, String)>,
+    /// For each feature evaluated in this rebuild, the features whose
+    /// results it read.
+    pub dependencies: HashMap<Uuid, Vec<Uuid>>,
 }
 
 /// Rebuild the feature tree from scratch (or from a change point).
 ///
 /// Replays features in order, resolving GeomRefs and executing operations.
+/// Features from `from_index` on that already have a result in
+/// `existing_results` keep it and are not executed again.
 pub fn rebuild(
     tree: &FeatureTree,
     kb: &mut dyn KernelBundle,
@@ -35,9 +41,10 @@ pub fn rebuild(
         feature_results: HashMap::new(),
         warnings: Vec::new(),
         errors: Vec::new(),
+        dependencies: HashMap::new(),
     };
 
-    // Carry forward results from features before the rebuild point
+    // Carry forward results that are still valid
     for (id, result) in existing_results {
         state.feature_results.insert(*id, result.clone());
     }
@@ -54,7 +61,13 @@ pub fn rebuild(
 
         // Resolve any GeomRef references before executing the feature
         resolve_feature_refs(feature, &state.feature_results, &mut state.warnings);
+        if state.feature_results.contains_key(&feature.id) {
+            continue;
+        }
 
+        state
+            .dependencies
+            .insert(feature.id, consumed_features(feature, tree));
         match execute_feature(feature, kb, &state.feature_results, tree) {
             Ok(result) => {
                 state.feature_results.insert(feature.id, result);
@@ -69,6 +82,46 @@ pub fn rebuild(
     state
 }
 
+/// IDs of the features whose results `feature` reads when it executes.
+///
+/// A cut extrude subtracts from the most recent solid before it, so it
+/// reads every earlier active feature that could provide one. A baked
+/// feature replays its own chain and reads nothing from the tree.
+fn consumed_features(feature: &Feature, tree: &FeatureTree) -> Vec<Uuid> {
+    let mut refs: Vec<&GeomRef> = feature.references.iter().collect();
+    let mut ids = Vec::new();
+    match &feature.operation {
+        Operation::Sketch { .. } | Operation::StaticSolid { .. } => {}
+        Operation::Extrude { params } => {
+            ids.push(params.sketch_id);
+            refs.extend(&params.target_body);
+            if params.cut {
+                ids.extend(
+                    tree.active_features()
+                        .iter()
+                        .take_while(|f| f.id != feature.id)
+                        .filter(|f| !matches!(f.operation, Operation::Sketch { .. }))
+                        .map(|f| f.id),
+                );
+            }
+        }
+        Operation::Revolve { params } => ids.push(params.sketch_id),
+        Operation::Fillet { params } => refs.extend(&params.edges),
+        Operation::Chamfer { params } => refs.extend(&params.edges),
+        Operation::Shell { params } => refs.extend(&params.faces_to_remove),
+        Operation::BooleanCombine { params } => refs.extend([&params.body_a, &params.body_b]),
+        Operation::AutoFillet { params } => refs.push(&params.body),
+    }
+    for geom_ref in refs {
+        if let Anchor::FeatureOutput { feature_id, .. } = geom_ref.anchor {
+            ids.push(feature_id);
+        }
+    }
+    ids.sort_unstable();
+    ids.dedup();
+    ids
+}
+
 /// Execute a single feature's operation.
 fn execute_feature(
     feature: &Feature,
@@ -87,6 +140,7 @@ fn execute_feature(
                     deleted: Vec::new(),
                     modified: Vec::new(),
                     role_assignments: Vec::new(),
+                    face_origins: Vec::new(),
                 },
                 diagnostics: modeling_ops::Diagnostics::default(),
             })
@@ -131,31 +185,36 @@ fn execute_feature(
                 (direction, params.depth, sketch.plane_origin)
             };
 
+            let (profile, winding_warning) = normalize_profile_winding(
+                &sketch.solved_profiles[params.profile_index],
+                &sketch.solved_positions,
+            );
             let x_axis = tangent_x_from_normal(sketch.plane_normal);
             let face_ids = kb.make_faces_from_profiles(
-                &sketch.solved_profiles,
+                std::slice::from_ref(&profile),
                 face_origin,
                 sketch.plane_normal,
                 x_axis,
                 &sketch.solved_positions,
             )?;
 
-            if face_ids.is_empty() {
+            let Some(&face_id) = face_ids.first() else {
                 return Err(EngineError::ProfileOutOfRange {
                     index: params.profile_index,
                     count: 0,
                 });
-            }
+            };
 
-            let face_index = params.profile_index.min(face_ids.len() - 1);
             let extrude_result =
-                execute_extrude(kb, face_ids[face_index], extrude_direction, extrude_depth, None)?;
+                execute_extrude(kb, face_id, extrude_direction, extrude_depth, None)?;
 
-            if params.cut {
+            let mut result = if params.cut {
                 // Find the target body to subtract from (most recent solid before this feature)
-                let target_handle = find_most_recent_solid(feature, feature_results, tree)
-                    .ok_or_else(|| EngineError::ResolutionFailed {
-                        reason: "Cut extrude requires an existing body to subtract from".into(),
+                let (target_handle, target) =
+                    find_most_recent_solid(feature, feature_results, tree).ok_or_else(|| {
+                        EngineError::ResolutionFailed {
+                            reason: "Cut extrude requires an existing body to subtract from".into(),
+                        }
                     })?;
 
                 let tool_handle = extrude_result
@@ -166,12 +225,22 @@ fn execute_feature(
                         reason: "Extrude produced no solid output for cut".into(),
                     })?;
 
-                let boolean_result =
-                    execute_boolean(kb, &target_handle, &tool_handle, BooleanKind::Subtract)?;
-                Ok(boolean_result)
+                execute_boolean_with_operand_roles(
+                    kb,
+                    &target_handle,
+                    &tool_handle,
+                    BooleanKind::Subtract,
+                    &target.provenance.role_assignments,
+                    &extrude_result.provenance.role_assignments,
+                )?
             } else {
-                Ok(extrude_result)
-            }
+                extrude_result
+            };
+            result
+                .diagnostics
+                .operation_warnings
+                .extend(winding_warning);
+            Ok(result)
         }
 
         Operation::Revolve { params } => {
@@ -230,7 +299,14 @@ fn execute_feature(
                 BooleanOp::Intersect => BooleanKind::Intersect,
             };
 
-            let result = execute_boolean(kb, &handle_a, &handle_b, kind)?;
+            let result = execute_boolean_with_operand_roles(
+                kb,
+                &handle_a,
+                &handle_b,
+                kind,
+                operand_roles(&params.body_a, feature_results),
+                operand_roles(&params.body_b, feature_results),
+            )?;
             Ok(result)
         }
 
@@ -286,7 +362,84 @@ fn execute_feature(
             let result = execute_shell(kb, &solid_handle, &face_ids, params.thickness)?;
             Ok(result)
         }
+
+        Operation::AutoFillet { params } => {
+            let solid_handle = find_solid_handle(&params.body, feature_results)?;
+            let edge_ids = auto_fillet_edges(kb.as_introspect(), &solid_handle, params);
+            if edge_ids.is_empty() {
+                return Err(EngineError::RebuildFailed {
+                    feature_name: feature.name.clone(),
+                    reason: "body has no sharp edges to fillet".to_string(),
+                });
+            }
+
+            let result = execute_fillet(kb, &solid_handle, &edge_ids, params.radius)?;
+            Ok(result)
+        }
+
+        Operation::StaticSolid { result, source } => match result {
+            Some(result) => Ok((**result).clone()),
+            None => replay_baked_chain(feature, source, kb),
+        },
+    }
+}
+
+/// Edges an auto-fillet rounds: every convex crease, plus concave ones
+/// unless `only_convex` is set. Tangent joins and seams are never sharp.
+///
+/// Edges are ordered longest first, so a `max_edges` cap keeps the most
+/// prominent edges and drops the short ones most likely to be consumed by
+/// a neighbouring fillet.
+fn auto_fillet_edges(
+    introspect: &dyn KernelIntrospect,
+    solid: &KernelSolidHandle,
+    params: &AutoFilletParams,
+) -> Vec<KernelId> {
+    let mut edges: Vec<(KernelId, f64)> = introspect
+        .list_edges(solid)
+        .into_iter()
+        .filter(|&edge| match introspect.edge_convexity(edge) {
+            Some(Convexity::Convex) => true,
+            Some(Convexity::Concave) => !params.only_convex,
+            _ => false,
+        })
+        .map(|edge| {
+            let length = introspect
+                .compute_signature(edge, TopoKind::Edge)
+                .length
+                .unwrap_or(0.0);
+            (edge, length)
+        })
+        .collect();
+    edges.sort_by(|a, b| b.1.total_cmp(&a.1));
+    if let Some(max) = params.max_edges {
+        edges.truncate(max);
     }
+    edges.into_iter().map(|(edge, _)| edge).collect()
+}
+
+/// Rebuild a baked feature's original chain on its own and return the
+/// result of the baked feature itself.
+fn replay_baked_chain(
+    feature: &Feature,
+    source: &[Feature],
+    kb: &mut dyn KernelBundle,
+) -> Result<OpResult, EngineError> {
+    let chain = FeatureTree {
+        features: source.to_vec(),
+        active_index: None,
+    };
+    let mut state = rebuild(&chain, kb, 0, &HashMap::new());
+    state
+        .feature_results
+        .remove(&feature.id)
+        .ok_or_else(|| EngineError::RebuildFailed {
+            feature_name: feature.name.clone(),
+            reason: state.errors.first().map_or_else(
+                || "baked chain produced no result".to_string(),
+                |(_, e)| format!("replaying baked chain: {}", e),
+            ),
+        })
 }
 
 /// Find the most recent solid handle from a feature's references.
@@ -315,18 +468,21 @@ fn find_latest_solid_handle(
 
 /// Find the most recent solid handle from features built before the given feature.
 ///
-/// Walks backwards through the feature tree to find the latest OpResult with a Main output.
-fn find_most_recent_solid(
+/// Walks backwards through the feature tree from just before `current_feature`
+/// to find the latest OpResult with a Main output, so results cached for
+/// later features are never picked. Returns that OpResult alongside the handle.
+fn find_most_recent_solid<'a>(
     current_feature: &Feature,
-    feature_results: &HashMap<Uuid, OpResult>,
+    feature_results: &'a HashMap<Uuid, OpResult>,
     tree: &FeatureTree,
-) -> Option<kernel_fork::KernelSolidHandle> {
+) -> Option<(kernel_fork::KernelSolidHandle, &'a OpResult)> {
     let active = tree.active_features();
+    let before = active
+        .iter()
+        .position(|f| f.id == current_feature.id)
+        .unwrap_or(active.len());
     // Walk backwards from the current feature
-    for feature in active.iter().rev() {
-        if feature.id == current_feature.id {
-            continue;
-        }
+    for feature in active[..before].iter().rev() {
         if feature.suppressed {
             continue;
         }
@@ -337,7 +493,7 @@ fn find_most_recent_solid(
         if let Some(result) = feature_results.get(&feature.id) {
             for (key, body_output) in &result.outputs {
                 if *key == OutputKey::Main {
-                    return Some(body_output.handle.clone());
+                    return Some((body_output.handle.clone(), result));
                 }
             }
         }
@@ -345,6 +501,19 @@ fn find_most_recent_solid(
     None
 }
 
+/// Role assignments of the feature a boolean operand refers to.
+fn operand_roles<'a>(
+    geom_ref: &waffle_types::GeomRef,
+    feature_results: &'a HashMap<Uuid, OpResult>,
+) -> &'a [(kernel_fork::KernelId, waffle_types::Role)] {
+    match &geom_ref.anchor {
+        waffle_types::Anchor::FeatureOutput { feature_id, .. } => feature_results
+            .get(feature_id)
+            .map_or(&[], |result| &result.provenance.role_assignments),
+        _ => &[],
+    }
+}
+
 /// Find the Sketch data from a feature in the tree by sketch feature ID.
 fn find_sketch_in_tree(sketch_id: Uuid, tree: &FeatureTree) -> Result<&Sketch, EngineError> {
     for feature in &tree.features {
@@ -1,8 +1,157 @@
 use uuid::Uuid;
 
-use crate::types::{EngineError, Feature, FeatureTree, Operation};
+use waffle_types::{Anchor, GeomRef};
+
+use crate::types::{
+    BooleanOp, EngineError, Feature, FeatureDescription, FeatureTree, Operation, ParameterValue,
+};
 
 impl FeatureTree {
+    /// Describe every feature for external tooling: kind, referenced feature
+    /// indices, scalar parameters and suppression.
+    ///
+    /// This is an introspection view, not a serialization format — sketch
+    /// geometry and GeomRef details are summarized, not reproduced.
+    pub fn describe(&self) -> Vec<FeatureDescription> {
+        self.features
+            .iter()
+            .enumerate()
+            .map(|(index, feature)| {
+                let mut refs: Vec<Uuid> = feature
+                    .references
+                    .iter()
+                    .filter_map(anchor_feature)
+                    .collect();
+
+                let (kind, parameters) = match &feature.operation {
+                    Operation::Sketch { sketch } => (
+                        "Sketch",
+                        vec![
+                            ("entities", ParameterValue::Count(sketch.entities.len())),
+                            (
+                                "constraints",
+                                ParameterValue::Count(sketch.constraints.len()),
+                            ),
+                            (
+                                "profiles",
+                                ParameterValue::Count(sketch.solved_profiles.len()),
+                            ),
+                        ],
+                    ),
+                    Operation::Extrude { params } => {
+                        refs.push(params.sketch_id);
+                        refs.extend(params.target_body.iter().filter_map(anchor_feature));
+                        let mut parameters = vec![
+                            ("profile_index", ParameterValue::Count(params.profile_index)),
+                            ("depth", ParameterValue::Number(params.depth)),
+                            ("symmetric", ParameterValue::Bool(params.symmetric)),
+                            ("cut", ParameterValue::Bool(params.cut)),
+                        ];
+                        if let Some(direction) = params.direction {
+                            parameters.push(("direction", ParameterValue::Vector(direction)));
+                        }
+                        ("Extrude", parameters)
+                    }
+                    Operation::Revolve { params } => {
+                        refs.push(params.sketch_id);
+                        (
+                            "Revolve",
+                            vec![
+                                ("profile_index", ParameterValue::Count(params.profile_index)),
+                                ("axis_origin", ParameterValue::Vector(params.axis_origin)),
+                                (
+                                    "axis_direction",
+                                    ParameterValue::Vector(params.axis_direction),
+                                ),
+                                ("angle", ParameterValue::Number(params.angle)),
+                            ],
+                        )
+                    }
+                    Operation::Fillet { params } => {
+                        refs.extend(params.edges.iter().filter_map(anchor_feature));
+                        (
+                            "Fillet",
+                            vec![
+                                ("edges", ParameterValue::Count(params.edges.len())),
+                                ("radius", ParameterValue::Number(params.radius)),
+                            ],
+                        )
+                    }
+                    Operation::Chamfer { params } => {
+                        refs.extend(params.edges.iter().filter_map(anchor_feature));
+                        (
+                            "Chamfer",
+                            vec![
+                                ("edges", ParameterValue::Count(params.edges.len())),
+                                ("distance", ParameterValue::Number(params.distance)),
+                            ],
+                        )
+                    }
+                    Operation::Shell { params } => {
+                        refs.extend(params.faces_to_remove.iter().filter_map(anchor_feature));
+                        (
+                            "Shell",
+                            vec![
+                                (
+                                    "faces_to_remove",
+                                    ParameterValue::Count(params.faces_to_remove.len()),
+                                ),
+                                ("thickness", ParameterValue::Number(params.thickness)),
+                            ],
+                        )
+                    }
+                    Operation::BooleanCombine { params } => {
+                        refs.extend(anchor_feature(&params.body_a));
+                        refs.extend(anchor_feature(&params.body_b));
+                        let op = match params.operation {
+                            BooleanOp::Union => "Union",
+                            BooleanOp::Subtract => "Subtract",
+                            BooleanOp::Intersect => "Intersect",
+                        };
+                        (
+                            "BooleanCombine",
+                            vec![("operation", ParameterValue::Text(op.to_string()))],
+                        )
+                    }
+                    Operation::AutoFillet { params } => {
+                        refs.extend(anchor_feature(&params.body));
+                        let mut parameters = vec![
+                            ("radius", ParameterValue::Number(params.radius)),
+                            ("only_convex", ParameterValue::Bool(params.only_convex)),
+                        ];
+                        if let Some(max_edges) = params.max_edges {
+                            parameters.push(("max_edges", ParameterValue::Count(max_edges)));
+                        }
+                        ("AutoFillet", parameters)
+                    }
+                    Operation::StaticSolid { source, .. } => (
+                        "StaticSolid",
+                        vec![("baked_features", ParameterValue::Count(source.len()))],
+                    ),
+                };
+
+                let mut references: Vec<usize> = refs
+                    .into_iter()
+                    .filter_map(|id| self.feature_index(id))
+                    .collect();
+                references.sort_unstable();
+                references.dedup();
+                FeatureDescription {
+                    index,
+                    id: feature.id,
+                    name: feature.name.clone(),
+                    kind: kind.to_string(),
+                    references,
+                    parameters: parameters
+                        .into_iter()
+                        .map(|(name, value)| (name.to_string(), value))
+                        .collect(),
+                    suppressed: feature.suppressed,
+                }
+            })
+            .collect()
+    }
+
     /// Add a feature at the end of the tree (or at the active index).
     pub fn add_feature(&mut self, name: String, operation: Operation) -> Uuid {
         let id = Uuid::new_v4();
@@ -90,6 +239,40 @@ impl FeatureTree {
         Ok(old_name)
     }
 
+    /// Set a numeric parameter of the feature named `feature_name`.
+    ///
+    /// `parameter` is one of the `Number` parameters [`describe`](Self::describe)
+    /// reports for that feature's kind, e.g. `depth` or `radius`.
+    pub fn set_parameter(
+        &mut self,
+        feature_name: &str,
+        parameter: &str,
+        value: f64,
+    ) -> Result<(), EngineError> {
+        let feature = self
+            .features
+            .iter_mut()
+            .find(|f| f.name == feature_name)
+            .ok_or_else(|| EngineError::InvalidParameter {
+                reason: format!("no feature named '{}'", feature_name),
+            })?;
+        let slot = match (&mut feature.operation, parameter) {
+            (Operation::Extrude { params }, "depth") => &mut params.depth,
+            (Operation::Revolve { params }, "angle") => &mut params.angle,
+            (Operation::Fillet { params }, "radius") => &mut params.radius,
+            (Operation::AutoFillet { params }, "radius") => &mut params.radius,
+            (Operation::Chamfer { params }, "distance") => &mut params.distance,
+            (Operation::Shell { params }, "thickness") => &mut params.thickness,
+            _ => {
+                return Err(EngineError::InvalidParameter {
+                    reason: format!("feature '{}' has no parameter '{}'", feature_name, parameter),
+                })
+            }
+        };
+        *slot = value;
+        Ok(())
+    }
+
     /// Find a feature by ID.
     pub fn find_feature(&self, id: Uuid) -> Option<&Feature> {
         self.features.iter().find(|f| f.id == id)
@@ -105,3 +288,11 @@ impl FeatureTree {
         self.features.iter().position(|f| f.id == id)
     }
 }
+
+/// The feature a GeomRef is anchored to, if it points at a feature output.
+fn anchor_feature(geom_ref: &GeomRef) -> Option<Uuid> {
+    match geom_ref.anchor {
+        Anchor::FeatureOutput { feature_id, .. } => Some(feature_id),
+        Anchor::Datum { .. } => None,
+    }
+}
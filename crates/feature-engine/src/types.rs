@@ -1,3 +1,4 @@
+use modeling_ops::OpResult;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use waffle_types::{GeomRef, Sketch};
@@ -62,6 +63,18 @@ pub enum Operation {
     Chamfer { params: ChamferParams },
     Shell { params: ShellParams },
     BooleanCombine { params: BooleanParams },
+    AutoFillet { params: AutoFilletParams },
+    /// A feature frozen by [`Engine::bake_feature`](crate::Engine::bake_feature).
+    ///
+    /// `result` is the geometry computed when it was baked and is only
+    /// valid in that kernel session, so it is not serialized. `source` is
+    /// the feature and its upstream chain as they were, in tree order, and
+    /// is replayed when there is no cached result (e.g. after a load).
+    StaticSolid {
+        #[serde(skip)]
+        result: Option<Box<OpResult>>,
+        source: Vec<Feature>,
+    },
 }
 
 /// Parameters for an extrude operation.
@@ -93,6 +106,22 @@ pub struct FilletParams {
     pub radius: f64,
 }
 
+/// Parameters for filleting every sharp edge of a body at once.
+///
+/// Edges are chosen by [`KernelIntrospect::edge_convexity`] when the feature
+/// rebuilds, so the selection follows the body as upstream features change.
+///
+/// [`KernelIntrospect::edge_convexity`]: kernel_fork::KernelIntrospect::edge_convexity
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoFilletParams {
+    pub body: GeomRef,
+    pub radius: f64,
+    /// Round only convex edges, leaving inside corners sharp.
+    pub only_convex: bool,
+    /// Fillet at most this many edges, longest first.
+    pub max_edges: Option<usize>,
+}
+
 /// Parameters for a chamfer operation.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ChamferParams {
@@ -124,6 +153,49 @@ pub enum BooleanOp {
     Intersect,
 }
 
+/// Read-only, structured summary of one feature. See [`FeatureTree::describe`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeatureDescription {
+    /// Position in the tree.
+    pub index: usize,
+    pub id: Uuid,
+    pub name: String,
+    /// Operation kind, e.g. `"Sketch"` or `"Extrude"`.
+    pub kind: String,
+    /// Indices of earlier features this one reads from.
+    pub references: Vec<usize>,
+    /// Scalar parameters by name, in a fixed per-kind order.
+    pub parameters: Vec<(String, ParameterValue)>,
+    pub suppressed: bool,
+}
+
+/// A single parameter value in a [`FeatureDescription`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value")]
+pub enum ParameterValue {
+    Number(f64),
+    Count(usize),
+    Bool(bool),
+    Vector([f64; 3]),
+    Text(String),
+}
+
+/// Summary of a feature's state after the last rebuild.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status")]
+pub enum FeatureStatus {
+    /// Built without warnings.
+    Ok,
+    /// Built, but produced warnings.
+    Warning { messages: Vec<String> },
+    /// Failed to rebuild.
+    Error { message: String },
+    /// Suppressed by the user; not rebuilt.
+    Suppressed,
+    /// Beyond the rollback index; not rebuilt.
+    RolledBack,
+}
+
 /// Errors from the feature engine.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum EngineError {
@@ -156,4 +228,13 @@ pub enum EngineError {
 
     #[error("nothing to redo")]
     NothingToRedo,
+
+    #[error("feature {id} has no result to bake")]
+    NothingToBake { id: Uuid },
+
+    #[error("invalid parameter: {reason}")]
+    InvalidParameter { reason: String },
+
+    #[error("failed to parse engine state: {reason}")]
+    ParseError { reason: String },
 }
@@ -33,6 +33,14 @@ pub enum Command {
         old_name: String,
         new_name: String,
     },
+    BakeFeature {
+        feature_id: Uuid,
+        old_operation: Box<Operation>,
+        new_operation: Box<Operation>,
+        /// Upstream features removed by the bake, with their positions
+        /// before it, in ascending order.
+        absorbed: Vec<(usize, Feature)>,
+    },
 }
 
 /// Two-stack undo/redo history.
@@ -1,6 +1,6 @@
 use feature_engine::types::*;
 use feature_engine::Engine;
-use kernel_fork::MockKernel;
+use kernel_fork::{Kernel, KernelIntrospect, MockKernel};
 use uuid::Uuid;
 use waffle_types::*;
 
@@ -167,6 +167,70 @@ fn tree_rollback_limits_active_features() {
     assert_eq!(tree.active_features().len(), 3);
 }
 
+#[test]
+fn tree_describe_constrained_bracket() {
+    let mut sketch_op = make_sketch_op();
+    if let Operation::Sketch { sketch } = &mut sketch_op {
+        // An L-bracket outline: 6 corners, 6 lines, 7 constraints.
+        let corners = [
+            (0.0, 0.0),
+            (4.0, 0.0),
+            (4.0, 1.0),
+            (1.0, 1.0),
+            (1.0, 3.0),
+            (0.0, 3.0),
+        ];
+        sketch.entities = corners
+            .iter()
+            .enumerate()
+            .map(|(i, &(x, y))| SketchEntity::Point {
+                id: i as u32 + 1,
+                x,
+                y,
+                construction: false,
+            })
+            .chain((0..6u32).map(|i| SketchEntity::Line {
+                id: 10 + i,
+                start_id: i + 1,
+                end_id: (i + 1) % 6 + 1,
+                construction: false,
+            }))
+            .collect();
+        sketch.constraints = vec![
+            SketchConstraint::Horizontal { entity: 10 },
+            SketchConstraint::Vertical { entity: 11 },
+            SketchConstraint::Horizontal { entity: 12 },
+            SketchConstraint::Vertical { entity: 13 },
+            SketchConstraint::Horizontal { entity: 14 },
+            SketchConstraint::Vertical { entity: 15 },
+            SketchConstraint::Dragged { point: 1 },
+        ];
+    }
+    let mut tree = FeatureTree::new();
+    let sketch_id = tree.add_feature("Bracket Sketch".to_string(), sketch_op);
+    tree.add_feature("Extrude 1".to_string(), make_extrude_op(sketch_id));
+
+    let described = tree.describe();
+    assert_eq!(described.len(), 2);
+
+    assert_eq!(described[0].kind, "Sketch");
+    assert!(described[0].references.is_empty());
+    assert!(described[0]
+        .parameters
+        .contains(&("constraints".to_string(), ParameterValue::Count(7))));
+
+    assert_eq!(described[1].kind, "Extrude");
+    assert_eq!(described[1].references, vec![0]);
+    assert!(described[1]
+        .parameters
+        .contains(&("depth".to_string(), ParameterValue::Number(5.0))));
+    assert!(!described[1].suppressed);
+
+    // The description is plain serde data.
+    let json = serde_json::to_string(&described).unwrap();
+    assert!(json.contains("\"kind\":\"Extrude\""));
+}
+
 // ── Engine Integration Tests ───────────────────────────────────────────────
 
 #[test]
@@ -296,6 +360,103 @@ fn engine_rollback() {
     assert_eq!(engine.tree.active_features().len(), 2);
 }
 
+#[test]
+fn feature_statuses_report_error_and_suppressed() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let s_id = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e_id = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s_id), &mut kernel)
+        .unwrap();
+    let bad_id = engine
+        .add_feature(
+            "Extrude 2".to_string(),
+            make_extrude_op(Uuid::new_v4()),
+            &mut kernel,
+        )
+        .unwrap();
+    let sup_id = engine
+        .add_feature("Sketch 2".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    engine.set_suppressed(sup_id, true, &mut kernel).unwrap();
+
+    let statuses = engine.feature_statuses();
+    assert_eq!(statuses.len(), 4);
+    assert_eq!(statuses[0], (s_id, FeatureStatus::Ok));
+    assert_eq!(statuses[1], (e_id, FeatureStatus::Ok));
+    assert_eq!(statuses[2].0, bad_id);
+    assert!(matches!(statuses[2].1, FeatureStatus::Error { .. }));
+    assert_eq!(statuses[3], (sup_id, FeatureStatus::Suppressed));
+}
+
+#[test]
+fn feature_statuses_warn_on_clockwise_profile() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let mut clockwise = make_sketch_op();
+    if let Operation::Sketch { sketch } = &mut clockwise {
+        sketch.solved_profiles[0].entity_ids = vec![1, 4, 3, 2];
+    }
+    let cw_sketch = engine
+        .add_feature("Sketch CW".to_string(), clockwise, &mut kernel)
+        .unwrap();
+    let cw_extrude = engine
+        .add_feature(
+            "Extrude CW".to_string(),
+            make_extrude_op(cw_sketch),
+            &mut kernel,
+        )
+        .unwrap();
+    let ccw_sketch = engine
+        .add_feature("Sketch CCW".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let ccw_extrude = engine
+        .add_feature(
+            "Extrude CCW".to_string(),
+            make_extrude_op(ccw_sketch),
+            &mut kernel,
+        )
+        .unwrap();
+
+    let diagnostics = &engine.get_result(cw_extrude).unwrap().diagnostics;
+    assert_eq!(
+        diagnostics.operation_warnings,
+        vec![modeling_ops::OperationWarning::ClockwiseProfileNormalized]
+    );
+    assert!(engine
+        .get_result(ccw_extrude)
+        .unwrap()
+        .diagnostics
+        .operation_warnings
+        .is_empty());
+
+    let statuses = engine.feature_statuses();
+    assert!(matches!(statuses[1].1, FeatureStatus::Warning { .. }));
+    assert_eq!(statuses[3], (ccw_extrude, FeatureStatus::Ok));
+}
+
+#[test]
+fn feature_statuses_mark_rolled_back_features() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    engine
+        .add_feature("A".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let b_id = engine
+        .add_feature("B".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+
+    engine.set_rollback(Some(0), &mut kernel);
+    let statuses = engine.feature_statuses();
+    assert_eq!(statuses[0].1, FeatureStatus::Ok);
+    assert_eq!(statuses[1], (b_id, FeatureStatus::RolledBack));
+}
+
 // ── GeomRef Resolution Tests ──────────────────────────────────────────────
 
 #[test]
@@ -538,6 +699,126 @@ fn rebuild_after_edit_updates_results() {
     assert_eq!(result.unwrap().outputs.len(), 1);
 }
 
+#[test]
+fn edit_keeps_results_of_unrelated_features() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let a = engine
+        .add_feature("Sketch A".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let b = engine
+        .add_feature("Extrude B".to_string(), make_extrude_op(a), &mut kernel)
+        .unwrap();
+    let s2 = engine
+        .add_feature("Sketch 2".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let c = engine
+        .add_feature("Extrude C".to_string(), make_extrude_op(s2), &mut kernel)
+        .unwrap();
+    assert_eq!(engine.affected_features(a), vec![a, b]);
+    assert_eq!(engine.affected_features(s2), vec![s2, c]);
+
+    // Each evaluation of an extrude makes a new solid with new face IDs.
+    let faces = |engine: &Engine, kernel: &MockKernel, id: Uuid| {
+        let handle = &engine.get_result(id).unwrap().outputs[0].1.handle;
+        kernel.list_faces(handle)
+    };
+    let (b_before, c_before) = (faces(&engine, &kernel, b), faces(&engine, &kernel, c));
+
+    engine
+        .edit_feature(a, make_sketch_op(), &mut kernel)
+        .unwrap();
+    assert!(engine.errors.is_empty(), "{:?}", engine.errors);
+    assert_ne!(faces(&engine, &kernel, b), b_before);
+    assert_eq!(faces(&engine, &kernel, c), c_before);
+}
+
+#[test]
+fn edited_cut_subtracts_from_the_body_before_it() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let a = engine
+        .add_feature("Sketch A".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    engine
+        .add_feature("Extrude B".to_string(), make_extrude_op(a), &mut kernel)
+        .unwrap();
+    let x = engine
+        .add_feature("Sketch X".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let mut cut_op = make_extrude_op(x);
+    if let Operation::Extrude { params } = &mut cut_op {
+        params.cut = true;
+        params.depth = 1.0;
+    }
+    let cut = engine
+        .add_feature("Cut X".to_string(), cut_op, &mut kernel)
+        .unwrap();
+    let s2 = engine
+        .add_feature("Sketch 2".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let mut tall_op = make_extrude_op(s2);
+    if let Operation::Extrude { params } = &mut tall_op {
+        params.depth = 20.0;
+    }
+    engine
+        .add_feature("Extrude C".to_string(), tall_op, &mut kernel)
+        .unwrap();
+
+    // The mock subtract returns a copy of the target body, so the cut's
+    // face geometry shows which body it subtracted from.
+    let cut_faces = |engine: &Engine, kernel: &MockKernel| {
+        let handle = &engine.get_result(cut).unwrap().outputs[0].1.handle;
+        kernel
+            .list_faces(handle)
+            .into_iter()
+            .map(|f| {
+                let sig = kernel.compute_signature(f, TopoKind::Face);
+                (sig.area, sig.centroid)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Extrude C keeps its cached result through the edit, and the cut must
+    // still not see it.
+    engine
+        .edit_feature(x, make_sketch_op(), &mut kernel)
+        .unwrap();
+    assert!(engine.errors.is_empty(), "{:?}", engine.errors);
+    let incremental = cut_faces(&engine, &kernel);
+
+    engine.rebuild_from_scratch(&mut kernel);
+    assert_eq!(incremental, cut_faces(&engine, &kernel));
+    assert!(incremental
+        .iter()
+        .all(|(_, centroid)| centroid.unwrap()[2] <= 5.0));
+}
+
+#[test]
+fn affected_features_forgets_suppressed_and_removed_features() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let a = engine
+        .add_feature("Sketch A".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let b = engine
+        .add_feature("Extrude B".to_string(), make_extrude_op(a), &mut kernel)
+        .unwrap();
+    let c = engine
+        .add_feature("Extrude C".to_string(), make_extrude_op(a), &mut kernel)
+        .unwrap();
+    assert_eq!(engine.affected_features(a), vec![a, b, c]);
+
+    // A suppressed feature reads nothing until it is evaluated again.
+    engine.set_suppressed(b, true, &mut kernel).unwrap();
+    assert_eq!(engine.affected_features(a), vec![a, c]);
+    engine.set_suppressed(b, false, &mut kernel).unwrap();
+    assert_eq!(engine.affected_features(a), vec![a, b, c]);
+
+    engine.remove_feature(c, &mut kernel).unwrap();
+    assert_eq!(engine.affected_features(a), vec![a, b]);
+}
+
 #[test]
 fn rebuild_error_on_missing_sketch() {
     let mut engine = Engine::new();
@@ -734,6 +1015,140 @@ fn undo_empty_returns_error() {
     assert!(matches!(result, Err(EngineError::NothingToUndo)));
 }
 
+#[test]
+fn bake_feature_reuses_result_and_undoes() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    let before = engine.get_result(e1).unwrap().clone();
+    let mesh_before = kernel.tessellate(&before.outputs[0].1.handle, 0.1).unwrap();
+
+    engine.bake_feature(e1, &mut kernel).unwrap();
+    assert_eq!(engine.tree.features.len(), 1);
+    assert_eq!(engine.tree.features[0].id, e1);
+    assert!(matches!(
+        &engine.tree.features[0].operation,
+        Operation::StaticSolid { source, .. } if source.len() == 2
+    ));
+
+    // Rebuilds never solve sketches (solved geometry is stored on the
+    // sketch op), so there is no solve count to watch. What baking must
+    // prevent is re-running the chain: the sketch is not rebuilt at all,
+    // and the extrude's kernel ids survive, where re-running it would mint
+    // new ones. Check both across repeated rebuilds.
+    for _ in 0..2 {
+        engine.rebuild_from_scratch(&mut kernel);
+        assert!(engine.errors.is_empty(), "{:?}", engine.errors);
+        assert!(engine.get_result(s1).is_none());
+        let after = engine.get_result(e1).unwrap();
+        assert_eq!(
+            after.provenance.role_assignments,
+            before.provenance.role_assignments
+        );
+        let mesh_after = kernel.tessellate(&after.outputs[0].1.handle, 0.1).unwrap();
+        assert_eq!(mesh_after.vertices, mesh_before.vertices);
+        assert_eq!(mesh_after.indices, mesh_before.indices);
+    }
+
+    engine.undo(&mut kernel).unwrap();
+    let ids: Vec<Uuid> = engine.tree.features.iter().map(|f| f.id).collect();
+    assert_eq!(ids, vec![s1, e1]);
+    assert!(matches!(
+        engine.tree.features[1].operation,
+        Operation::Extrude { .. }
+    ));
+    assert!(engine.get_result(e1).is_some());
+
+    engine.redo(&mut kernel).unwrap();
+    assert_eq!(engine.tree.features.len(), 1);
+    assert!(engine.get_result(s1).is_none());
+}
+
+#[test]
+fn baked_feature_replays_its_chain_after_reload() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    engine.bake_feature(e1, &mut kernel).unwrap();
+
+    // The cached geometry is session-only and is dropped on save.
+    let json = serde_json::to_string(&engine.tree).unwrap();
+    let mut reloaded = Engine::new();
+    reloaded.tree = serde_json::from_str(&json).unwrap();
+    let mut fresh = MockKernel::new();
+    reloaded.rebuild_from_scratch(&mut fresh);
+
+    assert!(reloaded.errors.is_empty(), "{:?}", reloaded.errors);
+    assert_eq!(reloaded.get_result(e1).unwrap().outputs.len(), 1);
+}
+
+#[test]
+fn engine_json_round_trip_rebuilds_same_solid() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    engine.set_rollback(Some(1), &mut kernel);
+
+    let mut loaded = Engine::from_json(&engine.to_json()).unwrap();
+    assert_eq!(loaded.tree.active_index, Some(1));
+    assert!(!loaded.can_undo());
+    let mut fresh = MockKernel::new();
+    loaded.rebuild_from_scratch(&mut fresh);
+    assert!(loaded.errors.is_empty(), "{:?}", loaded.errors);
+
+    let counts = |kernel: &MockKernel, engine: &Engine| {
+        let handle = &engine.get_result(e1).unwrap().outputs[0].1.handle;
+        (
+            kernel.list_faces(handle).len(),
+            kernel.list_edges(handle).len(),
+            kernel.list_vertices(handle).len(),
+        )
+    };
+    assert_eq!(counts(&fresh, &loaded), counts(&kernel, &engine));
+    assert_eq!(counts(&fresh, &loaded), (6, 12, 8));
+}
+
+#[test]
+fn engine_from_json_rejects_malformed_input() {
+    assert!(matches!(
+        Engine::from_json("{\"features\": 3}"),
+        Err(EngineError::ParseError { .. })
+    ));
+}
+
+#[test]
+fn bake_feature_without_result_fails() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let e1 = engine
+        .add_feature(
+            "Extrude 1".to_string(),
+            make_extrude_op(Uuid::new_v4()),
+            &mut kernel,
+        )
+        .unwrap();
+    assert!(matches!(
+        engine.bake_feature(e1, &mut kernel),
+        Err(EngineError::NothingToBake { id }) if id == e1
+    ));
+}
+
 // ── M7: Rollback Integration Tests ──────────────────────────────────────
 
 #[test]
@@ -801,6 +1216,72 @@ fn rollback_is_not_undoable() {
     assert!(engine.get_result(id).is_none());
 }
 
+/// Highest z of a feature's output in a set of rebuild results.
+fn top_of(
+    kernel: &mut MockKernel,
+    results: &std::collections::HashMap<Uuid, modeling_ops::OpResult>,
+    id: Uuid,
+) -> f64 {
+    let mesh = kernel
+        .tessellate(&results[&id].outputs[0].1.handle, 0.1)
+        .unwrap();
+    kernel_fork::mesh::bounding_box(&mesh).unwrap().1[2]
+}
+
+#[test]
+fn parameters_csv_builds_one_variant_per_row() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let width = engine
+        .add_feature("Width".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    let s2 = engine
+        .add_feature("Sketch 2".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let height = engine
+        .add_feature("Height".to_string(), make_extrude_op(s2), &mut kernel)
+        .unwrap();
+
+    let csv = "Width.depth,Height.depth\n2,3\n4.5,8\n";
+    let variants = engine.apply_parameters_csv(csv, &mut kernel);
+    assert_eq!(variants.len(), 2);
+
+    for (variant, expected) in variants.iter().zip([(2.0, 3.0), (4.5, 8.0)]) {
+        let results = variant.as_ref().unwrap();
+        assert!((top_of(&mut kernel, results, width) - expected.0).abs() < 1e-9);
+        assert!((top_of(&mut kernel, results, height) - expected.1).abs() < 1e-9);
+    }
+
+    // The tree is left as it was.
+    match &engine.tree.find_feature(width).unwrap().operation {
+        Operation::Extrude { params } => assert_eq!(params.depth, 5.0),
+        other => panic!("expected extrude, got {:?}", other),
+    }
+    assert!(engine.errors.is_empty());
+}
+
+#[test]
+fn parameters_csv_reports_bad_rows() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+
+    let variants = engine.apply_parameters_csv("Extrude 1.depth\n2\nwide\n", &mut kernel);
+    assert!(variants[0].is_ok());
+    assert!(matches!(variants[1], Err(EngineError::InvalidParameter { .. })));
+
+    let variants = engine.apply_parameters_csv("Extrude 1.radius\n2\n", &mut kernel);
+    assert!(matches!(variants[0], Err(EngineError::InvalidParameter { .. })));
+}
+
 // ── Helper: make_extrude_op with custom depth ────────────────────────────
 
 fn make_extrude_op_depth(sketch_id: Uuid, depth: f64) -> Operation {
@@ -891,6 +1372,47 @@ fn full_pipeline_sketch_extrude_boolean_rebuild() {
     assert!(!bool_result.provenance.role_assignments.is_empty());
 }
 
+#[test]
+fn boolean_faces_inherit_operand_roles() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    let s2 = engine
+        .add_feature("Sketch 2".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e2 = engine
+        .add_feature("Extrude 2".to_string(), make_extrude_op(s2), &mut kernel)
+        .unwrap();
+    let bool_id = engine
+        .add_feature(
+            "Boolean Union".to_string(),
+            make_boolean_union(e1, e2),
+            &mut kernel,
+        )
+        .unwrap();
+
+    let face_count = engine
+        .get_result(bool_id)
+        .unwrap()
+        .provenance
+        .face_origins
+        .len();
+    assert!(face_count > 0);
+    for i in 0..face_count {
+        let provenance = engine.face_provenance(bool_id, i).unwrap();
+        assert_ne!(provenance.origin, FaceOrigin::FromIntersection);
+        assert!(provenance.role.is_some(), "face {} lost its role", i);
+    }
+    assert!(engine.face_provenance(bool_id, face_count).is_none());
+    assert!(engine.face_provenance(e1, 0).is_none());
+}
+
 #[test]
 fn full_pipeline_edit_early_feature_rebuilds_downstream() {
     let mut engine = Engine::new();
@@ -1459,6 +1981,97 @@ fn fillet_resolve_geomref_produces_kernel_id() {
     assert!(!side_faces.is_empty(), "Extrude should have SideFace roles");
 }
 
+/// Create an auto-fillet operation on an extrude's main body.
+fn make_auto_fillet_op(extrude_id: Uuid, max_edges: Option<usize>) -> Operation {
+    Operation::AutoFillet {
+        params: AutoFilletParams {
+            body: GeomRef {
+                kind: TopoKind::Face,
+                anchor: Anchor::FeatureOutput {
+                    feature_id: extrude_id,
+                    output_key: OutputKey::Main,
+                },
+                selector: Selector::Role {
+                    role: Role::EndCapPositive,
+                    index: 0,
+                },
+                policy: ResolvePolicy::Strict,
+            },
+            radius: 0.5,
+            only_convex: true,
+            max_edges,
+        },
+    }
+}
+
+#[test]
+fn auto_fillet_rounds_every_box_edge() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    let f1 = engine
+        .add_feature(
+            "Auto Fillet".to_string(),
+            make_auto_fillet_op(e1, None),
+            &mut kernel,
+        )
+        .unwrap();
+
+    assert!(engine.errors.is_empty(), "{:?}", engine.errors);
+    let result = engine.get_result(f1).unwrap();
+    let handle = &result.outputs[0].1.handle;
+    // Each filleted edge adds one blend face to the box's six, and trades
+    // itself for two boundary edges with a new vertex at each end.
+    assert_eq!(kernel.list_faces(handle).len(), 6 + 12);
+    assert_eq!(kernel.list_edges(handle).len(), 12 + 12);
+    assert_eq!(kernel.list_vertices(handle).len(), 8 + 2 * 12);
+    assert_eq!(fillet_role_count(result), 12);
+}
+
+/// Number of faces an op's provenance tags as fillet faces.
+fn fillet_role_count(result: &modeling_ops::OpResult) -> usize {
+    result
+        .provenance
+        .role_assignments
+        .iter()
+        .filter(|(_, r)| matches!(r, Role::FilletFace { .. }))
+        .count()
+}
+
+#[test]
+fn auto_fillet_respects_max_edges() {
+    let mut engine = Engine::new();
+    let mut kernel = MockKernel::new();
+
+    let s1 = engine
+        .add_feature("Sketch 1".to_string(), make_sketch_op(), &mut kernel)
+        .unwrap();
+    let e1 = engine
+        .add_feature("Extrude 1".to_string(), make_extrude_op(s1), &mut kernel)
+        .unwrap();
+    let f1 = engine
+        .add_feature(
+            "Auto Fillet".to_string(),
+            make_auto_fillet_op(e1, Some(4)),
+            &mut kernel,
+        )
+        .unwrap();
+
+    assert!(engine.errors.is_empty(), "{:?}", engine.errors);
+    let result = engine.get_result(f1).unwrap();
+    let handle = &result.outputs[0].1.handle;
+    assert_eq!(kernel.list_faces(handle).len(), 6 + 4);
+    assert_eq!(kernel.list_edges(handle).len(), 12 + 4);
+    assert_eq!(kernel.list_vertices(handle).len(), 8 + 2 * 4);
+    assert_eq!(fillet_role_count(result), 4);
+}
+
 // ── M10: Performance Benchmarks ─────────────────────────────────────────
 
 /// Build a tree of N sketch+extrude pairs and return rebuild time.
@@ -0,0 +1,70 @@
+use feature_engine::types::FeatureTree;
+use kernel_fork::{Kernel, RenderMesh, TruckKernel};
+
+use crate::errors::ExportError;
+use crate::step_export::rebuild_final_solid;
+
+/// Chord tolerance used when tessellating for AMF export.
+const AMF_TOLERANCE: f64 = 0.01;
+
+/// Export a feature tree to AMF with a single colored material.
+///
+/// Rebuilds the model the same way as [`export_step`](crate::export_step),
+/// tessellates the final solid and encodes it with [`mesh_to_amf`].
+pub fn export_amf(
+    tree: &FeatureTree,
+    kb: &mut TruckKernel,
+    material_name: &str,
+    color: [f32; 4],
+) -> Result<String, ExportError> {
+    let handle = rebuild_final_solid(tree, kb)?;
+    let mesh = kb
+        .tessellate(&handle, AMF_TOLERANCE)
+        .map_err(|e| ExportError::TessellationFailed(format!("{}", e)))?;
+    Ok(mesh_to_amf(&mesh, material_name, color))
+}
+
+/// Encode a mesh as an AMF document with one object and one RGBA material.
+///
+/// Triangles are written flat (no curved-triangle edge data). The volume
+/// references material id 1, which carries `material_name` and `color`.
+pub fn mesh_to_amf(mesh: &RenderMesh, material_name: &str, color: [f32; 4]) -> String {
+    let mut xml = String::new();
+    xml.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str("<amf unit=\"millimeter\" version=\"1.1\">\n");
+
+    xml.push_str("  <material id=\"1\">\n");
+    xml.push_str(&format!(
+        "    <metadata type=\"name\">{}</metadata>\n",
+        escape_xml(material_name)
+    ));
+    xml.push_str(&format!(
+        "    <color><r>{}</r><g>{}</g><b>{}</b><a>{}</a></color>\n",
+        color[0], color[1], color[2], color[3]
+    ));
+    xml.push_str("  </material>\n");
+
+    xml.push_str("  <object id=\"0\">\n    <mesh>\n      <vertices>\n");
+    for v in mesh.vertices.chunks_exact(3) {
+        xml.push_str(&format!(
+            "        <vertex><coordinates><x>{}</x><y>{}</y><z>{}</z></coordinates></vertex>\n",
+            v[0], v[1], v[2]
+        ));
+    }
+    xml.push_str("      </vertices>\n      <volume materialid=\"1\">\n");
+    for t in mesh.indices.chunks_exact(3) {
+        xml.push_str(&format!(
+            "        <triangle><v1>{}</v1><v2>{}</v2><v3>{}</v3></triangle>\n",
+            t[0], t[1], t[2]
+        ));
+    }
+    xml.push_str("      </volume>\n    </mesh>\n  </object>\n</amf>\n");
+    xml
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
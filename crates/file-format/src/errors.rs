@@ -17,7 +17,7 @@ pub enum LoadError {
     MigrationFailed { from: u32, to: u32, reason: String },
 }
 
-/// Errors during STEP export.
+/// Errors during STEP or mesh export.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum ExportError {
     #[error("rebuild failed: {0}")]
@@ -26,6 +26,19 @@ pub enum ExportError {
     #[error("STEP export failed: {0}")]
     StepExportFailed(String),
 
+    #[error("tessellation failed: {0}")]
+    TessellationFailed(String),
+
     #[error("no solid available for export")]
     NoSolid,
 }
+
+/// Errors during mesh import.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ImportError {
+    #[error("OBJ line {line}: {reason}")]
+    Obj { line: usize, reason: String },
+
+    #[error("binary STL should be {expected} bytes, found {actual}")]
+    StlSize { expected: usize, actual: usize },
+}
@@ -0,0 +1,214 @@
+use base64::Engine as _;
+use kernel_fork::RenderMesh;
+use serde_json::{json, Value};
+
+/// Base color of the single default material: opaque light grey.
+const DEFAULT_BASE_COLOR: [f32; 4] = [0.8, 0.8, 0.8, 1.0];
+
+// glTF component types and buffer view targets.
+const FLOAT: u32 = 5126;
+const UNSIGNED_SHORT: u32 = 5123;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+
+/// Largest vertex count whose indices fit `UNSIGNED_SHORT`. Index 65535 is
+/// reserved for primitive restart, so it is not usable.
+const MAX_SHORT_VERTICES: usize = 65535;
+
+// GLB container constants.
+const GLB_MAGIC: u32 = 0x4654_6C67;
+const GLB_VERSION: u32 = 2;
+const CHUNK_JSON: u32 = 0x4E4F_534A;
+const CHUNK_BIN: u32 = 0x004E_4942;
+
+/// Encode a mesh as a self-contained glTF 2.0 document.
+///
+/// Positions, normals and indices share one buffer, embedded as a base64
+/// data URI, and the primitive uses a single default material. Indices are
+/// `UNSIGNED_SHORT` up to 65535 vertices and `UNSIGNED_INT` above. A mesh
+/// without triangles gives a valid document whose scene is empty.
+pub fn mesh_to_gltf(mesh: &RenderMesh) -> String {
+    let packed = pack(mesh);
+    let uri = packed.as_ref().map(|p| {
+        format!(
+            "data:application/octet-stream;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(&p.bin)
+        )
+    });
+    serde_json::to_string_pretty(&document(packed.as_ref(), uri))
+        .expect("glTF JSON always serializes")
+}
+
+/// Encode a mesh as a binary glTF (`.glb`) file.
+///
+/// Same document as [`mesh_to_gltf`], with the buffer stored in the GLB
+/// binary chunk instead of a data URI. Empty meshes have no binary chunk.
+pub fn mesh_to_glb(mesh: &RenderMesh) -> Vec<u8> {
+    let packed = pack(mesh);
+    let mut json =
+        serde_json::to_vec(&document(packed.as_ref(), None)).expect("glTF JSON always serializes");
+    json.resize(json.len().next_multiple_of(4), b' ');
+
+    let bin = packed.map(|p| p.bin);
+    let length = 12 + 8 + json.len() + bin.as_ref().map_or(0, |b| 8 + b.len());
+    let mut glb = Vec::with_capacity(length);
+    for word in [GLB_MAGIC, GLB_VERSION, length as u32] {
+        glb.extend_from_slice(&word.to_le_bytes());
+    }
+    glb.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    glb.extend_from_slice(&CHUNK_JSON.to_le_bytes());
+    glb.extend_from_slice(&json);
+    if let Some(bin) = bin {
+        glb.extend_from_slice(&(bin.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&CHUNK_BIN.to_le_bytes());
+        glb.extend_from_slice(&bin);
+    }
+    glb
+}
+
+/// The mesh laid out as one little-endian buffer: positions, then normals
+/// (if there is one per vertex), then indices padded to four bytes.
+struct Packed {
+    bin: Vec<u8>,
+    vertex_count: usize,
+    index_count: usize,
+    has_normals: bool,
+    index_type: u32,
+    min: [f32; 3],
+    max: [f32; 3],
+}
+
+/// `None` if the mesh has no triangles.
+fn pack(mesh: &RenderMesh) -> Option<Packed> {
+    let vertex_count = mesh.vertices.len() / 3;
+    let index_count = mesh.indices.len() / 3 * 3;
+    if vertex_count == 0 || index_count == 0 {
+        return None;
+    }
+    let has_normals = mesh.normals.len() == mesh.vertices.len();
+    let index_type = if vertex_count > MAX_SHORT_VERTICES {
+        UNSIGNED_INT
+    } else {
+        UNSIGNED_SHORT
+    };
+
+    let (mut min, mut max) = ([f32::INFINITY; 3], [f32::NEG_INFINITY; 3]);
+    for v in mesh.vertices.chunks_exact(3) {
+        for axis in 0..3 {
+            min[axis] = min[axis].min(v[axis]);
+            max[axis] = max[axis].max(v[axis]);
+        }
+    }
+
+    let normals: &[f32] = if has_normals { &mesh.normals } else { &[] };
+    let mut bin = Vec::new();
+    for f in mesh.vertices.iter().chain(normals) {
+        bin.extend_from_slice(&f.to_le_bytes());
+    }
+    for &i in &mesh.indices[..index_count] {
+        if index_type == UNSIGNED_INT {
+            bin.extend_from_slice(&i.to_le_bytes());
+        } else {
+            bin.extend_from_slice(&(i as u16).to_le_bytes());
+        }
+    }
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    Some(Packed {
+        bin,
+        vertex_count,
+        index_count,
+        has_normals,
+        index_type,
+        min,
+        max,
+    })
+}
+
+/// The glTF JSON for `packed`, with the buffer at `uri` (or in the GLB
+/// binary chunk when `uri` is `None`).
+fn document(packed: Option<&Packed>, uri: Option<String>) -> Value {
+    let asset = json!({ "version": "2.0", "generator": "waffle-iron" });
+    let Some(p) = packed else {
+        return json!({ "asset": asset, "scene": 0, "scenes": [{}] });
+    };
+
+    let vertex_bytes = p.vertex_count * 12;
+    let index_size = if p.index_type == UNSIGNED_INT { 4 } else { 2 };
+    let mut views = vec![json!({
+        "buffer": 0,
+        "byteOffset": 0,
+        "byteLength": vertex_bytes,
+        "target": ARRAY_BUFFER,
+    })];
+    let mut accessors = vec![json!({
+        "bufferView": 0,
+        "componentType": FLOAT,
+        "count": p.vertex_count,
+        "type": "VEC3",
+        "min": p.min,
+        "max": p.max,
+    })];
+    let mut attributes = json!({ "POSITION": 0 });
+    if p.has_normals {
+        views.push(json!({
+            "buffer": 0,
+            "byteOffset": vertex_bytes,
+            "byteLength": vertex_bytes,
+            "target": ARRAY_BUFFER,
+        }));
+        accessors.push(json!({
+            "bufferView": 1,
+            "componentType": FLOAT,
+            "count": p.vertex_count,
+            "type": "VEC3",
+        }));
+        attributes["NORMAL"] = json!(1);
+    }
+    let index_view = views.len();
+    let index_offset = vertex_bytes * views.len();
+    views.push(json!({
+        "buffer": 0,
+        "byteOffset": index_offset,
+        "byteLength": p.index_count * index_size,
+        "target": ELEMENT_ARRAY_BUFFER,
+    }));
+    accessors.push(json!({
+        "bufferView": index_view,
+        "componentType": p.index_type,
+        "count": p.index_count,
+        "type": "SCALAR",
+    }));
+
+    let mut buffer = json!({ "byteLength": p.bin.len() });
+    if let Some(uri) = uri {
+        buffer["uri"] = json!(uri);
+    }
+
+    json!({
+        "asset": asset,
+        "scene": 0,
+        "scenes": [{ "nodes": [0] }],
+        "nodes": [{ "mesh": 0 }],
+        "meshes": [{
+            "primitives": [{
+                "attributes": attributes,
+                "indices": index_view,
+                "material": 0,
+                "mode": 4,
+            }],
+        }],
+        "materials": [{
+            "name": "default",
+            "pbrMetallicRoughness": {
+                "baseColorFactor": DEFAULT_BASE_COLOR,
+                "metallicFactor": 0.0,
+                "roughnessFactor": 0.5,
+            },
+        }],
+        "accessors": accessors,
+        "bufferViews": views,
+        "buffers": [buffer],
+    })
+}
@@ -1,12 +1,20 @@
+pub mod amf_export;
 pub mod errors;
+pub mod gltf_export;
 pub mod load;
 pub mod metadata;
 pub mod migrate;
+pub mod obj_import;
 pub mod save;
 pub mod step_export;
+pub mod stl_import;
 
-pub use errors::{ExportError, LoadError};
+pub use amf_export::{export_amf, mesh_to_amf};
+pub use errors::{ExportError, ImportError, LoadError};
+pub use gltf_export::{mesh_to_glb, mesh_to_gltf};
 pub use load::load_project;
 pub use metadata::ProjectMetadata;
+pub use obj_import::{import_obj_multi, obj_to_mesh, obj_to_meshes};
 pub use save::{save_project, FORMAT_VERSION};
 pub use step_export::export_step;
+pub use stl_import::stl_to_mesh;
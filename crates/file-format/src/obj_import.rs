@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use kernel_fork::{FaceRange, KernelId, KernelSolidHandle, RenderMesh, TruckKernel};
+
+use crate::errors::ImportError;
+
+/// Name given to faces that appear before any `o`/`g` directive.
+const DEFAULT_OBJECT_NAME: &str = "object";
+
+/// Parse Wavefront OBJ text into one mesh per object.
+///
+/// A new object starts at every `o` or `g` directive; objects without faces
+/// are dropped. Vertex indices are global across the file as OBJ requires
+/// (1-based, or negative to count back from the latest vertex), and each
+/// object's mesh keeps only the vertices its faces use. Polygons are fanned
+/// into triangles. Texture coordinates, normals and materials are ignored;
+/// vertex normals are recomputed from the triangles. Each mesh gets a single
+/// face range covering all of its triangles.
+pub fn obj_to_meshes(text: &str) -> Result<Vec<(String, RenderMesh)>, ImportError> {
+    let obj = parse(text)?;
+    Ok(obj
+        .objects
+        .into_iter()
+        .map(|(name, triangles)| {
+            let triangles: Vec<[usize; 3]> =
+                triangles.iter().map(|t| t.map(|c| c.position)).collect();
+            (name, build_mesh(&obj.positions, &triangles))
+        })
+        .collect())
+}
+
+/// Parse Wavefront OBJ text into a single mesh, objects and groups merged.
+///
+/// Indices and polygons are read as in [`obj_to_meshes`], but `vn` normals
+/// are kept: each distinct position/normal pair the faces use becomes one
+/// vertex, so a corner shared by faces with different normals stays split
+/// and hard edges survive the trip. Corners without a normal get
+/// area-weighted ones. A face referencing a vertex or normal that doesn't
+/// exist is an error.
+pub fn obj_to_mesh(text: &str) -> Result<RenderMesh, ImportError> {
+    let obj = parse(text)?;
+    let triangles: Vec<[Corner; 3]> = obj
+        .objects
+        .into_iter()
+        .flat_map(|(_, triangles)| triangles)
+        .collect();
+    Ok(assemble(&obj.positions, &obj.normals, &triangles))
+}
+
+/// Import every object of an OBJ file as its own mesh body in `kernel`,
+/// returning each object's name with its handle, in file order.
+pub fn import_obj_multi(
+    kernel: &mut TruckKernel,
+    text: &str,
+) -> Result<Vec<(String, KernelSolidHandle)>, ImportError> {
+    Ok(obj_to_meshes(text)?
+        .into_iter()
+        .map(|(name, mesh)| (name, kernel.import_mesh(mesh)))
+        .collect())
+}
+
+/// One corner of an OBJ face: a 0-based position index, and a normal index
+/// for the `v//vn` and `v/vt/vn` forms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Corner {
+    position: usize,
+    normal: Option<usize>,
+}
+
+/// The parts of an OBJ file the importers use, with faces fanned into
+/// triangles and grouped by object.
+struct ObjData {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    objects: Vec<(String, Vec<[Corner; 3]>)>,
+}
+
+fn parse(text: &str) -> Result<ObjData, ImportError> {
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut objects: Vec<(String, Vec<[Corner; 3]>)> = Vec::new();
+    let mut name = DEFAULT_OBJECT_NAME.to_string();
+    let mut triangles: Vec<[Corner; 3]> = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let error = |reason: String| ImportError::Obj {
+            line: number + 1,
+            reason,
+        };
+        let mut tokens = line.split_whitespace();
+        match tokens.next() {
+            Some(kind @ ("v" | "vn")) => {
+                let what = if kind == "v" { "vertex" } else { "normal" };
+                let mut p = [0.0f32; 3];
+                for c in &mut p {
+                    *c = tokens
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(|| error(format!("{} needs three coordinates", what)))?;
+                }
+                if kind == "v" {
+                    positions.push(p);
+                } else {
+                    normals.push(p);
+                }
+            }
+            Some("f") => {
+                let corners = tokens
+                    .map(|t| corner(t, positions.len(), normals.len()))
+                    .collect::<Result<Vec<Corner>, String>>()
+                    .map_err(error)?;
+                if corners.len() < 3 {
+                    return Err(error("face needs at least three vertices".to_string()));
+                }
+                for k in 1..corners.len() - 1 {
+                    triangles.push([corners[0], corners[k], corners[k + 1]]);
+                }
+            }
+            Some("o") | Some("g") => {
+                let next: Vec<&str> = tokens.collect();
+                if !triangles.is_empty() {
+                    objects.push((name, std::mem::take(&mut triangles)));
+                }
+                name = if next.is_empty() {
+                    DEFAULT_OBJECT_NAME.to_string()
+                } else {
+                    next.join(" ")
+                };
+            }
+            _ => {}
+        }
+    }
+    if !triangles.is_empty() {
+        objects.push((name, triangles));
+    }
+    Ok(ObjData {
+        positions,
+        normals,
+        objects,
+    })
+}
+
+/// Resolve an OBJ `v`, `v/vt`, `v//vn` or `v/vt/vn` face token, given the
+/// number of vertices and normals read so far.
+fn corner(token: &str, vertex_count: usize, normal_count: usize) -> Result<Corner, String> {
+    let mut parts = token.split('/');
+    let position = parts
+        .next()
+        .and_then(|v| resolve_index(v, vertex_count))
+        .ok_or_else(|| "face references a missing vertex".to_string())?;
+    let normal = match parts.nth(1).filter(|n| !n.is_empty()) {
+        Some(n) => Some(
+            resolve_index(n, normal_count)
+                .ok_or_else(|| "face references a missing normal".to_string())?,
+        ),
+        None => None,
+    };
+    Ok(Corner { position, normal })
+}
+
+/// A 1-based or negative (relative) OBJ index as a 0-based index into a
+/// list of `count` elements.
+fn resolve_index(token: &str, count: usize) -> Option<usize> {
+    let index: i64 = token.parse().ok()?;
+    let resolved = if index < 0 {
+        count as i64 + index
+    } else {
+        index - 1
+    };
+    (0..count as i64)
+        .contains(&resolved)
+        .then_some(resolved as usize)
+}
+
+/// A mesh of `triangles` over `positions`, keeping only the vertices they
+/// use, with area-weighted vertex normals and one face range.
+pub(crate) fn build_mesh(positions: &[[f32; 3]], triangles: &[[usize; 3]]) -> RenderMesh {
+    let triangles: Vec<[Corner; 3]> = triangles
+        .iter()
+        .map(|t| {
+            t.map(|position| Corner {
+                position,
+                normal: None,
+            })
+        })
+        .collect();
+    assemble(positions, &[], &triangles)
+}
+
+/// Like [`build_mesh`], with one vertex per distinct corner; corners with a
+/// normal index take that normal instead of the area-weighted one.
+fn assemble(
+    positions: &[[f32; 3]],
+    file_normals: &[[f32; 3]],
+    triangles: &[[Corner; 3]],
+) -> RenderMesh {
+    let mut local: HashMap<Corner, u32> = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut given = Vec::new();
+    let mut indices = Vec::with_capacity(triangles.len() * 3);
+    for tri in triangles {
+        for &c in tri {
+            let i = *local.entry(c).or_insert_with(|| {
+                vertices.extend_from_slice(&positions[c.position]);
+                given.push(c.normal.map(|n| file_normals[n]));
+                (vertices.len() / 3 - 1) as u32
+            });
+            indices.push(i);
+        }
+    }
+
+    // Area-weighted vertex normals.
+    let mut normals = vec![0.0f32; vertices.len()];
+    for tri in indices.chunks_exact(3) {
+        let p = |k: usize| {
+            let i = tri[k] as usize * 3;
+            [vertices[i], vertices[i + 1], vertices[i + 2]]
+        };
+        let (a, b, c) = (p(0), p(1), p(2));
+        let (e1, e2) = (
+            [b[0] - a[0], b[1] - a[1], b[2] - a[2]],
+            [c[0] - a[0], c[1] - a[1], c[2] - a[2]],
+        );
+        let n = [
+            e1[1] * e2[2] - e1[2] * e2[1],
+            e1[2] * e2[0] - e1[0] * e2[2],
+            e1[0] * e2[1] - e1[1] * e2[0],
+        ];
+        for &v in tri {
+            for (axis, c) in n.iter().enumerate() {
+                normals[v as usize * 3 + axis] += c;
+            }
+        }
+    }
+    for (n, given) in normals.chunks_exact_mut(3).zip(given) {
+        if let Some(given) = given {
+            n.copy_from_slice(&given);
+        }
+        let len = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+        if len > 0.0 {
+            n.iter_mut().for_each(|c| *c /= len);
+        }
+    }
+
+    RenderMesh {
+        face_ranges: vec![FaceRange {
+            face_id: KernelId(0),
+            start_index: 0,
+            end_index: indices.len() as u32,
+        }],
+        vertices,
+        normals,
+        indices,
+    }
+}
@@ -1,5 +1,5 @@
 use feature_engine::types::FeatureTree;
-use kernel_fork::TruckKernel;
+use kernel_fork::{KernelSolidHandle, TruckKernel};
 use waffle_types::OutputKey;
 
 use crate::errors::ExportError;
@@ -10,14 +10,29 @@ use crate::errors::ExportError;
 /// the final solid to a STEP string. Returns an error if the rebuild
 /// fails or produces no solid.
 pub fn export_step(tree: &FeatureTree, kb: &mut TruckKernel) -> Result<String, ExportError> {
+    let last_handle = rebuild_final_solid(tree, kb)?;
+
+    // Export via TruckKernel
+    let step_string = kb
+        .export_step(&last_handle, "export.step")
+        .map_err(|e| ExportError::StepExportFailed(format!("{}", e)))?;
+
+    Ok(step_string)
+}
+
+/// Rebuild the tree from scratch and return the last non-suppressed
+/// feature's Main solid.
+pub(crate) fn rebuild_final_solid(
+    tree: &FeatureTree,
+    kb: &mut TruckKernel,
+) -> Result<KernelSolidHandle, ExportError> {
     // Build an engine and rebuild
     let mut engine = feature_engine::Engine::new();
     engine.tree = tree.clone();
     engine.rebuild_from_scratch(kb);
 
     // Find the last non-suppressed feature with a Main output
-    let last_handle = tree
-        .features
+    tree.features
         .iter()
         .rev()
         .filter(|f| !f.suppressed)
@@ -30,12 +45,5 @@ pub fn export_step(tree: &FeatureTree, kb: &mut TruckKernel) -> Result<String, E
                     .map(|(_, body)| body.handle.clone())
             })
         })
-        .ok_or(ExportError::NoSolid)?;
-
-    // Export via TruckKernel
-    let step_string = kb
-        .export_step(&last_handle, "export.step")
-        .map_err(|e| ExportError::StepExportFailed(format!("{}", e)))?;
-
-    Ok(step_string)
+        .ok_or(ExportError::NoSolid)
 }
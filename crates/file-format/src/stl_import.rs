@@ -0,0 +1,67 @@
+use kernel_fork::{mesh, RenderMesh};
+
+use crate::errors::ImportError;
+use crate::obj_import::build_mesh;
+
+/// Bytes before the first triangle: an 80-byte header and the triangle count.
+const HEADER_BYTES: usize = 84;
+
+/// Bytes per triangle: normal, three corners and the attribute byte count.
+const TRIANGLE_BYTES: usize = 50;
+
+/// Corners closer than this are welded into one vertex. STL repeats every
+/// shared corner, normally with bit-identical coordinates.
+const WELD_TOLERANCE: f64 = 1e-6;
+
+/// Parse a binary STL file into a welded mesh.
+///
+/// The file must be exactly as long as its triangle count says; a
+/// truncated or padded file is rejected rather than read partially. The
+/// stored facet normals are ignored: corners are welded so that triangles
+/// share vertices again, and vertex normals are recomputed from the
+/// triangles. The mesh gets a single face range covering all of them.
+pub fn stl_to_mesh(bytes: &[u8]) -> Result<RenderMesh, ImportError> {
+    let Some(count) = bytes.get(80..HEADER_BYTES) else {
+        return Err(ImportError::StlSize {
+            expected: HEADER_BYTES,
+            actual: bytes.len(),
+        });
+    };
+    let count = u32::from_le_bytes([count[0], count[1], count[2], count[3]]) as usize;
+    let expected = count
+        .checked_mul(TRIANGLE_BYTES)
+        .and_then(|body| body.checked_add(HEADER_BYTES));
+    if expected != Some(bytes.len()) {
+        return Err(ImportError::StlSize {
+            expected: expected.unwrap_or(usize::MAX),
+            actual: bytes.len(),
+        });
+    }
+
+    let mut corners = RenderMesh {
+        vertices: Vec::with_capacity(count * 9),
+        normals: Vec::new(),
+        indices: (0..count as u32 * 3).collect(),
+        face_ranges: Vec::new(),
+    };
+    for record in bytes[HEADER_BYTES..].chunks_exact(TRIANGLE_BYTES) {
+        corners.vertices.extend(
+            record[12..48]
+                .chunks_exact(4)
+                .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]])),
+        );
+    }
+    mesh::weld_vertices(&mut corners, WELD_TOLERANCE);
+
+    let positions: Vec<[f32; 3]> = corners
+        .vertices
+        .chunks_exact(3)
+        .map(|v| [v[0], v[1], v[2]])
+        .collect();
+    let triangles: Vec<[usize; 3]> = corners
+        .indices
+        .chunks_exact(3)
+        .map(|t| [t[0] as usize, t[1] as usize, t[2] as usize])
+        .collect();
+    Ok(build_mesh(&positions, &triangles))
+}
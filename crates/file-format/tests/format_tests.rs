@@ -3,7 +3,9 @@ use feature_engine::types::{
     Operation, RevolveParams, ShellParams,
 };
 use file_format::{
-    export_step, load_project, save_project, LoadError, ProjectMetadata, FORMAT_VERSION,
+    export_amf, export_step, import_obj_multi, load_project, mesh_to_amf, mesh_to_glb,
+    mesh_to_gltf, obj_to_mesh, obj_to_meshes, save_project, stl_to_mesh, ImportError, LoadError,
+    ProjectMetadata, FORMAT_VERSION,
 };
 use uuid::Uuid;
 use waffle_types::{
@@ -654,6 +656,292 @@ fn step_export_suppressed_only_returns_error() {
     assert!(result.is_err(), "Sketch-only tree should fail STEP export");
 }
 
+// ── AMF Export Tests ─────────────────────────────────────────────────────
+
+/// Minimal well-formedness check: every opening tag is closed in order.
+fn assert_xml_balanced(xml: &str) {
+    let mut stack: Vec<String> = Vec::new();
+    for tag in xml.split('<').skip(1) {
+        let tag = &tag[..tag.find('>').expect("unterminated tag")];
+        if tag.starts_with('?') || tag.ends_with('/') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            assert_eq!(stack.pop().as_deref(), Some(name), "mismatched </{}>", name);
+        } else {
+            stack.push(tag.split_whitespace().next().unwrap().to_string());
+        }
+    }
+    assert!(stack.is_empty(), "unclosed tags: {:?}", stack);
+}
+
+#[test]
+fn amf_encodes_mesh_and_material_color() {
+    let mesh = kernel_fork::RenderMesh {
+        vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+        normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        indices: vec![0, 1, 2, 0, 2, 3],
+        face_ranges: Vec::new(),
+    };
+
+    let amf = mesh_to_amf(&mesh, "PLA <red>", [0.8, 0.1, 0.2, 1.0]);
+
+    assert_xml_balanced(&amf);
+    assert_eq!(amf.matches("<vertex>").count(), 4);
+    assert_eq!(amf.matches("<triangle>").count(), 2);
+    assert!(amf.contains("<volume materialid=\"1\">"));
+    assert!(amf.contains("<material id=\"1\">"));
+    assert!(amf.contains("PLA &lt;red&gt;"));
+    assert!(amf.contains("<r>0.8</r><g>0.1</g><b>0.2</b><a>1</a>"));
+}
+
+#[test]
+fn amf_export_simple_box() {
+    use kernel_fork::TruckKernel;
+
+    let tree = make_rebuild_compatible_tree();
+    let mut kb = TruckKernel::new();
+    let amf = export_amf(&tree, &mut kb, "Steel", [0.5, 0.5, 0.5, 1.0]).unwrap();
+
+    assert_xml_balanced(&amf);
+    assert!(amf.matches("<triangle>").count() >= 12);
+    assert!(amf.contains("<metadata type=\"name\">Steel</metadata>"));
+}
+
+#[test]
+fn amf_export_empty_tree_returns_error() {
+    use kernel_fork::TruckKernel;
+
+    let mut kb = TruckKernel::new();
+    assert!(export_amf(&FeatureTree::new(), &mut kb, "PLA", [1.0; 4]).is_err());
+}
+
+// ── glTF Export Tests ────────────────────────────────────────────────────
+
+fn unit_quad() -> kernel_fork::RenderMesh {
+    kernel_fork::RenderMesh {
+        vertices: vec![0.0, 0.0, 0.0, 2.0, 0.0, 0.0, 2.0, 1.0, 0.5, 0.0, 1.0, 0.5],
+        normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+        indices: vec![0, 1, 2, 0, 2, 3],
+        face_ranges: Vec::new(),
+    }
+}
+
+#[test]
+fn gltf_packs_one_buffer_with_bounds() {
+    use base64::Engine as _;
+
+    let doc: serde_json::Value = serde_json::from_str(&mesh_to_gltf(&unit_quad())).unwrap();
+
+    assert_eq!(doc["asset"]["version"], "2.0");
+    let primitive = &doc["meshes"][0]["primitives"][0];
+    assert_eq!(primitive["attributes"]["POSITION"], 0);
+    assert_eq!(primitive["attributes"]["NORMAL"], 1);
+    assert_eq!(primitive["material"], 0);
+    assert_eq!(doc["materials"].as_array().unwrap().len(), 1);
+
+    let accessors = &doc["accessors"];
+    assert_eq!(accessors[0]["min"], serde_json::json!([0.0, 0.0, 0.0]));
+    assert_eq!(accessors[0]["max"], serde_json::json!([2.0, 1.0, 0.5]));
+    let indices = &accessors[primitive["indices"].as_u64().unwrap() as usize];
+    assert_eq!(indices["componentType"], 5123);
+    assert_eq!(indices["count"], 6);
+
+    // 4 positions + 4 normals + 6 u16 indices, padded to a multiple of 4.
+    let buffer = &doc["buffers"][0];
+    assert_eq!(buffer["byteLength"], 48 + 48 + 12);
+    let uri = buffer["uri"].as_str().unwrap();
+    let data = uri.strip_prefix("data:application/octet-stream;base64,").unwrap();
+    let bytes = base64::engine::general_purpose::STANDARD.decode(data).unwrap();
+    assert_eq!(bytes.len(), 108);
+    assert_eq!(&bytes[12..16], &2.0f32.to_le_bytes());
+    assert_eq!(&bytes[100..102], &2u16.to_le_bytes());
+}
+
+#[test]
+fn gltf_uses_unsigned_int_indices_past_65535_vertices() {
+    let mut mesh = unit_quad();
+    mesh.vertices.resize(65536 * 3, 0.0);
+    mesh.normals.resize(65536 * 3, 0.0);
+    mesh.indices.extend([0, 1, 65535]);
+
+    let doc: serde_json::Value = serde_json::from_str(&mesh_to_gltf(&mesh)).unwrap();
+    let accessor = doc["meshes"][0]["primitives"][0]["indices"].as_u64().unwrap() as usize;
+    assert_eq!(doc["accessors"][accessor]["componentType"], 5125);
+
+    mesh.vertices.truncate(65535 * 3);
+    mesh.normals.truncate(65535 * 3);
+    mesh.indices.truncate(6);
+    let doc: serde_json::Value = serde_json::from_str(&mesh_to_gltf(&mesh)).unwrap();
+    assert_eq!(doc["accessors"][accessor]["componentType"], 5123);
+}
+
+#[test]
+fn gltf_empty_mesh_has_no_primitives() {
+    let empty = kernel_fork::RenderMesh {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        indices: Vec::new(),
+        face_ranges: Vec::new(),
+    };
+
+    let doc: serde_json::Value = serde_json::from_str(&mesh_to_gltf(&empty)).unwrap();
+    assert_eq!(doc["asset"]["version"], "2.0");
+    assert_eq!(doc["scenes"][0], serde_json::json!({}));
+    assert!(doc.get("meshes").is_none());
+    assert!(doc.get("buffers").is_none());
+
+    let glb = mesh_to_glb(&empty);
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(u32::from_le_bytes(glb[8..12].try_into().unwrap()) as usize, glb.len());
+    let json_len = u32::from_le_bytes(glb[12..16].try_into().unwrap()) as usize;
+    assert_eq!(glb.len(), 20 + json_len, "no binary chunk");
+}
+
+#[test]
+fn glb_stores_buffer_in_binary_chunk() {
+    let glb = mesh_to_glb(&unit_quad());
+    let word = |at: usize| u32::from_le_bytes(glb[at..at + 4].try_into().unwrap()) as usize;
+
+    assert_eq!(&glb[0..4], b"glTF");
+    assert_eq!(word(4), 2);
+    assert_eq!(word(8), glb.len());
+    let json_len = word(12);
+    assert_eq!(json_len % 4, 0);
+    assert_eq!(&glb[16..20], b"JSON");
+    let doc: serde_json::Value = serde_json::from_slice(&glb[20..20 + json_len]).unwrap();
+    assert!(doc["buffers"][0].get("uri").is_none());
+
+    let bin_at = 20 + json_len;
+    assert_eq!(word(bin_at), 108);
+    assert_eq!(&glb[bin_at + 4..bin_at + 8], b"BIN\0");
+    assert_eq!(doc["buffers"][0]["byteLength"], 108);
+    assert_eq!(glb.len(), bin_at + 8 + 108);
+}
+
+// ── OBJ Import Tests ─────────────────────────────────────────────────────
+
+/// A unit quad (two triangles via a fanned polygon) and a tetrahedron, in
+/// separate `o` blocks sharing one global vertex list.
+const TWO_OBJECT_OBJ: &str = "\
+# two objects
+o plate
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+f 1 2 3 4
+o tetra
+v 0 0 1
+v 1 0 1
+v 0 1 1
+v 0 0 2
+f 5/1 7/2 6/3
+f 5//1 6//1 8//1
+f 6 7 8
+f -4 -1 -2
+";
+
+#[test]
+fn obj_import_splits_objects() {
+    let meshes = obj_to_meshes(TWO_OBJECT_OBJ).unwrap();
+    assert_eq!(meshes.len(), 2);
+
+    let (name, plate) = &meshes[0];
+    assert_eq!(name, "plate");
+    assert_eq!(plate.indices.len() / 3, 2);
+    assert_eq!(plate.vertices.len() / 3, 4);
+
+    let (name, tetra) = &meshes[1];
+    assert_eq!(name, "tetra");
+    assert_eq!(tetra.indices.len() / 3, 4);
+    assert_eq!(tetra.vertices.len() / 3, 4);
+    assert!(tetra.vertices.chunks_exact(3).all(|v| v[2] >= 1.0));
+    assert_eq!(tetra.normals.len(), tetra.vertices.len());
+    assert!(kernel_fork::mesh::validate_mesh(tetra).is_closed_manifold());
+}
+
+#[test]
+fn obj_import_multi_creates_one_body_per_object() {
+    use kernel_fork::{Kernel, TruckKernel};
+
+    let mut kb = TruckKernel::new();
+    let bodies = import_obj_multi(&mut kb, TWO_OBJECT_OBJ).unwrap();
+    assert_eq!(bodies.len(), 2);
+    for ((name, handle), expected_tris) in bodies.iter().zip([2, 4]) {
+        assert!(kb.is_imported(handle), "{} should be imported", name);
+        let mesh = kb.tessellate(handle, 0.1).unwrap();
+        assert_eq!(mesh.indices.len() / 3, expected_tris, "{}", name);
+    }
+}
+
+#[test]
+fn obj_import_reports_bad_face_line() {
+    let err = obj_to_meshes("v 0 0 0\nv 1 0 0\nf 1 2 3\n").unwrap_err();
+    assert!(matches!(err, ImportError::Obj { line: 3, .. }));
+}
+
+#[test]
+fn obj_to_mesh_keeps_file_normals() {
+    // A unit quad, fanned into two triangles, and a triangle standing on
+    // its edge with its own normal. Indices are relative on the quad.
+    let text = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+vn 0 0 2
+vn 0 -1 0
+f -4//1 -3//1 -2//1 -1//1
+o wall
+v 0 0 1
+f 1//2 2/7/2 5//2
+";
+    let mesh = obj_to_mesh(text).unwrap();
+    assert_eq!(mesh.indices.len() / 3, 3);
+    // Corners 1 and 2 appear with both normals, so each is split in two.
+    assert_eq!(mesh.vertices.len() / 3, 7);
+    let with_normal = |expected: [f32; 3]| {
+        mesh.normals
+            .chunks_exact(3)
+            .filter(|n| *n == expected)
+            .count()
+    };
+    assert_eq!(with_normal([0.0, 0.0, 1.0]), 4);
+    assert_eq!(with_normal([0.0, -1.0, 0.0]), 3);
+}
+
+#[test]
+fn obj_to_mesh_rejects_missing_normal() {
+    let text = "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nf 1//1 2//1 3//2\n";
+    let err = obj_to_mesh(text).unwrap_err();
+    assert!(matches!(err, ImportError::Obj { line: 5, .. }));
+    assert!(obj_to_mesh("v 0 0 0\nf 1 -2 1\n").is_err());
+}
+
+#[test]
+fn stl_import_rejects_wrong_length() {
+    // Header claiming two triangles, followed by only one.
+    let mut stl = vec![0u8; 80];
+    stl.extend_from_slice(&2u32.to_le_bytes());
+    stl.extend_from_slice(&[0u8; 50]);
+    let err = stl_to_mesh(&stl).unwrap_err();
+    assert!(matches!(
+        err,
+        ImportError::StlSize {
+            expected: 184,
+            actual: 134
+        }
+    ));
+
+    stl.extend_from_slice(&[0u8; 51]);
+    assert!(stl_to_mesh(&stl).is_err(), "trailing bytes are rejected too");
+    assert!(matches!(
+        stl_to_mesh(&stl[..40]),
+        Err(ImportError::StlSize { expected: 84, .. })
+    ));
+}
+
 // ── M6: Full Round-Trip Tests ──────────────────────────────────────────
 
 #[test]
@@ -728,6 +1016,66 @@ fn round_trip_preserves_feature_ids_through_rebuild() {
     }
 }
 
+#[test]
+fn round_trip_face_ref_finds_same_face() {
+    use kernel_fork::{KernelIntrospect, TruckKernel};
+
+    // Selections persist as GeomRefs, not kernel IDs.
+    let tree = make_rebuild_compatible_tree();
+    let extrude_id = tree.features[1].id;
+    let face_ref = GeomRef {
+        kind: TopoKind::Face,
+        anchor: Anchor::FeatureOutput {
+            feature_id: extrude_id,
+            output_key: OutputKey::Main,
+        },
+        selector: Selector::Role {
+            role: Role::EndCapPositive,
+            index: 0,
+        },
+        policy: ResolvePolicy::Strict,
+    };
+
+    let mut kb1 = TruckKernel::new();
+    let mut engine1 = feature_engine::Engine::new();
+    engine1.tree = tree.clone();
+    engine1.rebuild_from_scratch(&mut kb1);
+    let before =
+        feature_engine::resolve::resolve_with_fallback(&face_ref, &engine1.feature_results)
+            .unwrap()
+            .kernel_id;
+    let sig_before = kb1.compute_signature(before, TopoKind::Face);
+
+    let json = save_project(&tree, &ProjectMetadata::new("Face Ref"));
+    let (loaded_tree, _) = load_project(&json).unwrap();
+
+    let mut kb2 = TruckKernel::new();
+    let mut engine2 = feature_engine::Engine::new();
+    engine2.tree = loaded_tree;
+    engine2.rebuild_from_scratch(&mut kb2);
+    let after = feature_engine::resolve::resolve_with_fallback(&face_ref, &engine2.feature_results)
+        .unwrap()
+        .kernel_id;
+    let sig_after = kb2.compute_signature(after, TopoKind::Face);
+
+    let (c1, c2) = (sig_before.centroid.unwrap(), sig_after.centroid.unwrap());
+    let (n1, n2) = (sig_before.normal.unwrap(), sig_after.normal.unwrap());
+    for i in 0..3 {
+        assert!(
+            (c1[i] - c2[i]).abs() < 1e-9,
+            "centroid moved: {:?} vs {:?}",
+            c1,
+            c2
+        );
+        assert!(
+            (n1[i] - n2[i]).abs() < 1e-9,
+            "normal changed: {:?} vs {:?}",
+            n1,
+            n2
+        );
+    }
+}
+
 #[test]
 fn round_trip_step_export_matches_original() {
     use kernel_fork::TruckKernel;
@@ -0,0 +1,239 @@
+//! Merging of adjacent faces that lie in one plane, like the halves of a
+//! wall that a union of abutting boxes leaves split along the seam.
+//!
+//! Faces are grouped across shared edges whose two sides have the same
+//! plane, and each group becomes one face bounded by the group's edges that
+//! are not shared inside it. Straight edges meeting end to end in a line,
+//! with nothing else at the vertex between them, are then joined into one.
+
+use std::collections::HashMap;
+
+use truck_modeling::geometry::{Curve, Line, Surface};
+use truck_modeling::topology::{Edge, Face, Shell, Solid, Vertex, Wire};
+use truck_modeling::{EuclideanSpace, InnerSpace, Vector3};
+
+use crate::types::KernelError;
+
+/// Largest `|n_a x n_b|` at which two face normals count as parallel, and
+/// largest gap between two planes, relative to their distance from the
+/// origin (or 1 near it), at which they count as the same plane.
+const COPLANAR_TOLERANCE: f64 = 1e-9;
+
+fn merge_failed(message: impl Into<String>) -> KernelError {
+    KernelError::Other {
+        message: message.into(),
+    }
+}
+
+/// `solid` with each group of adjacent coplanar faces merged into one face
+/// and collinear straight edges joined.
+///
+/// Only planar faces merge. Faces that neither merge nor lose a vertex are
+/// kept as they are.
+pub fn merge_coplanar_faces(solid: &Solid) -> Result<Solid, KernelError> {
+    let shells = solid
+        .boundaries()
+        .iter()
+        .map(merge_shell)
+        .collect::<Result<Vec<_>, _>>()?;
+    Solid::try_new(shells).map_err(|e| merge_failed(format!("merged solid: {}", e)))
+}
+
+/// A face being rebuilt from its surface and boundary wires.
+struct Patch {
+    surface: Surface,
+    wires: Vec<Vec<Edge>>,
+    /// The face this patch came from, while it is still unchanged.
+    original: Option<Face>,
+}
+
+fn merge_shell(shell: &Shell) -> Result<Shell, KernelError> {
+    let faces: Vec<&Face> = shell.face_iter().collect();
+    let planes: Vec<Option<(Vector3, f64)>> = faces
+        .iter()
+        .map(|face| match face.oriented_surface() {
+            Surface::Plane(plane) => {
+                let normal = plane.normal();
+                Some((normal, normal.dot(plane.origin().to_vec())))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let mut edge_faces = HashMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for edge in face.boundaries().iter().flat_map(|wire| wire.iter()) {
+            edge_faces.entry(edge.id()).or_insert_with(Vec::new).push(f);
+        }
+    }
+    // Union-find over faces joined by an edge between two coplanar faces.
+    let mut parent: Vec<usize> = (0..faces.len()).collect();
+    fn root(parent: &mut [usize], mut f: usize) -> usize {
+        while parent[f] != f {
+            parent[f] = parent[parent[f]];
+            f = parent[f];
+        }
+        f
+    }
+    for around in edge_faces.values() {
+        if let [f, g] = around[..] {
+            if let (Some(a), Some(b)) = (planes[f], planes[g]) {
+                if coplanar(a, b) {
+                    let (rf, rg) = (root(&mut parent, f), root(&mut parent, g));
+                    parent[rf] = rg;
+                }
+            }
+        }
+    }
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut group_of_root = HashMap::new();
+    for f in 0..faces.len() {
+        let r = root(&mut parent, f);
+        let g = *group_of_root.entry(r).or_insert_with(|| {
+            groups.push(Vec::new());
+            groups.len() - 1
+        });
+        groups[g].push(f);
+    }
+
+    let mut patches = Vec::with_capacity(groups.len());
+    for group in &groups {
+        let first = faces[group[0]];
+        if let [_] = group[..] {
+            patches.push(Patch {
+                surface: first.oriented_surface(),
+                wires: first
+                    .boundaries()
+                    .iter()
+                    .map(|wire| wire.iter().cloned().collect())
+                    .collect(),
+                original: Some(first.clone()),
+            });
+            continue;
+        }
+        // Edges between two faces of the group drop out; the rest bound
+        // the merged face.
+        let edges: Vec<Edge> = group
+            .iter()
+            .flat_map(|&f| faces[f].boundaries())
+            .flat_map(|wire| wire.iter().cloned())
+            .collect();
+        let mut uses = HashMap::new();
+        for edge in &edges {
+            *uses.entry(edge.id()).or_insert(0) += 1;
+        }
+        let outline = edges.into_iter().filter(|e| uses[&e.id()] == 1).collect();
+        patches.push(Patch {
+            surface: first.oriented_surface(),
+            wires: chain(outline)?,
+            original: None,
+        });
+    }
+    join_collinear(&mut patches);
+
+    let faces = patches
+        .into_iter()
+        .map(|patch| match patch.original {
+            Some(face) => Ok(face),
+            None => {
+                let wires = patch.wires.into_iter().map(Wire::from_iter).collect();
+                Face::try_new(wires, patch.surface)
+                    .map_err(|e| merge_failed(format!("merged face: {}", e)))
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Shell::from(faces))
+}
+
+fn coplanar((na, da): (Vector3, f64), (nb, db): (Vector3, f64)) -> bool {
+    let scale = 1.0 + da.abs().max(db.abs());
+    na.dot(nb) > 0.0
+        && na.cross(nb).magnitude() <= COPLANAR_TOLERANCE
+        && (da - db).abs() <= COPLANAR_TOLERANCE * scale
+}
+
+/// Closed loops of the oriented `edges`, each following on from the last.
+fn chain(mut edges: Vec<Edge>) -> Result<Vec<Vec<Edge>>, KernelError> {
+    let mut loops = Vec::new();
+    while let Some(first) = edges.pop() {
+        let start = first.front().id();
+        let mut end = first.back().id();
+        let mut wire = vec![first];
+        while end != start {
+            let Some(i) = edges.iter().position(|e| e.front().id() == end) else {
+                return Err(merge_failed("merged face boundary is not closed"));
+            };
+            end = edges[i].back().id();
+            wire.push(edges.swap_remove(i));
+        }
+        loops.push(wire);
+    }
+    Ok(loops)
+}
+
+/// Replace every pair of straight edges that continue each other through a
+/// vertex used by no other edge with one edge between their far ends.
+fn join_collinear(patches: &mut [Patch]) {
+    loop {
+        let mut at_vertex = HashMap::new();
+        for edge in patches.iter().flat_map(|p| p.wires.iter().flatten()) {
+            for v in [edge.absolute_front(), edge.absolute_back()] {
+                let (_, around) = at_vertex
+                    .entry(v.id())
+                    .or_insert_with(|| (v.clone(), Vec::<Edge>::new()));
+                if !around.iter().any(|e| e.id() == edge.id()) {
+                    around.push(edge.clone());
+                }
+            }
+        }
+        let joint = at_vertex
+            .into_values()
+            .find_map(|(v, around)| match &around[..] {
+                [a, b] => {
+                    let (p, q) = (far_end(a, &v), far_end(b, &v));
+                    let through = p.id() != q.id()
+                        && matches!(a.curve(), Curve::Line(_))
+                        && matches!(b.curve(), Curve::Line(_))
+                        && continues(&p, &v, &q);
+                    through.then_some((v.id(), p, q))
+                }
+                _ => None,
+            });
+        let Some((v, p, q)) = joint else {
+            return;
+        };
+
+        let joined = Edge::new(&p, &q, Curve::Line(Line(p.point(), q.point())));
+        for patch in patches.iter_mut() {
+            for wire in &mut patch.wires {
+                let n = wire.len();
+                let Some(i) = (0..n).find(|&i| wire[i].back().id() == v) else {
+                    continue;
+                };
+                wire[i] = if wire[i].front().id() == p.id() {
+                    joined.clone()
+                } else {
+                    joined.inverse()
+                };
+                wire.remove((i + 1) % n);
+                patch.original = None;
+            }
+        }
+    }
+}
+
+/// The end of `edge` that isn't `v`.
+fn far_end(edge: &Edge, v: &Vertex) -> Vertex {
+    if edge.absolute_front().id() == v.id() {
+        edge.absolute_back().clone()
+    } else {
+        edge.absolute_front().clone()
+    }
+}
+
+/// Whether `p`, `v` and `q` lie on one line in that order.
+fn continues(p: &Vertex, v: &Vertex, q: &Vertex) -> bool {
+    let (d1, d2) = (v.point() - p.point(), q.point() - v.point());
+    d1.dot(d2) > 0.0
+        && d1.cross(d2).magnitude() <= COPLANAR_TOLERANCE * d1.magnitude() * d2.magnitude()
+}
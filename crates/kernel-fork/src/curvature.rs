@@ -0,0 +1,145 @@
+//! Curvature of face surfaces, for curvature-shaded previews and surface QA.
+//!
+//! Computed from the first and second fundamental forms of the surface's own
+//! parameterization, so the same formula is exact for planes, revolved
+//! surfaces and NURBS alike — no per-type special cases.
+
+use truck_modeling::topology::{Face, Solid};
+use truck_modeling::{InnerSpace, ParametricSurface, Point3, Vector3};
+
+use crate::face_uv;
+
+/// Below this `|Su x Sv|^2` the parameterization is singular (e.g. the pole
+/// of a revolved surface) and curvature is not defined there.
+const DEGENERATE_METRIC: f64 = 1e-24;
+
+/// Curvatures of a surface at one point.
+///
+/// Signed so that a surface bending away from its normal is positive: the
+/// outward faces of a convex solid have `k1, k2 >= 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Curvatures {
+    /// Larger principal curvature.
+    pub k1: f64,
+    /// Smaller principal curvature.
+    pub k2: f64,
+    /// Gaussian curvature `k1 * k2`.
+    pub gaussian: f64,
+    /// Mean curvature `(k1 + k2) / 2`.
+    pub mean: f64,
+}
+
+impl Curvatures {
+    /// The same curvatures seen from the other side of the surface.
+    pub fn flipped(self) -> Self {
+        Curvatures {
+            k1: -self.k2,
+            k2: -self.k1,
+            gaussian: self.gaussian,
+            mean: -self.mean,
+        }
+    }
+}
+
+/// Curvatures of `surface` at parameters `(u, v)`, relative to the normal
+/// `Su x Sv`. `None` where the parameterization is singular.
+pub fn surface_curvatures<S>(surface: &S, u: f64, v: f64) -> Option<Curvatures>
+where
+    S: ParametricSurface<Point = Point3, Vector = Vector3>,
+{
+    let (su, sv) = (surface.uder(u, v), surface.vder(u, v));
+    let cross = su.cross(sv);
+    let metric = cross.magnitude2();
+    if metric < DEGENERATE_METRIC {
+        return None;
+    }
+    let normal = cross / metric.sqrt();
+
+    // First fundamental form, and the second one taken against the inward
+    // side so that bending away from the normal counts as positive.
+    let (e, f, g) = (su.dot(su), su.dot(sv), sv.dot(sv));
+    let l = -surface.uuder(u, v).dot(normal);
+    let m = -surface.uvder(u, v).dot(normal);
+    let n = -surface.vvder(u, v).dot(normal);
+
+    // EG - F^2 equals |Su x Sv|^2.
+    let gaussian = (l * n - m * m) / metric;
+    let mean = (e * n - 2.0 * f * m + g * l) / (2.0 * metric);
+    let spread = (mean * mean - gaussian).max(0.0).sqrt();
+    Some(Curvatures {
+        k1: mean + spread,
+        k2: mean - spread,
+        gaussian,
+        mean,
+    })
+}
+
+/// Curvatures of the `face_index`-th face of `solid` at `point`, relative
+/// to the face's outward normal.
+///
+/// Faces are counted across all shells in order. `None` if the point is not
+/// on that face (see [`face_uv::face_uv_at`], which `trim_meshes` is passed
+/// on to) or sits on a singularity.
+pub fn face_curvatures_at(
+    solid: &Solid,
+    face_index: usize,
+    point: [f64; 3],
+    trim_meshes: &mut face_uv::TrimMeshes,
+) -> Option<Curvatures> {
+    let (u, v) = face_uv::face_uv_at(solid, face_index, point, trim_meshes)?;
+    let face: &Face = solid
+        .boundaries()
+        .iter()
+        .flat_map(|shell| shell.face_iter())
+        .nth(face_index)?;
+    let curvatures = surface_curvatures(&face.surface(), u, v)?;
+    Some(if face.orientation() {
+        curvatures
+    } else {
+        curvatures.flipped()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+
+    fn locate(solid: &Solid, point: [f64; 3]) -> Curvatures {
+        let face_count = solid.boundaries()[0].face_iter().count();
+        let mut trim_meshes = face_uv::TrimMeshes::new();
+        (0..face_count)
+            .find_map(|f| face_curvatures_at(solid, f, point, &mut trim_meshes))
+            .expect("point lies on some face")
+    }
+
+    #[test]
+    fn test_sphere_curvature_is_one_over_radius_everywhere() {
+        let r = 2.5;
+        let sphere = primitives::make_sphere(r);
+        for dir in [[0.3, 0.5, 0.6], [-0.4, 0.2, -0.7], [0.8, -0.5, 0.1]] {
+            let len = (dir[0] * dir[0] + dir[1] * dir[1] + dir[2] * dir[2]).sqrt();
+            let p = dir.map(|c: f64| c * r / len);
+            let c = locate(&sphere, p);
+            assert!((c.k1 - 1.0 / r).abs() < 1e-6, "{:?} at {:?}", c, p);
+            assert!((c.k2 - 1.0 / r).abs() < 1e-6, "{:?} at {:?}", c, p);
+            assert!((c.mean - 1.0 / r).abs() < 1e-6, "{:?} at {:?}", c, p);
+            assert!((c.gaussian - 1.0 / (r * r)).abs() < 1e-6, "{:?} at {:?}", c, p);
+        }
+    }
+
+    #[test]
+    fn test_plane_and_cylinder_curvature() {
+        let block = primitives::make_box(2.0, 2.0, 2.0);
+        let c = locate(&block, [1.0, 0.7, 2.0]);
+        assert_eq!([c.k1, c.k2, c.gaussian, c.mean], [0.0; 4]);
+
+        // A cylinder wall bends one way only.
+        let r = 0.5;
+        let cylinder = primitives::make_cylinder(r, 3.0);
+        let c = locate(&cylinder, [0.0, r, 1.5]);
+        assert!((c.k1 - 1.0 / r).abs() < 1e-6, "{:?}", c);
+        assert!(c.k2.abs() < 1e-6, "{:?}", c);
+        assert!(c.gaussian.abs() < 1e-6, "{:?}", c);
+    }
+}
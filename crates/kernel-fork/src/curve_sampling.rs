@@ -0,0 +1,166 @@
+//! Arc-length parameterization for truck curves.
+//!
+//! truck evaluates curves by parameter, which is not proportional to
+//! distance along the curve for NURBS and other non-uniform curves.
+//! These helpers measure length with Gauss-Legendre quadrature and invert
+//! it, so callers can place samples at even spacing.
+
+use truck_modeling::{BoundedCurve, InnerSpace, ParametricCurve, Point3, Vector3};
+
+/// 5-point Gauss-Legendre nodes and weights on [-1, 1].
+pub(crate) const GAUSS_LEGENDRE_5: [(f64, f64); 5] = [
+    (0.0, 0.568_888_888_888_889),
+    (-0.538_469_310_105_683, 0.478_628_670_499_366),
+    (0.538_469_310_105_683, 0.478_628_670_499_366),
+    (-0.906_179_845_938_664, 0.236_926_885_056_189),
+    (0.906_179_845_938_664, 0.236_926_885_056_189),
+];
+
+/// Number of equal parameter spans the quadrature is applied over.
+const QUADRATURE_SPANS: usize = 16;
+
+/// Length of the curve between parameters `t0` and `t1`.
+pub fn length_between<C>(curve: &C, t0: f64, t1: f64) -> f64
+where
+    C: ParametricCurve<Point = Point3, Vector = Vector3>,
+{
+    let span = (t1 - t0) / QUADRATURE_SPANS as f64;
+    let mut length = 0.0;
+    for i in 0..QUADRATURE_SPANS {
+        let a = t0 + span * i as f64;
+        let mid = a + span / 2.0;
+        for &(x, w) in &GAUSS_LEGENDRE_5 {
+            length += w * curve.der(mid + x * span / 2.0).magnitude();
+        }
+    }
+    length * span / 2.0
+}
+
+/// Total length of a bounded curve.
+pub fn curve_length<C>(curve: &C) -> f64
+where
+    C: ParametricCurve<Point = Point3, Vector = Vector3> + BoundedCurve,
+{
+    let (t0, t1) = curve.range_tuple();
+    length_between(curve, t0, t1)
+}
+
+/// Parameter at distance `s` from the start of the curve.
+///
+/// `total` is the curve's full length (from [`curve_length`]), passed in so
+/// repeated sampling doesn't re-measure it. `s` is clamped to `[0, total]`.
+pub fn parameter_at_arc_length<C>(curve: &C, s: f64, total: f64) -> f64
+where
+    C: ParametricCurve<Point = Point3, Vector = Vector3> + BoundedCurve,
+{
+    let (t0, t1) = curve.range_tuple();
+    if total <= 0.0 {
+        return t0;
+    }
+    let s = s.clamp(0.0, total);
+
+    // Newton's method on f(t) = length(t0, t) - s, safeguarded by bisection.
+    let (mut lo, mut hi) = (t0, t1);
+    let mut t = t0 + (t1 - t0) * s / total;
+    for _ in 0..32 {
+        let f = length_between(curve, t0, t) - s;
+        if f.abs() < 1e-10 * total.max(1.0) {
+            break;
+        }
+        if f > 0.0 {
+            hi = t;
+        } else {
+            lo = t;
+        }
+        let speed = curve.der(t).magnitude();
+        let newton = if speed > 1e-12 {
+            t - f / speed
+        } else {
+            f64::NAN
+        };
+        t = if newton > lo && newton < hi {
+            newton
+        } else {
+            (lo + hi) / 2.0
+        };
+    }
+    t
+}
+
+/// Point at distance `s` along the curve. See [`parameter_at_arc_length`].
+pub fn evaluate_arc_length<C>(curve: &C, s: f64, total: f64) -> Point3
+where
+    C: ParametricCurve<Point = Point3, Vector = Vector3> + BoundedCurve,
+{
+    curve.subs(parameter_at_arc_length(curve, s, total))
+}
+
+/// `n + 1` points spaced evenly by arc length, including both ends.
+pub fn sample_by_arc_length<C>(curve: &C, n: usize) -> Vec<Point3>
+where
+    C: ParametricCurve<Point = Point3, Vector = Vector3> + BoundedCurve,
+{
+    let n = n.max(1);
+    let total = curve_length(curve);
+    (0..=n)
+        .map(|i| evaluate_arc_length(curve, total * i as f64 / n as f64, total))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use truck_modeling::{BSplineCurve, KnotVec};
+
+    /// A quadratic Bezier whose parameter speed varies strongly along it.
+    fn uneven_bezier() -> BSplineCurve<Point3> {
+        BSplineCurve::new(
+            KnotVec::bezier_knot(2),
+            vec![
+                Point3::new(0.0, 0.0, 0.0),
+                Point3::new(0.2, 0.0, 0.0),
+                Point3::new(4.0, 3.0, 0.0),
+            ],
+        )
+    }
+
+    fn chord_spread(points: &[Point3]) -> f64 {
+        let chords: Vec<f64> = points
+            .windows(2)
+            .map(|w| (w[1] - w[0]).magnitude())
+            .collect();
+        let max = chords.iter().cloned().fold(f64::MIN, f64::max);
+        let min = chords.iter().cloned().fold(f64::MAX, f64::min);
+        max / min
+    }
+
+    #[test]
+    fn test_length_of_straight_segment() {
+        let line = BSplineCurve::new(
+            KnotVec::bezier_knot(1),
+            vec![Point3::new(0.0, 0.0, 0.0), Point3::new(3.0, 4.0, 0.0)],
+        );
+        assert!((curve_length(&line) - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_arc_length_sampling_evens_out_chords() {
+        let curve = uneven_bezier();
+        let n = 10;
+
+        let (t0, t1) = curve.range_tuple();
+        let by_parameter: Vec<Point3> = (0..=n)
+            .map(|i| curve.subs(t0 + (t1 - t0) * i as f64 / n as f64))
+            .collect();
+        let by_length = sample_by_arc_length(&curve, n);
+
+        assert!(chord_spread(&by_parameter) > 1.5);
+        assert!(
+            chord_spread(&by_length) < 1.01,
+            "arc-length chords should be near-equal, spread = {}",
+            chord_spread(&by_length)
+        );
+        assert!((by_length[0] - Point3::new(0.0, 0.0, 0.0)).magnitude() < 1e-9);
+        assert!((by_length[n] - Point3::new(4.0, 3.0, 0.0)).magnitude() < 1e-9);
+    }
+}
@@ -0,0 +1,220 @@
+//! Inverse mapping from a 3D point to a face's surface parameters.
+//!
+//! The (u, v) returned are in the face surface's own parameterization, so
+//! they line up with texture coordinates from the same surface: linear along
+//! a plane, angle-like and height-like on a swept cylinder wall.
+
+use std::collections::HashMap;
+use truck_meshalgo::prelude::*;
+use truck_meshalgo::tessellation::MeshableShape;
+use truck_modeling::topology::{Face, Shell, Solid};
+use truck_modeling::{InnerSpace, ParametricSurface, Point3, SPHint2D, SearchParameter};
+
+/// Newton iterations allowed when inverting the surface.
+const SEARCH_TRIALS: usize = 100;
+
+/// Distance from the surface at which a point still counts as on it.
+const ON_SURFACE_TOLERANCE: f64 = 1e-6;
+
+/// Chord tolerance of the tessellation used to test a point against the
+/// face's trimming boundary.
+const TRIM_MESH_TOLERANCE: f64 = 1e-3;
+
+/// Triangulations of the faces of one solid, made the first time a point is
+/// tested against each face's trimming boundary and kept for later points.
+#[derive(Debug, Default)]
+pub struct TrimMeshes {
+    meshes: HashMap<usize, Option<PolygonMesh>>,
+}
+
+impl TrimMeshes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of faces triangulated so far.
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Whether no face has been triangulated yet.
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// The triangulation of `face`, the `face_index`-th face of the solid,
+    /// made on first use.
+    fn get(&mut self, face_index: usize, face: &Face) -> Option<&PolygonMesh> {
+        self.meshes
+            .entry(face_index)
+            .or_insert_with(|| {
+                Shell::from(vec![face.clone()])
+                    .triangulation(TRIM_MESH_TOLERANCE)
+                    .face_iter()
+                    .next()
+                    .and_then(|meshed| meshed.surface())
+            })
+            .as_ref()
+    }
+}
+
+/// Surface parameters of `point` on the `face_index`-th face of `solid`.
+///
+/// Faces are counted across all shells in order. Returns `None` if there is
+/// no such face, the point is off the face's surface, or it lies on the
+/// surface but outside the face's boundary. `trim_meshes` must only ever be
+/// used with this `solid`.
+pub fn face_uv_at(
+    solid: &Solid,
+    face_index: usize,
+    point: [f64; 3],
+    trim_meshes: &mut TrimMeshes,
+) -> Option<(f64, f64)> {
+    let face: &Face = solid
+        .boundaries()
+        .iter()
+        .flat_map(|shell| shell.face_iter())
+        .nth(face_index)?;
+    let p = Point3::new(point[0], point[1], point[2]);
+
+    let surface = face.surface();
+    let (u, v) = surface.search_parameter(p, SPHint2D::None, SEARCH_TRIALS)?;
+    if surface.subs(u, v).distance(p) > ON_SURFACE_TOLERANCE {
+        return None;
+    }
+
+    // Surfaces extend past their face (planes are unbounded), so check the
+    // point against the face's own triangles.
+    let mesh = trim_meshes.get(face_index, face)?;
+    let positions = mesh.positions();
+    let on_face = mesh.tri_faces().iter().any(|tri| {
+        let [a, b, c] = [0, 1, 2].map(|k| positions[tri[k].pos]);
+        point_triangle_distance(p, a, b, c) <= 2.0 * TRIM_MESH_TOLERANCE
+    });
+    on_face.then_some((u, v))
+}
+
+/// Distance from `p` to the closest point of triangle `abc`.
+fn point_triangle_distance(p: Point3, a: Point3, b: Point3, c: Point3) -> f64 {
+    let (ab, ac, ap) = (b - a, c - a, p - a);
+    let normal = ab.cross(ac);
+    let area2 = normal.magnitude2();
+    if area2 > 0.0 {
+        // Barycentric coordinates of p projected onto the triangle's plane.
+        let w_b = ap.cross(ac).dot(normal) / area2;
+        let w_c = ab.cross(ap).dot(normal) / area2;
+        if w_b >= 0.0 && w_c >= 0.0 && w_b + w_c <= 1.0 {
+            return (ap.dot(normal) / area2.sqrt()).abs();
+        }
+    }
+    [(a, b), (b, c), (c, a)]
+        .into_iter()
+        .map(|(s, e)| {
+            let d = e - s;
+            let t = if d.magnitude2() > 0.0 {
+                ((p - s).dot(d) / d.magnitude2()).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (s + d * t).distance(p)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+    use std::f64::consts::PI;
+    use truck_modeling::geometry::{Plane, Surface};
+    use truck_modeling::topology::Wire;
+    use truck_modeling::{builder, Vector3};
+
+    #[test]
+    fn test_uv_on_plane_matches_its_parameterization() {
+        // A 4 x 2 plate whose top face is the plane o + u (1,0,0) + v (0,1,0)
+        // lifted to z = 1, so (x, y, 1) sits at (u, v) = (x, y).
+        let corners = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)]
+            .map(|(x, y)| builder::vertex(Point3::new(x, y, 0.0)));
+        let wire: Wire = (0..4)
+            .map(|i| builder::line(&corners[i], &corners[(i + 1) % 4]))
+            .collect();
+        let plane = Plane::new(
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        );
+        let face = Face::try_new(vec![wire], Surface::Plane(plane)).unwrap();
+        let plate = builder::tsweep(&face, Vector3::new(0.0, 0.0, 1.0));
+        let face_count = plate.boundaries()[0].face_iter().count();
+
+        let mut trim_meshes = TrimMeshes::new();
+        let mut uv = |f, p| face_uv_at(&plate, f, p, &mut trim_meshes);
+        for (x, y) in [(1.0, 1.0), (0.5, 1.5), (3.25, 0.25), (2.0, 1.0)] {
+            let found: Vec<_> = (0..face_count).filter_map(|f| uv(f, [x, y, 1.0])).collect();
+            assert_eq!(found.len(), 1, "({}, {}) is on the top face only", x, y);
+            let (u, v) = found[0];
+            assert!((u - x).abs() < 1e-9, "u = {}", u);
+            assert!((v - y).abs() < 1e-9, "v = {}", v);
+        }
+        // On the top face's plane but past its edge.
+        for f in 0..face_count {
+            assert_eq!(uv(f, [5.0, 1.0, 1.0]), None);
+        }
+        // Only the top face was ever triangulated, and only once.
+        assert_eq!(trim_meshes.len(), 1);
+    }
+
+    #[test]
+    fn test_uv_on_cylinder_wall_tracks_angle_and_height() {
+        let (r, h) = (1.5, 4.0);
+        let cylinder = primitives::make_cylinder(r, h);
+        let face_count = cylinder.boundaries()[0].face_iter().count();
+        let at = |angle: f64, z: f64| [r * angle.cos(), r * angle.sin(), z];
+        let mut trim_meshes = TrimMeshes::new();
+        let mut locate = |p: [f64; 3]| {
+            (0..face_count)
+                .find_map(|f| face_uv_at(&cylinder, f, p, &mut trim_meshes).map(|uv| (f, uv)))
+                .expect("point on the wall lies on some face")
+        };
+
+        let angle = 0.3 * PI;
+        let (face, low) = locate(at(angle, 1.0));
+        let (mid_face, mid) = locate(at(angle, 2.0));
+        let (high_face, high) = locate(at(angle, 3.0));
+        assert_eq!((face, face), (mid_face, high_face));
+
+        // Moving up the wall changes one parameter, linearly in height, and
+        // leaves the other alone.
+        let (low, mid, high) = ([low.0, low.1], [mid.0, mid.1], [high.0, high.1]);
+        let height = if (high[0] - low[0]).abs() > 1e-6 {
+            0
+        } else {
+            1
+        };
+        let around = 1 - height;
+        assert!((high[around] - low[around]).abs() < 1e-6);
+        assert!((high[height] - mid[height] - (mid[height] - low[height])).abs() < 1e-6);
+
+        // Moving around the wall changes the other one.
+        let (turned_face, turned) = locate(at(angle + 0.05, 1.0));
+        assert_eq!(face, turned_face);
+        let turned = [turned.0, turned.1];
+        assert!((turned[height] - low[height]).abs() < 1e-6);
+        assert!((turned[around] - low[around]).abs() > 1e-4);
+    }
+
+    #[test]
+    fn test_uv_rejects_points_off_the_face() {
+        let cylinder = primitives::make_cylinder(1.0, 2.0);
+        let face_count = cylinder.boundaries()[0].face_iter().count();
+        let mut trim_meshes = TrimMeshes::new();
+        let mut uv = |f, p| face_uv_at(&cylinder, f, p, &mut trim_meshes);
+        for f in 0..face_count {
+            // Inside the solid, and on a cap's plane but outside the disk.
+            assert_eq!(uv(f, [0.2, 0.1, 1.0]), None);
+            assert_eq!(uv(f, [3.0, 0.0, 0.0]), None);
+        }
+        assert_eq!(uv(face_count, [1.0, 0.0, 1.0]), None);
+    }
+}
@@ -1,7 +1,19 @@
+pub mod coplanar;
+pub mod curvature;
+pub mod curve_sampling;
+pub mod face_uv;
+pub mod loft;
+pub mod mesh;
+pub mod mesh_primitives;
 pub mod mock_kernel;
+pub mod planar_shell;
 pub mod primitives;
+pub mod surface_area;
+pub mod surface_intersection;
+pub mod surface_tessellation;
 pub mod tessellation;
 pub mod traits;
+pub mod transform;
 pub mod truck_introspect;
 pub mod truck_kernel;
 pub mod types;
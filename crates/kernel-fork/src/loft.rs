@@ -0,0 +1,218 @@
+//! Lofts between two planar polygonal profiles, and drafted extrusions
+//! built as a loft to an inset copy of the profile.
+//!
+//! Corresponding corners are joined by straight rails, and each pair of
+//! profile edges by a ruled (bilinear) face, so a quad twisted out of plane
+//! stays one face instead of folding into two triangles.
+
+use truck_modeling::geometry::{Curve, Line, Plane, Surface};
+use truck_modeling::topology::{Edge, Face, Shell, Solid, Vertex, Wire};
+use truck_modeling::{
+    builder, BSplineSurface, EuclideanSpace, InnerSpace, KnotVec, Point3, Vector3,
+};
+
+use crate::types::KernelError;
+
+/// How far, relative to its size, a profile corner may sit off the profile's
+/// plane.
+const PLANARITY_TOLERANCE: f64 = 1e-9;
+
+fn loft_failed(message: impl Into<String>) -> KernelError {
+    KernelError::Other {
+        message: message.into(),
+    }
+}
+
+/// A closed solid lofted from the `bottom` polygon to the `top` one.
+///
+/// Both profiles must be planar with the same number of corners, and may be
+/// given in either winding. Corner `i` of the bottom is joined to corner
+/// `i + k` of the top, with the shift `k` chosen to make the rails shortest
+/// in total, so profiles listed from different starting corners don't twist.
+/// The result has one ruled face per profile edge, a plane where the edge
+/// pair is flat, plus the two caps.
+pub fn loft_profiles(bottom: &[Point3], top: &[Point3]) -> Result<Solid, KernelError> {
+    if bottom.len() != top.len() {
+        return Err(KernelError::ProfileMismatch {
+            bottom: bottom.len(),
+            top: top.len(),
+        });
+    }
+    let n = bottom.len();
+    if n < 3 {
+        return Err(loft_failed("loft profiles need at least 3 corners"));
+    }
+    let (mut bottom, mut top) = (bottom.to_vec(), top.to_vec());
+    let mut normal = profile_normal(&bottom)?;
+    if profile_normal(&top)?.dot(normal) < 0.0 {
+        top.reverse();
+    }
+    let rise = normal.dot(Point3::centroid(&top) - Point3::centroid(&bottom));
+    if rise.abs() < PLANARITY_TOLERANCE * size(&bottom) {
+        return Err(loft_failed("loft profiles must not share a plane"));
+    }
+    // Wind both profiles counter-clockwise seen from the top.
+    if rise < 0.0 {
+        bottom.reverse();
+        top.reverse();
+        normal = -normal;
+    }
+    let rail_length = |k: usize| -> f64 {
+        (0..n)
+            .map(|i| (top[(i + k) % n] - bottom[i]).magnitude())
+            .sum()
+    };
+    let shift = (0..n)
+        .min_by(|&a, &b| rail_length(a).total_cmp(&rail_length(b)))
+        .unwrap_or(0);
+    top.rotate_left(shift);
+    build(&bottom, &top, normal)
+}
+
+/// A closed solid swept from the planar `profile` along `sweep`, with its
+/// walls leaning in by `draft_angle` radians (out when negative).
+///
+/// Every edge of the top cap is the matching bottom edge moved inward, in
+/// the profile's plane, by `|sweep| * tan(draft_angle)`, so each wall is a
+/// planar trapezoid. Angles steep enough to turn a top edge around are
+/// rejected.
+pub fn extrude_with_draft(
+    profile: &[Point3],
+    sweep: Vector3,
+    draft_angle: f64,
+) -> Result<Solid, KernelError> {
+    let n = profile.len();
+    if n < 3 {
+        return Err(loft_failed("extruded profiles need at least 3 corners"));
+    }
+    if draft_angle.is_nan() || draft_angle.abs() >= std::f64::consts::FRAC_PI_2 {
+        return Err(loft_failed("draft angle must be less than 90 degrees"));
+    }
+    let mut bottom = profile.to_vec();
+    let mut normal = profile_normal(&bottom)?;
+    let rise = normal.dot(sweep);
+    if rise.abs() < PLANARITY_TOLERANCE * size(&bottom) {
+        return Err(loft_failed("extrude direction lies in the profile's plane"));
+    }
+    if rise < 0.0 {
+        bottom.reverse();
+        normal = -normal;
+    }
+
+    // Counter-clockwise about the normal, the inside is to the left of
+    // every edge; each corner moves to where its two inset edges meet.
+    let inset = sweep.magnitude() * draft_angle.tan();
+    let mut top = Vec::with_capacity(n);
+    for i in 0..n {
+        let (a, p, b) = (bottom[(i + n - 1) % n], bottom[i], bottom[(i + 1) % n]);
+        let (m0, m1) = (
+            normal.cross(p - a).normalize(),
+            normal.cross(b - p).normalize(),
+        );
+        let bend = 1.0 + m0.dot(m1);
+        if bend < PLANARITY_TOLERANCE {
+            return Err(loft_failed("profile folds back on itself"));
+        }
+        top.push(p + (m0 + m1) * (inset / bend) + sweep);
+    }
+    if (0..n).any(|i| {
+        let j = (i + 1) % n;
+        (top[j] - top[i]).dot(bottom[j] - bottom[i]) <= 0.0
+    }) {
+        return Err(loft_failed(format!(
+            "draft angle {} is too steep for the profile",
+            draft_angle
+        )));
+    }
+    build(&bottom, &top, normal)
+}
+
+/// The loft with corner `i` of `bottom` joined to corner `i` of `top`, both
+/// wound counter-clockwise about `normal`, which points from bottom to top.
+fn build(bottom: &[Point3], top: &[Point3], normal: Vector3) -> Result<Solid, KernelError> {
+    let n = bottom.len();
+    let bottom_vertices: Vec<Vertex> = bottom.iter().map(|&p| builder::vertex(p)).collect();
+    let top_vertices: Vec<Vertex> = top.iter().map(|&p| builder::vertex(p)).collect();
+    let line = |a: &Vertex, b: &Vertex| Edge::new(a, b, Curve::Line(Line(a.point(), b.point())));
+    let ring = |vs: &[Vertex]| {
+        (0..n)
+            .map(|i| line(&vs[i], &vs[(i + 1) % n]))
+            .collect::<Vec<_>>()
+    };
+    let (bottom_edges, top_edges) = (ring(&bottom_vertices), ring(&top_vertices));
+    let rails: Vec<Edge> = (0..n)
+        .map(|i| line(&bottom_vertices[i], &top_vertices[i]))
+        .collect();
+
+    let mut faces = Vec::with_capacity(n + 2);
+    for i in 0..n {
+        let j = (i + 1) % n;
+        let wire = Wire::from_iter([
+            bottom_edges[i].clone(),
+            rails[j].clone(),
+            top_edges[i].inverse(),
+            rails[i].inverse(),
+        ]);
+        // u runs along the bottom edge and v up the rails, so the surface
+        // normal points out of the solid. Flat quads get a plane.
+        let (u, v) = (bottom[j] - bottom[i], top[i] - bottom[i]);
+        let flat = u.cross(v).normalize().dot(top[j] - bottom[i]).abs()
+            < PLANARITY_TOLERANCE * size(&bottom);
+        let surface = if flat {
+            Surface::Plane(Plane::new(bottom[i], bottom[j], top[i]))
+        } else {
+            Surface::BSplineSurface(BSplineSurface::new(
+                (KnotVec::bezier_knot(1), KnotVec::bezier_knot(1)),
+                vec![vec![bottom[i], top[i]], vec![bottom[j], top[j]]],
+            ))
+        };
+        let side = Face::try_new(vec![wire], surface)
+            .map_err(|e| loft_failed(format!("loft side face: {}", e)))?;
+        faces.push(side);
+    }
+    let bottom_wire = Wire::from_iter(bottom_edges).inverse();
+    faces.push(cap(bottom_wire, bottom, -normal)?);
+    faces.push(cap(Wire::from_iter(top_edges), top, normal)?);
+
+    Solid::try_new(vec![Shell::from(faces)]).map_err(|e| loft_failed(format!("loft: {}", e)))
+}
+
+/// Planar face bounded by `wire` around `points`, facing along `normal`.
+fn cap(wire: Wire, points: &[Point3], normal: Vector3) -> Result<Face, KernelError> {
+    let origin = Point3::centroid(points);
+    let u = points[1] - points[0];
+    let plane = Plane::new(origin, origin + u, origin + normal.cross(u));
+    Face::try_new(vec![wire], Surface::Plane(plane))
+        .map_err(|e| loft_failed(format!("loft cap: {}", e)))
+}
+
+/// Unit normal of a planar profile by Newell's method, counter-clockwise
+/// about it.
+fn profile_normal(points: &[Point3]) -> Result<Vector3, KernelError> {
+    let mut normal = Vector3::new(0.0, 0.0, 0.0);
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        normal += p.to_vec().cross(q.to_vec());
+    }
+    if normal.magnitude() < PLANARITY_TOLERANCE * size(points).powi(2) {
+        return Err(loft_failed("loft profile has no area"));
+    }
+    let normal = normal.normalize();
+    let origin = Point3::centroid(points);
+    if points
+        .iter()
+        .any(|&p| normal.dot(p - origin).abs() > PLANARITY_TOLERANCE * size(points))
+    {
+        return Err(loft_failed("loft profiles must be planar"));
+    }
+    Ok(normal)
+}
+
+/// Distance from the centroid to the farthest corner.
+fn size(points: &[Point3]) -> f64 {
+    let origin = Point3::centroid(points);
+    points
+        .iter()
+        .map(|&p| (p - origin).magnitude())
+        .fold(0.0, f64::max)
+}
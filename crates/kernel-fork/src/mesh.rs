@@ -0,0 +1,2877 @@
+//! Geometric queries on tessellated `RenderMesh` data.
+//!
+//! These work on any mesh regardless of where it came from — a kernel
+//! tessellation or an imported file — so they never touch B-Rep topology.
+
+use crate::types::{FaceRange, KernelId, RenderMesh, Solidity};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+
+/// Axis-aligned bounding box of a mesh's vertices as `(min, max)`.
+///
+/// Returns `None` for a mesh with no vertices.
+pub fn bounding_box(mesh: &RenderMesh) -> Option<([f64; 3], [f64; 3])> {
+    if mesh.vertices.len() < 3 {
+        return None;
+    }
+    let mut min = [f64::MAX; 3];
+    let mut max = [f64::MIN; 3];
+    for v in mesh.vertices.chunks_exact(3) {
+        for axis in 0..3 {
+            let c = v[axis] as f64;
+            min[axis] = min[axis].min(c);
+            max[axis] = max[axis].max(c);
+        }
+    }
+    Some((min, max))
+}
+
+/// Order-independent key for the edge between two vertices.
+pub fn undirected_edge(a: u32, b: u32) -> (u32, u32) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Edge → incident-triangle map, built once and shared by the mesh queries.
+///
+/// Kernel tessellations duplicate vertices along face seams, so vertices are
+/// first welded by exact position; every id exposed here is such a welded
+/// vertex id rather than a raw mesh index.
+#[derive(Debug, Clone)]
+pub struct EdgeAdjacency {
+    corners: Vec<[u32; 3]>,
+    edges: HashMap<(u32, u32), Vec<usize>>,
+    representatives: Vec<u32>,
+}
+
+impl EdgeAdjacency {
+    pub fn new(mesh: &RenderMesh) -> Self {
+        let mut seen: HashMap<[u32; 3], u32> = HashMap::new();
+        let mut representatives = Vec::new();
+        let ids: Vec<u32> = mesh
+            .vertices
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(i, v)| {
+                let key = [v[0].to_bits(), v[1].to_bits(), v[2].to_bits()];
+                *seen.entry(key).or_insert_with(|| {
+                    representatives.push(i as u32);
+                    representatives.len() as u32 - 1
+                })
+            })
+            .collect();
+
+        let corners: Vec<[u32; 3]> = mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| [ids[t[0] as usize], ids[t[1] as usize], ids[t[2] as usize]])
+            .collect();
+        let mut edges: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (t, c) in corners.iter().enumerate() {
+            for k in 0..3 {
+                edges
+                    .entry(undirected_edge(c[k], c[(k + 1) % 3]))
+                    .or_default()
+                    .push(t);
+            }
+        }
+        Self {
+            corners,
+            edges,
+            representatives,
+        }
+    }
+
+    /// Number of distinct undirected edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Every undirected edge with the triangles that use it.
+    pub fn edges(&self) -> impl Iterator<Item = ((u32, u32), &[usize])> + '_ {
+        self.edges.iter().map(|(&e, tris)| (e, tris.as_slice()))
+    }
+
+    /// Triangles using the edge between welded vertices `a` and `b`.
+    pub fn triangles(&self, a: u32, b: u32) -> &[usize] {
+        self.edges
+            .get(&undirected_edge(a, b))
+            .map_or(&[], |tris| tris.as_slice())
+    }
+
+    /// Welded vertex ids of triangle `t`, in winding order.
+    pub fn corners(&self, t: usize) -> [u32; 3] {
+        self.corners[t]
+    }
+
+    /// Whether triangle `t` walks the edge from `a` to `b` (as opposed to `b` to `a`).
+    pub fn traverses(&self, t: usize, a: u32, b: u32) -> bool {
+        let c = self.corners[t];
+        (0..3).any(|k| c[k] == a && c[(k + 1) % 3] == b)
+    }
+
+    /// A mesh vertex index at the position of welded vertex `id`.
+    pub fn representative(&self, id: u32) -> u32 {
+        self.representatives[id as usize]
+    }
+}
+
+/// Signed volume of the given triangles (positive when wound outward).
+///
+/// Tetrahedra are taken from the centroid of the triangles' corners rather
+/// than the world origin, so a model far from the origin doesn't lose its
+/// volume to cancellation, and the terms are added with Neumaier's
+/// compensated summation.
+fn signed_volume_of(mesh: &RenderMesh, triangles: &[usize]) -> f64 {
+    let pos = |i: u32| {
+        let i = i as usize * 3;
+        [
+            mesh.vertices[i] as f64,
+            mesh.vertices[i + 1] as f64,
+            mesh.vertices[i + 2] as f64,
+        ]
+    };
+    let mut centroid = [0.0; 3];
+    for &t in triangles {
+        for k in 0..3 {
+            for (c, x) in centroid.iter_mut().zip(pos(mesh.indices[t * 3 + k])) {
+                *c += x;
+            }
+        }
+    }
+    let count = (triangles.len() * 3).max(1) as f64;
+    let centroid = centroid.map(|c| c / count);
+
+    let (mut sum, mut compensation) = (0.0f64, 0.0f64);
+    for &t in triangles {
+        let [a, b, c] = [0, 1, 2].map(|k| sub(pos(mesh.indices[t * 3 + k]), centroid));
+        let term = dot(a, cross(b, c)) / 6.0;
+        let next = sum + term;
+        compensation += if sum.abs() >= term.abs() {
+            (sum - next) + term
+        } else {
+            (term - next) + sum
+        };
+        sum = next;
+    }
+    sum + compensation
+}
+
+/// Make triangle winding consistent across every connected component.
+///
+/// Each component is seeded with its lowest-index triangle, whose winding
+/// is kept, and flood-filled across shared edges, flipping any neighbour
+/// that traverses a shared edge in the same direction as the triangle it was
+/// reached from. Closed components that end up inside-out are then flipped as
+/// a whole so they wind outward, each judged by its own signed volume, so
+/// the separate lumps of a split boolean result are all fixed. A shell that
+/// bounds an internal void is flipped outward too, since nothing here tells
+/// a void from a lump. Open components have no inside, so they
+/// keep the seed's winding; the result depends only on triangle order and is
+/// the same on every run. Vertex normals are left untouched.
+///
+/// Returns the number of connected components processed.
+pub fn repair_winding(mesh: &mut RenderMesh) -> usize {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let tri_count = mesh.indices.len() / 3;
+    let mut flipped = vec![false; tri_count];
+    let mut visited = vec![false; tri_count];
+    let mut components = 0;
+    for seed in 0..tri_count {
+        if visited[seed] {
+            continue;
+        }
+        components += 1;
+        visited[seed] = true;
+        let mut queue = VecDeque::from([seed]);
+        let mut members = Vec::new();
+        while let Some(t) = queue.pop_front() {
+            members.push(t);
+            let c = adjacency.corners(t);
+            for k in 0..3 {
+                let (mut a, mut b) = (c[k], c[(k + 1) % 3]);
+                if flipped[t] {
+                    std::mem::swap(&mut a, &mut b);
+                }
+                for &n in adjacency.triangles(a, b) {
+                    if visited[n] {
+                        continue;
+                    }
+                    visited[n] = true;
+                    // Neighbours must walk the shared edge the other way.
+                    flipped[n] = adjacency.traverses(n, a, b);
+                    queue.push_back(n);
+                }
+            }
+        }
+        for &t in &members {
+            if flipped[t] {
+                mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+            }
+        }
+        let closed = members.iter().all(|&t| {
+            let c = adjacency.corners(t);
+            (0..3).all(|k| adjacency.triangles(c[k], c[(k + 1) % 3]).len() == 2)
+        });
+        if closed && signed_volume_of(mesh, &members) < 0.0 {
+            for &t in &members {
+                mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+            }
+        }
+    }
+    components
+}
+
+/// Edge-level defect counts for a triangle mesh. See [`validate_mesh`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MeshValidation {
+    /// Edges used by exactly one triangle (holes or open borders).
+    pub boundary_edges: usize,
+    /// Edges shared by two triangles that both traverse it in the same
+    /// direction — a winding flip rather than a hole.
+    pub flipped_edges: usize,
+    /// Edges shared by more than two triangles.
+    pub non_manifold_edges: usize,
+    /// Closed loops the boundary edges form, i.e. the number of holes or
+    /// open borders. See [`boundary_loops`].
+    pub boundary_loop_count: usize,
+}
+
+impl MeshValidation {
+    /// True when every edge is shared by exactly two consistently wound triangles.
+    pub fn is_closed_manifold(&self) -> bool {
+        self.boundary_edges == 0 && self.flipped_edges == 0 && self.non_manifold_edges == 0
+    }
+
+    /// True when the mesh is valid for a body of the given solidity: closed
+    /// manifold for a solid, and manifold with open borders allowed for a
+    /// sheet.
+    pub fn is_valid(&self, solidity: Solidity) -> bool {
+        match solidity {
+            Solidity::Solid => self.is_closed_manifold(),
+            Solidity::Sheet => self.flipped_edges == 0 && self.non_manifold_edges == 0,
+        }
+    }
+}
+
+/// Classify every edge of the mesh, matching vertices by position.
+pub fn validate_mesh(mesh: &RenderMesh) -> MeshValidation {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let mut report = MeshValidation::default();
+    for ((a, b), tris) in adjacency.edges() {
+        match tris {
+            [_] => report.boundary_edges += 1,
+            [t, u] if adjacency.traverses(*t, a, b) == adjacency.traverses(*u, a, b) => {
+                report.flipped_edges += 1
+            }
+            [_, _] => {}
+            _ => report.non_manifold_edges += 1,
+        }
+    }
+    if report.boundary_edges > 0 {
+        report.boundary_loop_count = boundary_loops(mesh).len();
+    }
+    report
+}
+
+/// Signed volume enclosed by the mesh; positive when triangles wind outward.
+///
+/// Only meaningful for closed meshes.
+pub fn signed_volume(mesh: &RenderMesh) -> f64 {
+    let all: Vec<usize> = (0..mesh.indices.len() / 3).collect();
+    signed_volume_of(mesh, &all)
+}
+
+/// Whether `point` lies inside the closed mesh, by the parity of crossings
+/// along a ray.
+///
+/// The ray is tilted off the coordinate axes so it doesn't graze the edges
+/// of axis-aligned faces. Points on the surface may go either way.
+pub fn contains_point(mesh: &RenderMesh, point: [f64; 3]) -> bool {
+    let dir = [0.577_215_66, 0.618_033_99, 0.532_088_89];
+    let len = norm(dir);
+    let dir = [dir[0] / len, dir[1] / len, dir[2] / len];
+    mesh_triangles(mesh)
+        .filter(|tri| ray_hits_triangle(point, dir, tri))
+        .count()
+        % 2
+        == 1
+}
+
+/// Total area of the mesh's triangles, summed in `f64`.
+pub fn surface_area(mesh: &RenderMesh) -> f64 {
+    mesh_triangles(mesh)
+        .map(|tri| norm(triangle_normal(&tri)) / 2.0)
+        .sum()
+}
+
+/// Cross-section of a mesh by the plane through `point` with normal
+/// `normal`, as polylines of the points where the plane crosses its edges.
+///
+/// Vertices exactly on the plane count as lying on its positive side, so a
+/// plane that only touches a vertex, an edge or a face contributes nothing
+/// and no zero-length segments appear. Segments are stitched through the
+/// mesh edges they cross: a closed manifold gives closed loops, running
+/// counter-clockwise seen from the positive side around material, with
+/// the first point not repeated at the end. Open borders leave open chains.
+/// Points where a loop runs straight on are dropped, so a box cut through
+/// its middle gives a four-corner rectangle.
+pub fn section(mesh: &RenderMesh, point: [f64; 3], normal: [f64; 3]) -> Vec<Vec<[f64; 3]>> {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let position = |id: u32| {
+        let i = adjacency.representative(id) as usize * 3;
+        [0, 1, 2].map(|k| mesh.vertices[i + k] as f64)
+    };
+    let distance = |id: u32| dot(sub(position(id), point), normal);
+
+    // Each cut triangle leads from the edge where its boundary dives below
+    // the plane to the edge where it comes back up.
+    let mut next: HashMap<(u32, u32), (u32, u32)> = HashMap::new();
+    for t in 0..mesh.indices.len() / 3 {
+        let c = adjacency.corners(t);
+        let below = c.map(|v| distance(v) < 0.0);
+        let (mut down, mut up) = (None, None);
+        for k in 0..3 {
+            let (a, b) = (c[k], c[(k + 1) % 3]);
+            match (below[k], below[(k + 1) % 3]) {
+                (false, true) => down = Some(undirected_edge(a, b)),
+                (true, false) => up = Some(undirected_edge(a, b)),
+                _ => {}
+            }
+        }
+        if let (Some(down), Some(up)) = (down, up) {
+            next.insert(down, up);
+        }
+    }
+
+    let crossing = |(a, b): (u32, u32)| {
+        let (da, db) = (distance(a), distance(b));
+        let s = da / (da - db);
+        let (pa, pb) = (position(a), position(b));
+        [0, 1, 2].map(|k| pa[k] + (pb[k] - pa[k]) * s)
+    };
+    let mut starts: Vec<(u32, u32)> = next.keys().copied().collect();
+    starts.sort_unstable();
+    // Chain heads (an edge nothing leads into) first, then the loops.
+    let targets: std::collections::HashSet<(u32, u32)> = next.values().copied().collect();
+    starts.sort_by_key(|e| targets.contains(e));
+
+    let mut polylines = Vec::new();
+    for start in starts {
+        if !next.contains_key(&start) {
+            continue;
+        }
+        let closed = targets.contains(&start);
+        let mut edge = start;
+        let mut points = vec![crossing(edge)];
+        while let Some(to) = next.remove(&edge) {
+            if to == start {
+                break;
+            }
+            points.push(crossing(to));
+            edge = to;
+        }
+        polylines.push(drop_straight_points(points, closed));
+    }
+    polylines
+}
+
+/// Remove points where a polyline carries straight on (or doubles back on
+/// a zero-length step). Ends of an open polyline are kept.
+fn drop_straight_points(points: Vec<[f64; 3]>, closed: bool) -> Vec<[f64; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return points;
+    }
+    let keep = |i: usize| {
+        if !closed && (i == 0 || i == n - 1) {
+            return true;
+        }
+        let (prev, here, next) = (points[(i + n - 1) % n], points[i], points[(i + 1) % n]);
+        let (d1, d2) = (sub(here, prev), sub(next, here));
+        let scale = norm(d1) * norm(d2);
+        scale > 0.0 && (norm(cross(d1, d2)) > 1e-9 * scale || dot(d1, d2) < 0.0)
+    };
+    (0..n).filter(|&i| keep(i)).map(|i| points[i]).collect()
+}
+
+/// Walk boundary edges into closed loops of vertex indices, in the direction
+/// the adjacent triangles traverse them.
+///
+/// Vertices are matched by position, and each loop lists one mesh index per
+/// corner. A closed mesh has none; a lone triangle is one loop of three.
+pub fn boundary_loops(mesh: &RenderMesh) -> Vec<Vec<u32>> {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let mut next: HashMap<u32, u32> = HashMap::new();
+    for ((a, b), tris) in adjacency.edges() {
+        if let [t] = tris {
+            if adjacency.traverses(*t, a, b) {
+                next.insert(a, b);
+            } else {
+                next.insert(b, a);
+            }
+        }
+    }
+
+    let mut starts: Vec<u32> = next.keys().copied().collect();
+    starts.sort_unstable();
+    let mut loops = Vec::new();
+    for start in starts {
+        let mut ring = Vec::new();
+        let mut current = start;
+        while let Some(to) = next.remove(&current) {
+            ring.push(adjacency.representative(current));
+            current = to;
+        }
+        if ring.len() >= 3 && current == start {
+            loops.push(ring);
+        }
+    }
+    loops
+}
+
+/// Cap every boundary loop with at most `max_loop_edges` edges.
+///
+/// Each loop is ear-clipped in its best-fit (Newell) plane, so caps stay
+/// inside non-convex holes and no vertices are added. Caps are wound to
+/// match the surrounding triangles. Larger loops are left open. Returns the
+/// number of loops filled.
+pub fn fill_holes(mesh: &mut RenderMesh, max_loop_edges: usize) -> usize {
+    let mut filled = 0;
+    for mut ring in boundary_loops(mesh) {
+        if ring.len() > max_loop_edges {
+            continue;
+        }
+        // The cap walks the loop the other way round from its neighbours.
+        ring.reverse();
+        let pos = |i: u32| {
+            let i = i as usize * 3;
+            [
+                mesh.vertices[i] as f64,
+                mesh.vertices[i + 1] as f64,
+                mesh.vertices[i + 2] as f64,
+            ]
+        };
+        let mut normal = [0.0; 3];
+        for (k, &v) in ring.iter().enumerate() {
+            let p = pos(v);
+            let q = pos(ring[(k + 1) % ring.len()]);
+            // Newell's method.
+            normal[0] += (p[1] - q[1]) * (p[2] + q[2]);
+            normal[1] += (p[2] - q[2]) * (p[0] + q[0]);
+            normal[2] += (p[0] - q[0]) * (p[1] + q[1]);
+        }
+        // Project into the plane so the cap runs counter-clockwise.
+        let axis = if normal[0].abs() < 0.5 * norm(normal) {
+            [1.0, 0.0, 0.0]
+        } else {
+            [0.0, 1.0, 0.0]
+        };
+        let u = cross(axis, normal);
+        let v = cross(normal, u);
+        let flat: Vec<[f64; 2]> = ring
+            .iter()
+            .map(|&i| {
+                let p = pos(i);
+                [dot(p, u), dot(p, v)]
+            })
+            .collect();
+        for [a, b, c] in ear_clip(&flat) {
+            mesh.indices.extend([ring[a], ring[b], ring[c]]);
+        }
+        filled += 1;
+    }
+    filled
+}
+
+/// Triangulate a simple counter-clockwise polygon by ear clipping, as
+/// corner indices; every polygon of n corners yields n - 2 triangles.
+///
+/// Degenerate or self-touching polygons can run out of proper ears. The
+/// remaining polygon is then split along its shortest diagonal that stays
+/// inside it and crosses no edge, and each half is clipped on its own, so
+/// triangles never overlap. Only when no such diagonal exists either (the
+/// rest is collinear) is the most convex corner clipped regardless.
+fn ear_clip(polygon: &[[f64; 2]]) -> Vec<[usize; 3]> {
+    let mut triangles = Vec::with_capacity(polygon.len().saturating_sub(2));
+    clip_ears(polygon, (0..polygon.len()).collect(), &mut triangles);
+    triangles
+}
+
+/// Twice the signed area of triangle `abc`; positive when counter-clockwise.
+fn turn(a: [f64; 2], b: [f64; 2], c: [f64; 2]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
+
+fn clip_ears(polygon: &[[f64; 2]], mut left: Vec<usize>, triangles: &mut Vec<[usize; 3]>) {
+    while left.len() > 3 {
+        let n = left.len();
+        let corner = |k: usize| {
+            let [a, b, c] = [left[(k + n - 1) % n], left[k], left[(k + 1) % n]];
+            ([a, b, c], [polygon[a], polygon[b], polygon[c]])
+        };
+        let is_ear = |k: usize| {
+            let (ids, [a, b, c]) = corner(k);
+            turn(a, b, c) > 0.0
+                && left.iter().all(|&i| {
+                    let p = polygon[i];
+                    ids.contains(&i)
+                        || p == a
+                        || p == b
+                        || p == c
+                        || turn(a, b, p) < 0.0
+                        || turn(b, c, p) < 0.0
+                        || turn(c, a, p) < 0.0
+                })
+        };
+        if let Some(k) = (0..n).find(|&k| is_ear(k)) {
+            triangles.push(corner(k).0);
+            left.remove(k);
+            continue;
+        }
+        if let Some((i, j)) = shortest_inner_diagonal(polygon, &left) {
+            let mut other = left[j..].to_vec();
+            other.extend_from_slice(&left[..=i]);
+            left.truncate(j + 1);
+            left.drain(..i);
+            clip_ears(polygon, other, triangles);
+            continue;
+        }
+        let k = (0..n)
+            .max_by(|&i, &j| {
+                let (_, [a, b, c]) = corner(i);
+                let (_, [d, e, f]) = corner(j);
+                turn(a, b, c).total_cmp(&turn(d, e, f))
+            })
+            .unwrap_or(0);
+        triangles.push(corner(k).0);
+        left.remove(k);
+    }
+    if let [a, b, c] = left[..] {
+        triangles.push([a, b, c]);
+    }
+}
+
+/// Positions `(i, j)`, `i < j`, in `left` of the shortest diagonal that
+/// properly crosses no edge of the polygon `left` outlines and whose
+/// midpoint lies strictly inside it.
+fn shortest_inner_diagonal(polygon: &[[f64; 2]], left: &[usize]) -> Option<(usize, usize)> {
+    let n = left.len();
+    let outline: Vec<[f64; 2]> = left.iter().map(|&i| polygon[i]).collect();
+    let crosses = |p: [f64; 2], q: [f64; 2], a: [f64; 2], b: [f64; 2]| {
+        turn(p, q, a) * turn(p, q, b) < 0.0 && turn(a, b, p) * turn(a, b, q) < 0.0
+    };
+    let mut best: Option<(f64, (usize, usize))> = None;
+    for i in 0..n {
+        for j in i + 2..n {
+            if i == 0 && j == n - 1 {
+                continue;
+            }
+            let (p, q) = (outline[i], outline[j]);
+            let length = (q[0] - p[0]).hypot(q[1] - p[1]);
+            if length == 0.0 || best.is_some_and(|(l, _)| l <= length) {
+                continue;
+            }
+            let mid = [(p[0] + q[0]) / 2.0, (p[1] + q[1]) / 2.0];
+            let on_outline = (0..n).any(|k| {
+                let (a, b) = (outline[k], outline[(k + 1) % n]);
+                turn(a, b, mid) == 0.0
+                    && mid[0] >= a[0].min(b[0])
+                    && mid[0] <= a[0].max(b[0])
+                    && mid[1] >= a[1].min(b[1])
+                    && mid[1] <= a[1].max(b[1])
+            });
+            if on_outline
+                || !point_in_polygon_2d(mid, &outline)
+                || (0..n).any(|k| crosses(p, q, outline[k], outline[(k + 1) % n]))
+            {
+                continue;
+            }
+            best = Some((length, (i, j)));
+        }
+    }
+    best.map(|(_, diagonal)| diagonal)
+}
+
+/// Even-odd test of `point` against a closed polygon.
+fn point_in_polygon_2d(point: [f64; 2], polygon: &[[f64; 2]]) -> bool {
+    let mut inside = false;
+    for (k, &a) in polygon.iter().enumerate() {
+        let b = polygon[(k + 1) % polygon.len()];
+        if (a[1] > point[1]) != (b[1] > point[1])
+            && point[0] < a[0] + (point[1] - a[1]) / (b[1] - a[1]) * (b[0] - a[0])
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// Volume of a slightly open mesh, estimated by capping its holes on a copy.
+///
+/// The input is left untouched.
+pub fn estimated_volume(mesh: &RenderMesh) -> f64 {
+    let mut capped = mesh.clone();
+    fill_holes(&mut capped, usize::MAX);
+    signed_volume(&capped).abs()
+}
+
+/// What [`repair_mesh`] changed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Duplicate triangle pairs merged, see [`merge_duplicate_faces`].
+    pub merged_duplicates: usize,
+    /// Triangles whose winding was reversed.
+    pub rewound_triangles: usize,
+    /// Boundary loops capped, see [`fill_holes`].
+    pub filled_holes: usize,
+}
+
+impl RepairReport {
+    /// True when the mesh needed no changes.
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Repair a mesh for export: merge duplicate faces, make winding consistent
+/// and outward, then cap every hole.
+pub fn repair_mesh(mesh: &mut RenderMesh) -> RepairReport {
+    let merged_duplicates = merge_duplicate_faces(mesh);
+    let before = mesh.indices.clone();
+    repair_winding(mesh);
+    let filled_holes = fill_holes(mesh, usize::MAX);
+    // Open shells keep their seed's winding, so a shell the caps just closed
+    // may still be inside-out; orient it now that it has an inside.
+    repair_winding(mesh);
+    RepairReport {
+        merged_duplicates,
+        rewound_triangles: rewound_count(&before, &mesh.indices),
+        filled_holes,
+    }
+}
+
+/// Fix the defects that need no new geometry — duplicate faces and
+/// inconsistent or inward winding — then validate the result.
+///
+/// Unlike [`repair_mesh`] holes are left open, so any `boundary_edges` in the
+/// returned validation are real gaps still to be dealt with.
+pub fn repair_and_verify(mesh: &mut RenderMesh) -> (RepairReport, MeshValidation) {
+    let report = merge_and_rewind(mesh);
+    (report, validate_mesh(mesh))
+}
+
+fn merge_and_rewind(mesh: &mut RenderMesh) -> RepairReport {
+    let merged_duplicates = merge_duplicate_faces(mesh);
+    let before = mesh.indices.clone();
+    repair_winding(mesh);
+    RepairReport {
+        merged_duplicates,
+        rewound_triangles: rewound_count(&before, &mesh.indices),
+        filled_holes: 0,
+    }
+}
+
+/// Triangles of `before` whose corners differ in `after`; triangles appended
+/// to `after` are not counted.
+fn rewound_count(before: &[u32], after: &[u32]) -> usize {
+    before
+        .chunks_exact(3)
+        .zip(after.chunks_exact(3))
+        .filter(|(a, b)| a != b)
+        .count()
+}
+
+/// Faces whose triangles wind so their normal points into the solid.
+///
+/// For each face range a ray is cast from a point on its largest triangle
+/// along the winding normal; an odd number of crossings with every other
+/// triangle, the face's own included, means the normal points inward. The
+/// ray start and direction are nudged off-axis so it does not run along the
+/// seams of axis-aligned faces. The mesh should be closed for the result to
+/// be meaningful.
+pub fn misoriented_faces(mesh: &RenderMesh) -> Vec<KernelId> {
+    let pos = |i: u32| {
+        let i = i as usize * 3;
+        [
+            mesh.vertices[i] as f64,
+            mesh.vertices[i + 1] as f64,
+            mesh.vertices[i + 2] as f64,
+        ]
+    };
+    let triangle = |t: usize| {
+        [
+            pos(mesh.indices[t * 3]),
+            pos(mesh.indices[t * 3 + 1]),
+            pos(mesh.indices[t * 3 + 2]),
+        ]
+    };
+    let Some((min, max)) = bounding_box(mesh) else {
+        return Vec::new();
+    };
+    let diagonal = norm(sub(max, min));
+
+    let mut inward = Vec::new();
+    for range in &mesh.face_ranges {
+        let own = range.start_index as usize / 3..range.end_index as usize / 3;
+        let Some(largest) = own.clone().max_by(|&a, &b| {
+            let area = |t| norm(triangle_normal(&triangle(t)));
+            area(a).total_cmp(&area(b))
+        }) else {
+            continue;
+        };
+        let [a, b, c] = triangle(largest);
+        let n = triangle_normal(&[a, b, c]);
+        let len = norm(n);
+        if len == 0.0 {
+            continue;
+        }
+        let mut dir = [
+            n[0] / len + 1.3e-3,
+            n[1] / len + 0.7e-3,
+            n[2] / len + 1.1e-3,
+        ];
+        let dir_len = norm(dir);
+        dir = [dir[0] / dir_len, dir[1] / dir_len, dir[2] / dir_len];
+        let mut origin = [0.0; 3];
+        for axis in 0..3 {
+            origin[axis] =
+                0.47 * a[axis] + 0.31 * b[axis] + 0.22 * c[axis] + dir[axis] * diagonal * 1e-6;
+        }
+
+        let crossings = (0..mesh.indices.len() / 3)
+            .filter(|&t| t != largest)
+            .filter(|&t| ray_hits_triangle(origin, dir, &triangle(t)))
+            .count();
+        if crossings % 2 == 1 {
+            inward.push(range.face_id);
+        }
+    }
+    inward
+}
+
+/// Steepest overhang, in degrees from vertical, printable without supports.
+pub const SUPPORT_ANGLE_DEGREES: f64 = 45.0;
+
+/// Area of downward-facing triangles that would need support when printed
+/// with `up` as the build direction.
+///
+/// A triangle needs support when its wall leans out past
+/// `max_overhang_degrees` from vertical. Triangles lying on the build plate
+/// (the lowest point along `up`) are not counted.
+pub fn overhang_area(mesh: &RenderMesh, up: [f64; 3], max_overhang_degrees: f64) -> f64 {
+    let up_len = norm(up);
+    if up_len == 0.0 {
+        return 0.0;
+    }
+    let up = [up[0] / up_len, up[1] / up_len, up[2] / up_len];
+    let threshold = -max_overhang_degrees.to_radians().sin();
+    let (plate, eps) = plate_height(mesh, up);
+    let mut area = 0.0;
+    for tri in mesh_triangles(mesh) {
+        let n = triangle_normal(&tri);
+        let len = norm(n);
+        if len == 0.0 || dot(n, up) / len >= threshold {
+            continue;
+        }
+        if tri.iter().all(|p| dot(*p, up) - plate < eps) {
+            continue;
+        }
+        area += len / 2.0;
+    }
+    area
+}
+
+/// Pick the build direction with the least overhang area.
+///
+/// Tries the six axis directions plus up to `candidate_count` orientations
+/// that put one of the largest distinct face normals down. Ties are broken
+/// in favour of the larger footprint on the build plate. Returns the up
+/// direction and its overhang area (see [`overhang_area`]).
+pub fn best_print_orientation(mesh: &RenderMesh, candidate_count: usize) -> ([f32; 3], f64) {
+    let mut candidates: Vec<[f64; 3]> = vec![
+        [1.0, 0.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [0.0, -1.0, 0.0],
+        [0.0, 0.0, 1.0],
+        [0.0, 0.0, -1.0],
+    ];
+    let mut by_area: Vec<([f64; 3], f64)> = mesh_triangles(mesh)
+        .map(|tri| triangle_normal(&tri))
+        .filter(|n| norm(*n) > 0.0)
+        .map(|n| {
+            let len = norm(n);
+            ([-n[0] / len, -n[1] / len, -n[2] / len], len)
+        })
+        .collect();
+    by_area.sort_by(|a, b| b.1.total_cmp(&a.1));
+    let mut added = 0;
+    for (up, _) in by_area {
+        if added == candidate_count {
+            break;
+        }
+        if candidates.iter().all(|c| dot(*c, up) < 1.0 - 1e-6) {
+            candidates.push(up);
+            added += 1;
+        }
+    }
+
+    let mut best = ([0.0, 0.0, 1.0], f64::INFINITY, 0.0);
+    for up in candidates {
+        let overhang = overhang_area(mesh, up, SUPPORT_ANGLE_DEGREES);
+        let contact = contact_area(mesh, up);
+        if overhang < best.1 - 1e-9 || (overhang < best.1 + 1e-9 && contact > best.2 + 1e-9) {
+            best = (up, overhang, contact);
+        }
+    }
+    let (up, overhang, _) = best;
+    ([up[0] as f32, up[1] as f32, up[2] as f32], overhang)
+}
+
+/// Area of downward-facing triangles resting on the build plate.
+fn contact_area(mesh: &RenderMesh, up: [f64; 3]) -> f64 {
+    let (plate, eps) = plate_height(mesh, up);
+    mesh_triangles(mesh)
+        .filter(|tri| tri.iter().all(|p| dot(*p, up) - plate < eps))
+        .map(|tri| triangle_normal(&tri))
+        .filter(|n| dot(*n, up) < 0.0)
+        .map(|n| norm(n) / 2.0)
+        .sum()
+}
+
+/// Lowest vertex height along `up`, with a matching distance tolerance.
+fn plate_height(mesh: &RenderMesh, up: [f64; 3]) -> (f64, f64) {
+    let plate = mesh
+        .vertices
+        .chunks_exact(3)
+        .map(|v| dot([v[0] as f64, v[1] as f64, v[2] as f64], up))
+        .fold(f64::INFINITY, f64::min);
+    let eps = bounding_box(mesh).map_or(0.0, |(min, max)| norm(sub(max, min)) * 1e-6);
+    (plate, eps)
+}
+
+fn mesh_triangles(mesh: &RenderMesh) -> impl Iterator<Item = [[f64; 3]; 3]> + '_ {
+    let pos = |i: u32| {
+        let i = i as usize * 3;
+        [
+            mesh.vertices[i] as f64,
+            mesh.vertices[i + 1] as f64,
+            mesh.vertices[i + 2] as f64,
+        ]
+    };
+    mesh.indices
+        .chunks_exact(3)
+        .map(move |t| [pos(t[0]), pos(t[1]), pos(t[2])])
+}
+
+/// Triangle shape statistics for a mesh. See [`mesh_quality`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeshQuality {
+    pub triangle_count: usize,
+    pub min_area: f64,
+    pub max_area: f64,
+    pub mean_area: f64,
+    /// Standard deviation of triangle area over the mean; 0 for a mesh of
+    /// identical triangles.
+    pub area_variation: f64,
+    /// Smallest interior angle of any triangle, in degrees.
+    pub min_angle_degrees: f64,
+}
+
+/// Summarize triangle areas and angles, to compare tessellations of the
+/// same shape.
+pub fn mesh_quality(mesh: &RenderMesh) -> MeshQuality {
+    let mut areas = Vec::with_capacity(mesh.indices.len() / 3);
+    let mut min_angle = f64::INFINITY;
+    for tri in mesh_triangles(mesh) {
+        areas.push(norm(triangle_normal(&tri)) / 2.0);
+        for k in 0..3 {
+            let u = sub(tri[(k + 1) % 3], tri[k]);
+            let v = sub(tri[(k + 2) % 3], tri[k]);
+            let lengths = norm(u) * norm(v);
+            let angle = if lengths > 0.0 {
+                (dot(u, v) / lengths).clamp(-1.0, 1.0).acos()
+            } else {
+                0.0
+            };
+            min_angle = min_angle.min(angle.to_degrees());
+        }
+    }
+    if areas.is_empty() {
+        return MeshQuality::default();
+    }
+
+    let n = areas.len() as f64;
+    let mean = areas.iter().sum::<f64>() / n;
+    let variance = areas.iter().map(|a| (a - mean).powi(2)).sum::<f64>() / n;
+    MeshQuality {
+        triangle_count: areas.len(),
+        min_area: areas.iter().cloned().fold(f64::INFINITY, f64::min),
+        max_area: areas.iter().cloned().fold(0.0, f64::max),
+        mean_area: mean,
+        area_variation: if mean > 0.0 {
+            variance.sqrt() / mean
+        } else {
+            0.0
+        },
+        min_angle_degrees: min_angle,
+    }
+}
+
+/// Whether two meshes describe the same triangles, ignoring vertex and
+/// triangle order and how vertices are shared.
+///
+/// Positions are snapped to a `tol` grid; the welded vertex sets and the
+/// triangle sets (each rotated to start at its smallest corner, winding kept)
+/// must then match exactly. Positions within `tol` of each other can still
+/// land in different cells, so pick `tol` well below feature size.
+pub fn meshes_equivalent(a: &RenderMesh, b: &RenderMesh, tol: f64) -> bool {
+    type Cell = [i64; 3];
+    fn canonical(mesh: &RenderMesh, tol: f64) -> (Vec<Cell>, Vec<[Cell; 3]>) {
+        let cell = |i: u32| {
+            let i = i as usize * 3;
+            [0, 1, 2].map(|k| (mesh.vertices[i + k] as f64 / tol).round() as i64)
+        };
+        let mut vertices: Vec<Cell> = (0..mesh.vertices.len() as u32 / 3).map(cell).collect();
+        vertices.sort_unstable();
+        vertices.dedup();
+        let mut triangles: Vec<[Cell; 3]> = mesh
+            .indices
+            .chunks_exact(3)
+            .map(|t| {
+                let mut tri = [cell(t[0]), cell(t[1]), cell(t[2])];
+                let first = (0..3).min_by_key(|&k| tri[k]).unwrap();
+                tri.rotate_left(first);
+                tri
+            })
+            .collect();
+        triangles.sort_unstable();
+        (vertices, triangles)
+    }
+    tol > 0.0 && canonical(a, tol) == canonical(b, tol)
+}
+
+/// Round positions and normals to `decimals` decimal places in place.
+///
+/// Tessellation output can differ in the last bits between platforms;
+/// quantizing before writing golden files or exports makes the bytes stable.
+pub fn quantize(mesh: &mut RenderMesh, decimals: u32) {
+    let scale = 10f64.powi(decimals as i32);
+    for v in mesh.vertices.iter_mut().chain(mesh.normals.iter_mut()) {
+        let rounded = ((*v as f64) * scale).round() / scale;
+        // Avoid writing -0.0 so sign noise around zero cannot differ either.
+        *v = if rounded == 0.0 { 0.0 } else { rounded as f32 };
+    }
+}
+
+/// Remove coincident triangles left where two bodies touch face to face.
+///
+/// Triangles over the same welded vertices are matched up: an oppositely
+/// wound pair is an internal wall between the operands and both are dropped;
+/// a same-wound duplicate keeps one copy. Face ranges are shrunk to match.
+/// truck rejects booleans with coplanar faces outright, so this is for meshes
+/// combined outside the kernel (imports, concatenated bodies). Returns the
+/// number of duplicate pairs merged.
+pub fn merge_duplicate_faces(mesh: &mut RenderMesh) -> usize {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let tri_count = mesh.indices.len() / 3;
+    let mut by_corners: HashMap<[u32; 3], Vec<usize>> = HashMap::new();
+    for t in 0..tri_count {
+        let mut key = adjacency.corners(t);
+        key.sort_unstable();
+        by_corners.entry(key).or_default().push(t);
+    }
+
+    let mut remove = vec![false; tri_count];
+    let mut merged = 0;
+    let mut groups: Vec<Vec<usize>> = by_corners.into_values().filter(|g| g.len() > 1).collect();
+    groups.sort();
+    for group in groups {
+        let mut open: Vec<usize> = Vec::new();
+        for t in group {
+            let [a, b, _] = adjacency.corners(t);
+            match open.pop() {
+                Some(o) => {
+                    // Same winding: keep the first copy; opposite: drop both.
+                    if !adjacency.traverses(o, a, b) {
+                        remove[o] = true;
+                    }
+                    remove[t] = true;
+                    merged += 1;
+                }
+                None => open.push(t),
+            }
+        }
+    }
+    if merged == 0 {
+        return 0;
+    }
+
+    remove_triangles(mesh, &remove);
+    merged
+}
+
+/// Drop the triangles flagged in `remove`, shrinking face ranges to match
+/// and dropping ranges left empty.
+fn remove_triangles(mesh: &mut RenderMesh, remove: &[bool]) {
+    let tri_count = mesh.indices.len() / 3;
+    let mut indices = Vec::with_capacity(mesh.indices.len());
+    let mut new_start = vec![0u32; tri_count + 1];
+    for t in 0..tri_count {
+        new_start[t] = indices.len() as u32;
+        if !remove[t] {
+            indices.extend_from_slice(&mesh.indices[t * 3..t * 3 + 3]);
+        }
+    }
+    new_start[tri_count] = indices.len() as u32;
+    for range in &mut mesh.face_ranges {
+        range.start_index = new_start[range.start_index as usize / 3];
+        range.end_index = new_start[range.end_index as usize / 3];
+    }
+    mesh.face_ranges.retain(|r| r.end_index > r.start_index);
+    mesh.indices = indices;
+}
+
+/// Union-find lookup with path halving.
+fn root(parent: &mut [u32], mut x: u32) -> u32 {
+    while parent[x as usize] != x {
+        parent[x as usize] = parent[parent[x as usize] as usize];
+        x = parent[x as usize];
+    }
+    x
+}
+
+/// Collapse passes [`defeature`] makes before giving up on a face.
+const DEFEATURE_MAX_PASSES: usize = 8;
+
+/// Counts of what [`defeature`] removed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DefeatureReport {
+    /// Edges collapsed to a point.
+    pub collapsed_edges: usize,
+    /// Triangles that became degenerate and were dropped.
+    pub removed_triangles: usize,
+    /// Face ranges that lost all their triangles.
+    pub removed_faces: usize,
+}
+
+/// Remove slivers: collapse edges shorter than `min_edge_length` and shrink
+/// away faces whose area is below `min_face_area`.
+///
+/// Collapsed vertices move to the centroid of everything merged into them,
+/// and triangles left with a repeated corner are dropped, so a thin strip
+/// between two faces disappears while the faces either side close the gap.
+/// A small face goes by collapsing the shortest edge of each of its
+/// triangles, repeated until it is gone.
+pub fn defeature(
+    mesh: &mut RenderMesh,
+    min_edge_length: f64,
+    min_face_area: f64,
+) -> DefeatureReport {
+    let mut report = DefeatureReport::default();
+    for _ in 0..DEFEATURE_MAX_PASSES {
+        let adjacency = EdgeAdjacency::new(mesh);
+        let welded_count = adjacency.representatives.len();
+        let position = |id: u32| {
+            let v = adjacency.representative(id) as usize * 3;
+            [0, 1, 2].map(|k| mesh.vertices[v + k] as f64)
+        };
+        let length = |(a, b): (u32, u32)| norm(sub(position(a), position(b)));
+
+        let mut collapse: Vec<(u32, u32)> = adjacency
+            .edges()
+            .map(|(e, _)| e)
+            .filter(|&e| length(e) < min_edge_length)
+            .collect();
+        for range in &mesh.face_ranges {
+            let tris = range.start_index as usize / 3..range.end_index as usize / 3;
+            let area: f64 = tris
+                .clone()
+                .map(|t| norm(triangle_normal(&adjacency.corners(t).map(position))) / 2.0)
+                .sum();
+            if area >= min_face_area {
+                continue;
+            }
+            for t in tris {
+                let c = adjacency.corners(t);
+                let shortest = (0..3)
+                    .map(|k| undirected_edge(c[k], c[(k + 1) % 3]))
+                    .min_by(|&x, &y| length(x).total_cmp(&length(y)));
+                collapse.extend(shortest);
+            }
+        }
+
+        let mut parent: Vec<u32> = (0..welded_count as u32).collect();
+        let mut merged = 0;
+        for (a, b) in collapse {
+            let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+            if ra != rb {
+                parent[rb as usize] = ra;
+                merged += 1;
+            }
+        }
+        if merged == 0 {
+            break;
+        }
+        report.collapsed_edges += merged;
+
+        let roots: Vec<u32> = (0..welded_count as u32)
+            .map(|id| root(&mut parent, id))
+            .collect();
+        let mut sums = vec![([0.0f64; 3], 0usize); welded_count];
+        for id in 0..welded_count as u32 {
+            let p = position(id);
+            let (sum, count) = &mut sums[roots[id as usize] as usize];
+            for k in 0..3 {
+                sum[k] += p[k];
+            }
+            *count += 1;
+        }
+
+        let tri_count = mesh.indices.len() / 3;
+        let mut remove = vec![false; tri_count];
+        for (t, flag) in remove.iter_mut().enumerate() {
+            let c = adjacency.corners(t).map(|id| roots[id as usize]);
+            *flag = c[0] == c[1] || c[1] == c[2] || c[2] == c[0];
+            for (k, &r) in c.iter().enumerate() {
+                let (sum, count) = sums[r as usize];
+                let v = mesh.indices[t * 3 + k] as usize * 3;
+                for (dst, total) in mesh.vertices[v..v + 3].iter_mut().zip(sum) {
+                    *dst = (total / count as f64) as f32;
+                }
+            }
+        }
+        let faces_before = mesh.face_ranges.len();
+        report.removed_triangles += remove.iter().filter(|&&r| r).count();
+        remove_triangles(mesh, &remove);
+        report.removed_faces += faces_before - mesh.face_ranges.len();
+    }
+    report
+}
+
+/// A mesh whose positions and normals are indexed separately, like OBJ's
+/// `v` / `vn` pairs. See [`weld_by_face`].
+#[derive(Debug, Clone)]
+pub struct WeldedMesh {
+    /// Flat `[x, y, z, ...]` unique positions.
+    pub positions: Vec<f32>,
+    /// Flat `[nx, ny, nz, ...]` normals.
+    pub normals: Vec<f32>,
+    /// `(position index, normal index)` for each triangle corner, three per triangle.
+    pub corners: Vec<(u32, u32)>,
+}
+
+/// Weld coincident positions across the whole mesh while keeping one normal
+/// per position *per face range*.
+///
+/// Face identity is exact in the tessellation, so normals are averaged only
+/// within a face: a box comes out with 8 positions but 24 normals, and sharp
+/// edges keep crisp shading. Triangles outside any face range share a
+/// single group.
+pub fn weld_by_face(mesh: &RenderMesh) -> WeldedMesh {
+    let tri_count = mesh.indices.len() / 3;
+    let mut face_of = vec![usize::MAX; tri_count];
+    for (f, range) in mesh.face_ranges.iter().enumerate() {
+        let end = (range.end_index as usize / 3).min(tri_count);
+        let start = (range.start_index as usize / 3).min(end);
+        face_of[start..end].fill(f);
+    }
+
+    let mut out = WeldedMesh {
+        positions: Vec::new(),
+        normals: Vec::new(),
+        corners: Vec::with_capacity(mesh.indices.len()),
+    };
+    let mut position_ids: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut normal_ids: HashMap<(u32, usize), u32> = HashMap::new();
+    let mut normal_sums: Vec<[f64; 3]> = Vec::new();
+    for (corner, &vi) in mesh.indices[..tri_count * 3].iter().enumerate() {
+        let v = vi as usize * 3;
+        let p = &mesh.vertices[v..v + 3];
+        let key = [p[0].to_bits(), p[1].to_bits(), p[2].to_bits()];
+        let pid = *position_ids.entry(key).or_insert_with(|| {
+            out.positions.extend_from_slice(p);
+            (out.positions.len() / 3 - 1) as u32
+        });
+        let nid = *normal_ids
+            .entry((pid, face_of[corner / 3]))
+            .or_insert_with(|| {
+                normal_sums.push([0.0; 3]);
+                (normal_sums.len() - 1) as u32
+            });
+        if let Some(n) = mesh.normals.get(v..v + 3) {
+            for axis in 0..3 {
+                normal_sums[nid as usize][axis] += n[axis] as f64;
+            }
+        }
+        out.corners.push((pid, nid));
+    }
+    for sum in normal_sums {
+        let len = norm(sum);
+        let n = if len > 0.0 { sum.map(|c| c / len) } else { sum };
+        out.normals.extend(n.map(|c| c as f32));
+    }
+    out
+}
+
+/// Merge vertices closer than `tolerance` to each other and return how many
+/// were removed.
+///
+/// Positions are bucketed into cubes of side `tolerance`, and each vertex is
+/// compared with those in its own and the 26 surrounding cubes, so a pair
+/// straddling a cube face merges like any other. Merging is transitive: a
+/// chain of vertices each within `tolerance` of the next becomes one. Each
+/// group keeps the position and normal of its lowest-index vertex, indices
+/// are remapped and triangles are left in place, even if degenerate.
+/// Nothing merges unless `tolerance` is positive.
+pub fn weld_vertices(mesh: &mut RenderMesh, tolerance: f64) -> usize {
+    if tolerance.is_nan() || tolerance <= 0.0 {
+        return 0;
+    }
+    let count = mesh.vertices.len() / 3;
+    let position = |i: u32| {
+        let i = i as usize * 3;
+        [0, 1, 2].map(|k| mesh.vertices[i + k] as f64)
+    };
+
+    let mut cells: HashMap<[i64; 3], Vec<u32>> = HashMap::new();
+    let mut parent: Vec<u32> = (0..count as u32).collect();
+    for i in 0..count as u32 {
+        let p = position(i);
+        let cell = p.map(|c| (c / tolerance).floor() as i64);
+        for n in 0..27 {
+            let neighbour = [cell[0] + n % 3 - 1, cell[1] + n / 3 % 3 - 1, cell[2] + n / 9 - 1];
+            for &j in cells.get(&neighbour).into_iter().flatten() {
+                if norm(sub(p, position(j))) <= tolerance {
+                    // Hang the later root under the earlier one, so every
+                    // group's root is its lowest-index vertex.
+                    let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                    parent[ri.max(rj) as usize] = ri.min(rj);
+                }
+            }
+        }
+        cells.entry(cell).or_default().push(i);
+    }
+
+    let has_normals = mesh.normals.len() == mesh.vertices.len();
+    let mut new_index = vec![u32::MAX; count];
+    let (mut vertices, mut normals) = (Vec::new(), Vec::new());
+    for i in 0..count as u32 {
+        let r = root(&mut parent, i);
+        if r == i {
+            new_index[i as usize] = (vertices.len() / 3) as u32;
+            let at = i as usize * 3;
+            vertices.extend_from_slice(&mesh.vertices[at..at + 3]);
+            if has_normals {
+                normals.extend_from_slice(&mesh.normals[at..at + 3]);
+            }
+        } else {
+            new_index[i as usize] = new_index[r as usize];
+        }
+    }
+    for index in &mut mesh.indices {
+        *index = new_index[*index as usize];
+    }
+    mesh.vertices = vertices;
+    if has_normals {
+        mesh.normals = normals;
+    }
+    count - mesh.vertices.len() / 3
+}
+
+/// Recompute vertex normals, smooth across edges whose adjacent triangles
+/// meet at no more than `crease_angle_deg` and split along sharper ones.
+///
+/// Corners at the same position are grouped by walking across the smooth
+/// edges around it; each group becomes one vertex whose normal is the
+/// area-weighted average of its triangles' normals. A cube's corner thus
+/// turns into three vertices with crisp face normals, while a cylinder
+/// wall shades as one smooth surface. Border and non-manifold edges always
+/// split. Triangles keep their order, so face ranges carry over unchanged;
+/// vertices are welded by exact position first, like [`EdgeAdjacency`].
+pub fn recompute_normals(mesh: &mut RenderMesh, crease_angle_deg: f64) {
+    let adjacency = EdgeAdjacency::new(mesh);
+    let tri_count = mesh.indices.len() / 3;
+    let face_normals: Vec<[f64; 3]> = mesh_triangles(mesh).map(|t| triangle_normal(&t)).collect();
+    let min_cos = crease_angle_deg.to_radians().cos();
+
+    // Union corners (3 * triangle + k) that share a vertex across a smooth edge.
+    let mut parent: Vec<u32> = (0..tri_count as u32 * 3).collect();
+    let corner_at = |t: usize, v: u32| {
+        let k = adjacency.corners(t).iter().position(|&c| c == v).unwrap();
+        (t * 3 + k) as u32
+    };
+    for ((a, b), tris) in adjacency.edges() {
+        let &[t, u] = tris else {
+            continue;
+        };
+        let (nt, nu) = (face_normals[t], face_normals[u]);
+        let (lt, lu) = (norm(nt), norm(nu));
+        // A degenerate triangle has no direction of its own to crease on.
+        if lt > 0.0 && lu > 0.0 && dot(nt, nu) < min_cos * lt * lu {
+            continue;
+        }
+        for v in [a, b] {
+            let ri = root(&mut parent, corner_at(t, v));
+            let rj = root(&mut parent, corner_at(u, v));
+            parent[ri.max(rj) as usize] = ri.min(rj);
+        }
+    }
+
+    let mut new_index = vec![u32::MAX; tri_count * 3];
+    let (mut vertices, mut normals) = (Vec::new(), Vec::<[f64; 3]>::new());
+    for corner in 0..tri_count * 3 {
+        let r = root(&mut parent, corner as u32) as usize;
+        if new_index[r] == u32::MAX {
+            let at = adjacency.representative(adjacency.corners(r / 3)[r % 3]) as usize * 3;
+            vertices.extend_from_slice(&mesh.vertices[at..at + 3]);
+            new_index[r] = normals.len() as u32;
+            normals.push([0.0; 3]);
+        }
+        let v = new_index[r] as usize;
+        normals[v] = [0, 1, 2].map(|k| normals[v][k] + face_normals[corner / 3][k]);
+        new_index[corner] = new_index[r];
+    }
+
+    mesh.indices = new_index;
+    mesh.vertices = vertices;
+    mesh.normals = normals
+        .into_iter()
+        .flat_map(|n| {
+            let len = norm(n);
+            n.map(|c| if len > 0.0 { c / len } else { c } as f32)
+        })
+        .collect();
+}
+
+/// Un-weld a mesh so every triangle has its own three vertices carrying the
+/// triangle's face normal — the inverse of welding.
+///
+/// Viewers that interpolate vertex normals then shade each triangle flat,
+/// with crisp edges everywhere. Triangles keep their order, so face ranges
+/// carry over unchanged. Degenerate triangles get a zero normal.
+pub fn flat_shaded(mesh: &RenderMesh) -> RenderMesh {
+    let corner_count = mesh.indices.len() / 3 * 3;
+    let mut out = RenderMesh {
+        vertices: Vec::with_capacity(corner_count * 3),
+        normals: Vec::with_capacity(corner_count * 3),
+        indices: (0..corner_count as u32).collect(),
+        face_ranges: mesh.face_ranges.clone(),
+    };
+    for tri in mesh_triangles(mesh) {
+        let n = triangle_normal(&tri);
+        let len = norm(n);
+        let n = if len > 0.0 { n.map(|c| c / len) } else { n };
+        for p in tri {
+            out.vertices.extend(p.map(|c| c as f32));
+            out.normals.extend(n.map(|c| c as f32));
+        }
+    }
+    out
+}
+
+/// Split a mesh into chunks of at most `max_triangles` triangles each, in
+/// triangle order, so a viewer can upload a large model incrementally.
+///
+/// Each chunk is a self-contained mesh holding only the vertices its own
+/// triangles use; drawn together the chunks reproduce the original. A
+/// `max_triangles` of zero is treated as one.
+pub fn mesh_chunks(mesh: &RenderMesh, max_triangles: usize) -> Vec<RenderMesh> {
+    let tri_count = mesh.indices.len() / 3;
+    let step = max_triangles.max(1);
+    (0..tri_count)
+        .step_by(step)
+        .map(|first| mesh_chunk(mesh, first, first + step))
+        .collect()
+}
+
+/// Triangles `first..end` of a mesh as a standalone mesh, with vertices
+/// renumbered from zero and face ranges clipped to the chunk.
+pub fn mesh_chunk(mesh: &RenderMesh, first: usize, end: usize) -> RenderMesh {
+    let end = end.min(mesh.indices.len() / 3);
+    let first = first.min(end);
+    let mut out = RenderMesh {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        indices: Vec::with_capacity((end - first) * 3),
+        face_ranges: Vec::new(),
+    };
+    let mut remap: HashMap<u32, u32> = HashMap::new();
+    for &vi in &mesh.indices[first * 3..end * 3] {
+        let id = *remap.entry(vi).or_insert_with(|| {
+            let v = vi as usize * 3;
+            out.vertices.extend_from_slice(&mesh.vertices[v..v + 3]);
+            if let Some(n) = mesh.normals.get(v..v + 3) {
+                out.normals.extend_from_slice(n);
+            }
+            (out.vertices.len() / 3 - 1) as u32
+        });
+        out.indices.push(id);
+    }
+
+    let (lo, hi) = ((first * 3) as u32, (end * 3) as u32);
+    for range in &mesh.face_ranges {
+        let start = range.start_index.max(lo);
+        let stop = range.end_index.min(hi);
+        if start < stop {
+            out.face_ranges.push(FaceRange {
+                face_id: range.face_id,
+                start_index: start - lo,
+                end_index: stop - lo,
+            });
+        }
+    }
+    out
+}
+
+/// Reduce a mesh to at most `target_triangles` triangles by quadric
+/// error-metric edge collapses (Garland–Heckbert).
+///
+/// Vertices are first welded by exact position. Each collapse merges an
+/// edge into the point that least disturbs the planes of the triangles
+/// around it, cheapest first. A collapse is refused when it would flip a
+/// surviving triangle, pinch the surface into a non-manifold edge, or move
+/// a border or non-manifold vertex, so a closed manifold stays one.
+/// Decimation stops at the target or when no valid collapse remains.
+/// Surviving triangles keep their order and face ranges, and normals are
+/// recomputed as area-weighted averages. A mesh already at or below the
+/// target is returned unchanged.
+pub fn decimate_mesh(mesh: &RenderMesh, target_triangles: usize) -> RenderMesh {
+    let tri_count = mesh.indices.len() / 3;
+    if tri_count <= target_triangles {
+        return mesh.clone();
+    }
+    let adjacency = EdgeAdjacency::new(mesh);
+    let mut positions: Vec<[f64; 3]> = adjacency
+        .representatives
+        .iter()
+        .map(|&r| {
+            let r = r as usize * 3;
+            [0, 1, 2].map(|k| mesh.vertices[r + k] as f64)
+        })
+        .collect();
+    let vertex_count = positions.len();
+    let mut corners: Vec<[u32; 3]> = (0..tri_count).map(|t| adjacency.corners(t)).collect();
+    let mut alive = vec![true; tri_count];
+    let mut live = tri_count;
+
+    let mut quadrics = vec![Quadric::default(); vertex_count];
+    let mut incident: Vec<Vec<usize>> = vec![Vec::new(); vertex_count];
+    for (t, c) in corners.iter().enumerate() {
+        let n = triangle_normal(&c.map(|v| positions[v as usize]));
+        let area = norm(n) / 2.0;
+        if area > 0.0 {
+            let plane = Quadric::plane(n.map(|x| x / (2.0 * area)), positions[c[0] as usize], area);
+            for &v in c {
+                quadrics[v as usize].add(&plane);
+            }
+        }
+        for &v in c {
+            incident[v as usize].push(t);
+        }
+    }
+    let mut locked = vec![false; vertex_count];
+    for ((a, b), tris) in adjacency.edges() {
+        if tris.len() != 2 {
+            locked[a as usize] = true;
+            locked[b as usize] = true;
+        }
+    }
+
+    let mut version = vec![0u32; vertex_count];
+    let mut heap = BinaryHeap::new();
+    for ((a, b), _) in adjacency.edges() {
+        heap.extend(plan_collapse(
+            a, b, &positions, &quadrics, &locked, &version,
+        ));
+    }
+
+    while live > target_triangles {
+        let Some(collapse) = heap.pop() else {
+            break;
+        };
+        let (keep, gone) = (collapse.keep as usize, collapse.remove as usize);
+        if version[keep] != collapse.versions.0 || version[gone] != collapse.versions.1 {
+            continue;
+        }
+        incident[keep].retain(|&t| alive[t]);
+        incident[gone].retain(|&t| alive[t]);
+        if !collapse_is_valid(&collapse, &corners, &incident, &positions) {
+            continue;
+        }
+
+        for t in std::mem::take(&mut incident[gone]) {
+            if corners[t].contains(&collapse.keep) {
+                alive[t] = false;
+                live -= 1;
+            } else {
+                for v in &mut corners[t] {
+                    if *v == collapse.remove {
+                        *v = collapse.keep;
+                    }
+                }
+                incident[keep].push(t);
+            }
+        }
+        incident[keep].retain(|&t| alive[t]);
+        positions[keep] = collapse.position;
+        let merged = quadrics[gone];
+        quadrics[keep].add(&merged);
+        version[keep] += 1;
+        version[gone] += 1;
+        let keep = collapse.keep;
+        for n in ring(keep, &corners, &incident[keep as usize]) {
+            heap.extend(plan_collapse(
+                keep, n, &positions, &quadrics, &locked, &version,
+            ));
+        }
+    }
+
+    // Keep the welded vertices still in use, in their original order.
+    let mut new_index = vec![u32::MAX; vertex_count];
+    let mut normals = vec![[0.0f64; 3]; vertex_count];
+    for (c, _) in corners.iter().zip(&alive).filter(|(_, &a)| a) {
+        let n = triangle_normal(&c.map(|v| positions[v as usize]));
+        for &v in c {
+            normals[v as usize] = [0, 1, 2].map(|k| normals[v as usize][k] + n[k]);
+            new_index[v as usize] = 0;
+        }
+    }
+    let mut out = RenderMesh {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        indices: Vec::with_capacity(tri_count * 3),
+        face_ranges: mesh.face_ranges.clone(),
+    };
+    for v in 0..vertex_count {
+        if new_index[v] == u32::MAX {
+            continue;
+        }
+        new_index[v] = (out.vertices.len() / 3) as u32;
+        let len = norm(normals[v]);
+        let n = normals[v].map(|c| if len > 0.0 { c / len } else { c });
+        out.vertices.extend(positions[v].map(|c| c as f32));
+        out.normals.extend(n.map(|c| c as f32));
+    }
+    for c in &corners {
+        out.indices.extend(c.map(|v| new_index[v as usize]));
+    }
+    let dead: Vec<bool> = alive.iter().map(|a| !a).collect();
+    remove_triangles(&mut out, &dead);
+    out
+}
+
+/// Sum of squared distances to a set of weighted planes, as the upper
+/// triangle of its symmetric 4x4 matrix.
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// The plane through `p` with unit normal `n`.
+    fn plane(n: [f64; 3], p: [f64; 3], weight: f64) -> Self {
+        let [a, b, c] = n;
+        let d = -dot(n, p);
+        let q = [
+            a * a,
+            a * b,
+            a * c,
+            a * d,
+            b * b,
+            b * c,
+            b * d,
+            c * c,
+            c * d,
+            d * d,
+        ];
+        Self(q.map(|q| q * weight))
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for (q, o) in self.0.iter_mut().zip(other.0) {
+            *q += o;
+        }
+    }
+
+    fn error(&self, p: [f64; 3]) -> f64 {
+        let q = &self.0;
+        let [x, y, z] = p;
+        q[0] * x * x
+            + q[4] * y * y
+            + q[7] * z * z
+            + q[9]
+            + 2.0 * (q[1] * x * y + q[2] * x * z + q[5] * y * z + q[3] * x + q[6] * y + q[8] * z)
+    }
+
+    /// The point of least error, unless the planes leave it undetermined
+    /// (all parallel, or all through one line).
+    fn minimizer(&self) -> Option<[f64; 3]> {
+        let q = &self.0;
+        let rows = [[q[0], q[1], q[2]], [q[1], q[4], q[5]], [q[2], q[5], q[7]]];
+        let rhs = [-q[3], -q[6], -q[8]];
+        let det = dot(rows[0], cross(rows[1], rows[2]));
+        let trace = q[0] + q[4] + q[7];
+        if det.abs() <= 1e-9 * trace.powi(3) {
+            return None;
+        }
+        // The inverse's columns are the pairwise cross products of the rows.
+        let columns = [
+            cross(rows[1], rows[2]),
+            cross(rows[2], rows[0]),
+            cross(rows[0], rows[1]),
+        ];
+        let p = [0, 1, 2].map(|k| (0..3).map(|j| columns[j][k] * rhs[j]).sum::<f64>() / det);
+        p.iter().all(|c| c.is_finite()).then_some(p)
+    }
+}
+
+/// A planned edge collapse, ordered so the heap yields the cheapest first.
+#[derive(Debug, Clone, Copy)]
+struct Collapse {
+    cost: f64,
+    keep: u32,
+    remove: u32,
+    position: [f64; 3],
+    /// Vertex versions when planned; a collapse touching either vertex
+    /// since then is stale.
+    versions: (u32, u32),
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+    }
+}
+
+/// Where collapsing edge `a`-`b` should put the merged vertex, and at what
+/// cost. A locked endpoint stays put; `None` if both are locked.
+fn plan_collapse(
+    a: u32,
+    b: u32,
+    positions: &[[f64; 3]],
+    quadrics: &[Quadric],
+    locked: &[bool],
+    version: &[u32],
+) -> Option<Collapse> {
+    let (keep, remove) = match (locked[a as usize], locked[b as usize]) {
+        (true, true) => return None,
+        (false, true) => (b, a),
+        _ => (a, b),
+    };
+    let (pk, pr) = (positions[keep as usize], positions[remove as usize]);
+    let mut q = quadrics[keep as usize];
+    q.add(&quadrics[remove as usize]);
+
+    let mid = [0, 1, 2].map(|k| (pk[k] + pr[k]) / 2.0);
+    let mut options = vec![pk];
+    if !locked[keep as usize] {
+        options.extend([pr, mid]);
+        // The optimum can run off far from a nearly flat edge; only trust
+        // it near the edge.
+        if let Some(p) = q
+            .minimizer()
+            .filter(|&p| norm(sub(p, mid)) <= norm(sub(pk, pr)))
+        {
+            options.push(p);
+        }
+    }
+    let (cost, position) = options
+        .into_iter()
+        .map(|p| (q.error(p), p))
+        .min_by(|x, y| x.0.total_cmp(&y.0))?;
+    Some(Collapse {
+        cost,
+        keep,
+        remove,
+        position,
+        versions: (version[keep as usize], version[remove as usize]),
+    })
+}
+
+/// Vertices sharing a live triangle with `v`.
+fn ring(v: u32, corners: &[[u32; 3]], incident: &[usize]) -> Vec<u32> {
+    let mut out: Vec<u32> = incident
+        .iter()
+        .flat_map(|&t| corners[t])
+        .filter(|&u| u != v)
+        .collect();
+    out.sort_unstable();
+    out.dedup();
+    out
+}
+
+/// Whether `collapse` keeps the surface manifold and no triangle flips.
+/// `incident` must hold only live triangles for both endpoints.
+fn collapse_is_valid(
+    collapse: &Collapse,
+    corners: &[[u32; 3]],
+    incident: &[Vec<usize>],
+    positions: &[[f64; 3]],
+) -> bool {
+    let (keep, remove) = (collapse.keep, collapse.remove);
+    let shared: Vec<usize> = incident[remove as usize]
+        .iter()
+        .copied()
+        .filter(|&t| corners[t].contains(&keep))
+        .collect();
+    if shared.len() != 2 {
+        return false;
+    }
+    // Link condition: the only vertices joined to both ends are the two
+    // opposite the edge, otherwise the collapse glues sheets together.
+    let mut opposite: Vec<u32> = shared
+        .iter()
+        .flat_map(|&t| corners[t])
+        .filter(|&v| v != keep && v != remove)
+        .collect();
+    opposite.sort_unstable();
+    let keep_ring = ring(keep, corners, &incident[keep as usize]);
+    let common: Vec<u32> = ring(remove, corners, &incident[remove as usize])
+        .into_iter()
+        .filter(|v| keep_ring.binary_search(v).is_ok())
+        .collect();
+    if common != opposite {
+        return false;
+    }
+    // An opposite vertex of valence three would be left with two triangles
+    // folded onto each other.
+    if opposite
+        .iter()
+        .any(|&o| ring(o, corners, &incident[o as usize]).len() <= 3)
+    {
+        return false;
+    }
+
+    for &v in &[keep, remove] {
+        for &t in &incident[v as usize] {
+            if shared.contains(&t) {
+                continue;
+            }
+            let before = corners[t].map(|c| positions[c as usize]);
+            let after = corners[t].map(|c| {
+                if c == keep || c == remove {
+                    collapse.position
+                } else {
+                    positions[c as usize]
+                }
+            });
+            let (n0, n1) = (triangle_normal(&before), triangle_normal(&after));
+            if dot(n0, n1) <= 0.0 || norm(n1) <= 1e-12 * norm(n0) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn cross(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn dot(a: [f64; 3], b: [f64; 3]) -> f64 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn norm(a: [f64; 3]) -> f64 {
+    dot(a, a).sqrt()
+}
+
+/// Unnormalized winding normal (twice the triangle's area in length).
+fn triangle_normal(tri: &[[f64; 3]; 3]) -> [f64; 3] {
+    cross(sub(tri[1], tri[0]), sub(tri[2], tri[0]))
+}
+
+/// Möller–Trumbore test for a ray hitting a triangle in front of its origin.
+fn ray_hits_triangle(origin: [f64; 3], dir: [f64; 3], tri: &[[f64; 3]; 3]) -> bool {
+    let e1 = sub(tri[1], tri[0]);
+    let e2 = sub(tri[2], tri[0]);
+    let p = cross(dir, e2);
+    let det = dot(e1, p);
+    if det.abs() < 1e-12 {
+        return false;
+    }
+    let s = sub(origin, tri[0]);
+    let u = dot(s, p) / det;
+    if !(0.0..=1.0).contains(&u) {
+        return false;
+    }
+    let q = cross(s, e1);
+    let v = dot(dir, q) / det;
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+    dot(e2, q) / det > 0.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::FaceRange;
+
+    fn triangle_mesh() -> RenderMesh {
+        RenderMesh {
+            vertices: vec![0.0, -1.0, 2.0, 3.0, 0.0, 2.0, 1.0, 4.0, -5.0],
+            normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 1, 2],
+            face_ranges: Vec::new(),
+        }
+    }
+
+    fn box_mesh(offset: [f32; 3]) -> RenderMesh {
+        let mut vertices = Vec::new();
+        for i in 0..8 {
+            vertices.push(offset[0] + (i & 1) as f32);
+            vertices.push(offset[1] + ((i >> 1) & 1) as f32);
+            vertices.push(offset[2] + ((i >> 2) & 1) as f32);
+        }
+        RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices: vec![
+                0, 2, 1, 1, 2, 3, // -z
+                4, 5, 6, 5, 7, 6, // +z
+                0, 1, 4, 1, 5, 4, // -y
+                2, 6, 3, 3, 6, 7, // +y
+                0, 4, 2, 2, 4, 6, // -x
+                1, 3, 5, 3, 7, 5, // +x
+            ],
+            face_ranges: (0..6)
+                .map(|f| FaceRange {
+                    face_id: KernelId(f),
+                    start_index: f as u32 * 6,
+                    end_index: f as u32 * 6 + 6,
+                })
+                .collect(),
+        }
+    }
+
+    fn merge(a: RenderMesh, b: RenderMesh) -> RenderMesh {
+        let base = (a.vertices.len() / 3) as u32;
+        let mut out = a;
+        out.vertices.extend(b.vertices);
+        out.normals.extend(b.normals);
+        out.indices.extend(b.indices.iter().map(|i| i + base));
+        out
+    }
+
+    fn directed_edges_unique(mesh: &RenderMesh) -> bool {
+        let mut seen = std::collections::HashSet::new();
+        mesh.indices
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .all(|e| seen.insert(e))
+    }
+
+    #[test]
+    fn test_edge_adjacency_of_box() {
+        let adjacency = EdgeAdjacency::new(&box_mesh([0.0; 3]));
+        // 12 cube edges plus one diagonal per face.
+        assert_eq!(adjacency.edge_count(), 18);
+        let cube_edges = adjacency
+            .edges()
+            .filter(|((a, b), _)| {
+                let (pa, pb) = (*a as usize, *b as usize);
+                (pa ^ pb).count_ones() == 1
+            })
+            .count();
+        assert_eq!(cube_edges, 12);
+        for (_, tris) in adjacency.edges() {
+            assert_eq!(tris.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_undirected_edge_is_order_independent() {
+        assert_eq!(undirected_edge(3, 7), (3, 7));
+        assert_eq!(undirected_edge(7, 3), (3, 7));
+    }
+
+    #[test]
+    fn test_repair_winding_fixes_each_component() {
+        let mut mesh = merge(box_mesh([0.0; 3]), box_mesh([5.0, 0.0, 0.0]));
+        // Flip one triangle on the first box and the whole second box.
+        mesh.indices.swap(1, 2);
+        for t in 12..24 {
+            mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        assert!(!directed_edges_unique(&mesh));
+
+        assert_eq!(repair_winding(&mut mesh), 2);
+        assert!(directed_edges_unique(&mesh));
+        assert!(signed_volume_of(&mesh, &(0..12).collect::<Vec<_>>()) > 0.99);
+        assert!(signed_volume_of(&mesh, &(12..24).collect::<Vec<_>>()) > 0.99);
+    }
+
+    #[test]
+    fn test_repair_winding_is_repeatable() {
+        let mut mesh = box_mesh([0.0; 3]);
+        for t in [0, 3, 7, 10] {
+            mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        // Flipping the seed and three others is undone exactly, whichever
+        // way the flood fill reaches them.
+        repair_winding(&mut mesh);
+        assert_eq!(mesh.indices, box_mesh([0.0; 3]).indices);
+
+        // Repairing an already consistent mesh changes nothing.
+        let repaired = mesh.indices.clone();
+        repair_winding(&mut mesh);
+        assert_eq!(mesh.indices, repaired);
+    }
+
+    #[test]
+    fn test_repair_winding_keeps_seed_on_open_mesh() {
+        // Open-topped box wound inside-out: a volume check would flip it,
+        // but an open surface has no inside, so the seed's winding stands.
+        let mut mesh = box_mesh([0.0; 3]);
+        mesh.indices.drain(6..12);
+        mesh.face_ranges.clear();
+        for t in 0..mesh.indices.len() / 3 {
+            mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        let before = mesh.indices.clone();
+
+        repair_winding(&mut mesh);
+        assert_eq!(mesh.indices, before);
+    }
+
+    #[test]
+    fn test_signed_volume_of_large_box_far_from_origin() {
+        assert!((signed_volume(&box_mesh([0.0; 3])) - 1.0).abs() < 1e-6);
+
+        let mut big = box_mesh([0.0; 3]);
+        for (k, v) in big.vertices.iter_mut().enumerate() {
+            *v = *v * [100.0, 200.0, 300.0][k % 3] + 5000.0;
+        }
+        let volume = signed_volume(&big);
+        assert!((volume - 6.0e6).abs() < 0.01, "{}", volume);
+
+        // Triangle order doesn't change the result beyond rounding.
+        let mut reordered = big.clone();
+        let tris: Vec<&[u32]> = big.indices.chunks_exact(3).rev().collect();
+        reordered.indices = tris.concat();
+        assert!((signed_volume(&reordered) - volume).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_contains_point_inside_box_and_not_its_neighbour() {
+        let mesh = merge(box_mesh([0.0; 3]), box_mesh([3.0, 0.0, 0.0]));
+        assert!(contains_point(&mesh, [0.5, 0.5, 0.5]));
+        assert!(contains_point(&mesh, [3.2, 0.9, 0.1]));
+        assert!(!contains_point(&mesh, [2.0, 0.5, 0.5]));
+        assert!(!contains_point(&mesh, [-0.5, 0.5, 0.5]));
+        assert!(!contains_point(&box_mesh([0.0; 3]), [3.5, 0.5, 0.5]));
+    }
+
+    #[test]
+    fn test_surface_area_of_boxes() {
+        assert!((surface_area(&box_mesh([0.0; 3])) - 6.0).abs() < 1e-6);
+
+        let mut mesh = box_mesh([0.0; 3]);
+        for v in mesh.vertices.chunks_exact_mut(3) {
+            v[0] *= 2.0;
+            v[1] *= 3.0;
+            v[2] *= 4.0;
+        }
+        assert!((surface_area(&mesh) - 52.0).abs() < 1e-6);
+    }
+
+    fn polygon_area(points: &[[f64; 3]], normal: [f64; 3]) -> f64 {
+        let n = points.len();
+        (0..n)
+            .map(|i| dot(cross(points[i], points[(i + 1) % n]), normal))
+            .sum::<f64>()
+            / 2.0
+    }
+
+    #[test]
+    fn test_section_of_unit_cube_is_a_square() {
+        let loops = section(&box_mesh([0.0; 3]), [0.0, 0.0, 0.5], [0.0, 0.0, 1.0]);
+        assert_eq!(loops.len(), 1);
+        let square = &loops[0];
+        assert_eq!(square.len(), 4, "{:?}", square);
+        assert!(square.iter().all(|p| (p[2] - 0.5).abs() < 1e-12));
+        // Counter-clockwise seen from +z.
+        assert!((polygon_area(square, [0.0, 0.0, 1.0]) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_section_skips_plane_touching_a_vertex() {
+        // The plane touches the cube only at its origin corner.
+        let touching = section(&box_mesh([0.0; 3]), [0.0; 3], [1.0, 1.0, 1.0]);
+        assert!(touching.is_empty(), "{:?}", touching);
+        // Through a face: the face counts as above, nothing is cut.
+        assert!(section(&box_mesh([0.0; 3]), [0.0; 3], [0.0, 0.0, 1.0]).is_empty());
+    }
+
+    #[test]
+    fn test_section_of_two_boxes_and_open_mesh() {
+        let two = merge(box_mesh([0.0; 3]), box_mesh([5.0, 0.0, 0.0]));
+        let loops = section(&two, [0.0, 0.25, 0.0], [0.0, -1.0, 0.0]);
+        assert_eq!(loops.len(), 2);
+        for l in &loops {
+            assert!((polygon_area(l, [0.0, -1.0, 0.0]) - 1.0).abs() < 1e-9);
+        }
+
+        // Without its +x face the cut is an open chain along the other three.
+        let mut open = box_mesh([0.0; 3]);
+        open.indices.truncate(30);
+        let chains = section(&open, [0.0, 0.0, 0.5], [0.0, 0.0, 1.0]);
+        assert_eq!(chains.len(), 1);
+        assert_eq!(chains[0].len(), 4);
+        assert!(chains[0].iter().all(|p| p[2] == 0.5));
+        assert_eq!(chains[0][0][0], 1.0);
+        assert_eq!(chains[0][3][0], 1.0);
+    }
+
+    #[test]
+    fn test_validate_mesh_closed_box() {
+        assert!(validate_mesh(&box_mesh([0.0; 3])).is_closed_manifold());
+        assert_eq!(validate_mesh(&box_mesh([0.0; 3])).boundary_loop_count, 0);
+        assert!(boundary_loops(&box_mesh([0.0; 3])).is_empty());
+    }
+
+    #[test]
+    fn test_boundary_loops_of_triangle_and_open_boxes() {
+        let loops = boundary_loops(&triangle_mesh());
+        assert_eq!(loops, vec![vec![0, 1, 2]]);
+        assert_eq!(validate_mesh(&triangle_mesh()).boundary_loop_count, 1);
+
+        // Without its +x face the box has one square hole.
+        let mut open = box_mesh([0.0; 3]);
+        open.indices.truncate(30);
+        let loops = boundary_loops(&open);
+        assert_eq!(loops.len(), 1);
+        let mut corners = loops[0].clone();
+        corners.sort_unstable();
+        assert_eq!(corners, vec![1, 3, 5, 7]);
+        let report = validate_mesh(&open);
+        assert_eq!(report.boundary_edges, 4);
+        assert_eq!(report.boundary_loop_count, 1);
+
+        // Without the opposite -x face as well: two holes.
+        open.indices.truncate(24);
+        let report = validate_mesh(&open);
+        assert_eq!(report.boundary_edges, 8);
+        assert_eq!(report.boundary_loop_count, 2);
+    }
+
+    #[test]
+    fn test_validate_mesh_separates_flipped_from_boundary() {
+        // Both triangles walk the shared edge 1 -> 2.
+        let mesh = RenderMesh {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0],
+            normals: vec![0.0; 12],
+            indices: vec![0, 1, 2, 1, 2, 3],
+            face_ranges: Vec::new(),
+        };
+        let report = validate_mesh(&mesh);
+        assert_eq!(report.flipped_edges, 1);
+        assert_eq!(report.boundary_edges, 4);
+        assert_eq!(report.non_manifold_edges, 0);
+    }
+
+    #[test]
+    fn test_estimated_volume_caps_open_box() {
+        let mut mesh = box_mesh([0.0; 3]);
+        // Drop the +z face.
+        mesh.indices.drain(6..12);
+        let before = mesh.clone();
+
+        let volume = estimated_volume(&mesh);
+        assert!((volume - 1.0).abs() < 1e-6, "volume = {}", volume);
+        assert_eq!(mesh.indices, before.indices);
+        assert_eq!(mesh.vertices, before.vertices);
+    }
+
+    #[test]
+    fn test_fill_holes_caps_missing_box_face() {
+        let mut mesh = box_mesh([0.0; 3]);
+        mesh.indices.drain(6..12);
+        // The square hole has four edges.
+        assert_eq!(fill_holes(&mut mesh, 3), 0);
+
+        assert_eq!(fill_holes(&mut mesh, 4), 1);
+        assert_eq!(mesh.indices.len(), 36);
+        assert_eq!(mesh.vertices.len(), 24, "ear clipping adds no vertices");
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!((signed_volume(&mesh) - 1.0).abs() < 1e-6);
+    }
+
+    /// Walls of a prism over an L of three unit squares, with both ends
+    /// open, facing out or (`inward`) in.
+    fn l_prism_walls(inward: bool) -> RenderMesh {
+        let l = [
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ];
+        let n = l.len() as u32;
+        let mut vertices = Vec::new();
+        for z in [0.0f32, 1.0] {
+            for [x, y] in l {
+                vertices.extend([x, y, z]);
+            }
+        }
+        let mut indices = Vec::new();
+        for i in 0..n {
+            let j = (i + 1) % n;
+            if inward {
+                indices.extend([i, n + j, j, i, n + i, n + j]);
+            } else {
+                indices.extend([i, j, n + j, i, n + j, n + i]);
+            }
+        }
+        RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+            face_ranges: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fill_holes_caps_l_shaped_prism() {
+        let mut mesh = l_prism_walls(false);
+
+        // A fan from the centroid would spill outside the reflex corner.
+        assert_eq!(fill_holes(&mut mesh, 6), 2);
+        assert_eq!(mesh.indices.len() / 3, 2 * 6 + 2 * 4);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!((signed_volume(&mesh) - 3.0).abs() < 1e-6);
+        assert!((surface_area(&mesh) - (3.0 * 2.0 + 8.0)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_fill_holes_winds_caps_with_their_walls() {
+        // Outward walls leave the top loop counter-clockwise seen from +z and
+        // the bottom one clockwise; inward walls swap them. Each cap must
+        // face the way its walls do and cover the L exactly.
+        let inside_l = |[x, y]: [f64; 2]| {
+            (0.0..=2.0).contains(&x) && (0.0..=2.0).contains(&y) && (x <= 1.0 || y <= 1.0)
+        };
+        for inward in [false, true] {
+            let mut mesh = l_prism_walls(inward);
+            let walls = mesh.indices.len();
+            assert_eq!(fill_holes(&mut mesh, 6), 2);
+            assert!(validate_mesh(&mesh).is_closed_manifold());
+            let volume = if inward { -3.0 } else { 3.0 };
+            assert!((signed_volume(&mesh) - volume).abs() < 1e-6);
+
+            for z in [0.0, 1.0] {
+                let up = (z == 1.0) != inward;
+                let (mut area, mut moment) = (0.0, [0.0; 2]);
+                let corner = |i: u32| [0, 1, 2].map(|k| mesh.vertices[i as usize * 3 + k] as f64);
+                for t in mesh.indices[walls..].chunks(3) {
+                    let [a, b, c] = [corner(t[0]), corner(t[1]), corner(t[2])];
+                    if a[2] != z {
+                        continue;
+                    }
+                    let normal = cross(sub(b, a), sub(c, a));
+                    assert_eq!(normal[2] > 0.0, up, "cap at z = {} faces wrong way", z);
+                    let centroid = [0, 1].map(|k| (a[k] + b[k] + c[k]) / 3.0);
+                    assert!(inside_l(centroid), "{:?} lies outside the L", centroid);
+                    let half = normal[2].abs() / 2.0;
+                    area += half;
+                    moment = [0, 1].map(|k| moment[k] + half * centroid[k]);
+                }
+                assert!((area - 3.0).abs() < 1e-9, "cap area {}", area);
+                let centroid = moment.map(|m| m / area);
+                for c in centroid {
+                    assert!((c - 5.0 / 6.0).abs() < 1e-9, "cap centroid {:?}", centroid);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_ear_clip_splits_comb_without_overlap() {
+        // Teeth reaching down to the base leave no proper ear at some point;
+        // lifting them by 1e-9 makes the notches near-degenerate instead.
+        for lift in [0.0, 1e-9] {
+            let comb = [
+                [0.0, 0.0],
+                [6.0, 0.0],
+                [6.0, 1.0],
+                [5.0, lift],
+                [4.0, 1.0],
+                [3.0, lift],
+                [2.0, 1.0],
+                [1.0, lift],
+                [0.0, 1.0],
+            ];
+            let twice_area = |[a, b, c]: [[f64; 2]; 3]| {
+                (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+            };
+            let triangles = ear_clip(&comb);
+            assert_eq!(triangles.len(), comb.len() - 2);
+
+            let mut total = 0.0;
+            for [a, b, c] in triangles {
+                let corners = [comb[a], comb[b], comb[c]];
+                let area = twice_area(corners) / 2.0;
+                assert!(area >= 0.0, "{:?} winds clockwise", corners);
+                total += area;
+                if area > 1e-6 {
+                    let centroid = [0, 1].map(|k| corners.iter().map(|p| p[k]).sum::<f64>() / 3.0);
+                    let inside = point_in_polygon_2d(centroid, &comb);
+                    assert!(inside, "{:?} is outside", corners);
+                }
+            }
+            let expected = (1..comb.len() - 1)
+                .map(|i| twice_area([comb[0], comb[i], comb[i + 1]]) / 2.0)
+                .sum::<f64>();
+            assert!((total - expected).abs() < 1e-9, "area {}", total);
+        }
+    }
+
+    #[test]
+    fn test_repair_mesh_closes_open_box() {
+        let mut mesh = box_mesh([0.0; 3]);
+        // Drop the +z face and flip a -y triangle.
+        mesh.indices.drain(6..12);
+        mesh.indices.swap(7, 8);
+        assert!(!validate_mesh(&mesh).is_closed_manifold());
+
+        let report = repair_mesh(&mut mesh);
+        assert_eq!(report.filled_holes, 1);
+        assert_eq!(report.rewound_triangles, 1);
+        assert_eq!(report.merged_duplicates, 0);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!((signed_volume(&mesh) - 1.0).abs() < 1e-6);
+
+        assert!(repair_mesh(&mut mesh).is_clean());
+    }
+
+    #[test]
+    fn test_repair_mesh_turns_capped_shell_outward() {
+        // Open-topped box whose seed triangle is inverted: the open shell
+        // keeps the seed's inside-out winding until the cap closes it.
+        let mut mesh = box_mesh([0.0; 3]);
+        mesh.indices.drain(6..12);
+        mesh.face_ranges.clear();
+        mesh.indices.swap(1, 2);
+
+        let report = repair_mesh(&mut mesh);
+        assert_eq!(report.filled_holes, 1);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!((signed_volume(&mesh) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_sheet_validation_allows_borders_only() {
+        let sheet = validate_mesh(&triangle_mesh());
+        assert_eq!(sheet.boundary_edges, 3);
+        assert!(sheet.is_valid(Solidity::Sheet));
+        assert!(!sheet.is_valid(Solidity::Solid));
+
+        let closed = validate_mesh(&box_mesh([0.0; 3]));
+        assert!(closed.is_valid(Solidity::Solid));
+
+        let mut open_box = box_mesh([0.0; 3]);
+        open_box.indices.drain(6..12);
+        let open = validate_mesh(&open_box);
+        assert!(!open.is_valid(Solidity::Solid));
+        assert!(open.is_valid(Solidity::Sheet));
+
+        // A flipped triangle is a defect even in a sheet.
+        open_box.indices.swap(7, 8);
+        assert!(!validate_mesh(&open_box).is_valid(Solidity::Sheet));
+    }
+
+    #[test]
+    fn test_misoriented_faces_finds_flipped_face() {
+        let mut mesh = box_mesh([0.0; 3]);
+        assert!(misoriented_faces(&mesh).is_empty());
+
+        // Flip both triangles of the +x face.
+        for t in 10..12 {
+            mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        assert_eq!(misoriented_faces(&mesh), vec![KernelId(5)]);
+    }
+
+    /// An L-shaped plate: legs 10 long and 3 wide, 1 thick in z.
+    fn l_bracket() -> RenderMesh {
+        let outline = [
+            [0.0, 0.0],
+            [10.0, 0.0],
+            [10.0, 3.0],
+            [3.0, 3.0],
+            [3.0, 10.0],
+            [0.0, 10.0],
+        ];
+        let mut vertices = Vec::new();
+        for z in [0.0, 1.0] {
+            for [x, y] in outline {
+                vertices.extend([x, y, z]);
+            }
+        }
+        let mut indices = Vec::new();
+        for k in 1..5u32 {
+            indices.extend([0, k + 1, k]); // bottom, facing -z
+            indices.extend([6, 6 + k, 6 + k + 1]); // top, facing +z
+        }
+        for i in 0..6u32 {
+            let j = (i + 1) % 6;
+            indices.extend([i, j, 6 + j, i, 6 + j, 6 + i]);
+        }
+        RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+            face_ranges: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_overhang_area_of_standing_bracket() {
+        // Stood on the end of the horizontal leg, the other leg's inner
+        // face (7 × 1) hangs over the plate.
+        let area = overhang_area(&l_bracket(), [-1.0, 0.0, 0.0], SUPPORT_ANGLE_DEGREES);
+        assert!((area - 7.0).abs() < 1e-6, "area = {}", area);
+    }
+
+    #[test]
+    fn test_best_print_orientation_lays_bracket_flat() {
+        let (up, overhang) = best_print_orientation(&l_bracket(), 4);
+        assert!((up[2].abs() - 1.0).abs() < 1e-6, "up = {:?}", up);
+        assert!(overhang.abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_misoriented_faces_counts_crossings_of_the_same_face() {
+        // A U-channel prism whose channel (inner walls and floor) is one
+        // face. A ray leaving the left inner wall crosses the right inner
+        // wall of that same face before it leaves the solid.
+        let u = [
+            [0.0, 0.0],
+            [3.0, 0.0],
+            [3.0, 2.0],
+            [2.0, 2.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ];
+        let n = u.len() as u32;
+        let mut vertices = Vec::new();
+        for z in [0.0f32, 1.0] {
+            for [x, y] in u {
+                vertices.extend([x, y, z]);
+            }
+        }
+        let mut indices = Vec::new();
+        for i in 0..n {
+            let j = (i + 1) % n;
+            indices.extend([i, j, n + j, i, n + j, n + i]);
+        }
+        let mut mesh = RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+            face_ranges: Vec::new(),
+        };
+        assert_eq!(fill_holes(&mut mesh, 8), 2);
+
+        // Walls 3..=5 form the channel; ties in area pick its last triangle,
+        // on the left inner wall.
+        let spans = [(0, 1), (1, 2), (2, 3), (3, 6), (6, 7), (7, 8)];
+        for (id, (first, end)) in spans.into_iter().enumerate() {
+            mesh.face_ranges.push(FaceRange {
+                face_id: KernelId(id as u64),
+                start_index: first * 6,
+                end_index: end * 6,
+            });
+        }
+        mesh.face_ranges.push(FaceRange {
+            face_id: KernelId(6),
+            start_index: 48,
+            end_index: mesh.indices.len() as u32,
+        });
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!(misoriented_faces(&mesh).is_empty());
+
+        for t in 6..12 {
+            mesh.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        assert_eq!(misoriented_faces(&mesh), vec![KernelId(3)]);
+    }
+
+    #[test]
+    fn test_meshes_equivalent_ignores_ordering() {
+        let original = box_mesh([0.0; 3]);
+        // Unweld every triangle, start each at its second corner and
+        // reverse the triangle order.
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for tri in original.indices.chunks_exact(3).rev() {
+            for &i in [tri[1], tri[2], tri[0]].iter() {
+                let i = i as usize * 3;
+                vertices.extend_from_slice(&original.vertices[i..i + 3]);
+                indices.push(indices.len() as u32);
+            }
+        }
+        let rewelded = RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+            face_ranges: Vec::new(),
+        };
+        assert!(meshes_equivalent(&original, &rewelded, 1e-5));
+
+        // A coarse sphere (an octahedron) is a different shape.
+        let sphere = RenderMesh {
+            vertices: vec![
+                1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, -1.0, 0.0, 0.0, 0.0, 1.0, 0.0,
+                0.0, -1.0,
+            ],
+            normals: vec![0.0; 18],
+            indices: vec![
+                0, 2, 4, 2, 1, 4, 1, 3, 4, 3, 0, 4, 2, 0, 5, 1, 2, 5, 3, 1, 5, 0, 3, 5,
+            ],
+            face_ranges: Vec::new(),
+        };
+        assert!(!meshes_equivalent(&original, &sphere, 1e-5));
+    }
+
+    #[test]
+    fn test_quantize_makes_noisy_meshes_identical() {
+        let bits = |m: &RenderMesh| -> Vec<u32> {
+            m.vertices
+                .iter()
+                .chain(&m.normals)
+                .map(|v| v.to_bits())
+                .collect()
+        };
+        let mut a = box_mesh([0.1, 0.2, 0.3]);
+        let mut b = a.clone();
+        // Last-bit noise such as another platform's FMA would produce.
+        for (i, v) in b.vertices.iter_mut().enumerate() {
+            *v += if i % 2 == 0 { 3e-7 } else { -2e-7 };
+        }
+        b.normals[0] = -0.0;
+        assert_ne!(bits(&a), bits(&b));
+
+        quantize(&mut a, 4);
+        quantize(&mut b, 4);
+        assert_eq!(bits(&a), bits(&b));
+    }
+
+    #[test]
+    fn test_quantize_finer_keeps_more_precision() {
+        let mut fine = box_mesh([0.00012, 0.0, 0.0]);
+        let mut coarse = fine.clone();
+        quantize(&mut fine, 5);
+        quantize(&mut coarse, 3);
+        assert!((fine.vertices[0] - 0.00012).abs() < 1e-6);
+        assert_eq!(coarse.vertices[0], 0.0);
+    }
+
+    #[test]
+    fn test_merge_duplicate_faces_removes_shared_wall() {
+        // Two unit boxes touching at x = 1, concatenated without a boolean.
+        let mut mesh = merge(box_mesh([0.0; 3]), box_mesh([1.0, 0.0, 0.0]));
+        assert_eq!(validate_mesh(&mesh).non_manifold_edges, 5);
+
+        assert_eq!(merge_duplicate_faces(&mut mesh), 2);
+        assert_eq!(mesh.indices.len(), 20 * 3);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        assert!((signed_volume(&mesh) - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_weld_by_face_keeps_split_normals() {
+        // Unweld the box so every triangle carries its own flat normal, as
+        // a kernel tessellation does.
+        let source = box_mesh([0.0; 3]);
+        let mut mesh = RenderMesh {
+            vertices: Vec::new(),
+            normals: Vec::new(),
+            indices: Vec::new(),
+            face_ranges: source.face_ranges.clone(),
+        };
+        for tri in source.indices.chunks_exact(3) {
+            let corners = [tri[0], tri[1], tri[2]].map(|i| {
+                let i = i as usize * 3;
+                [0, 1, 2].map(|k| source.vertices[i + k] as f64)
+            });
+            let n = triangle_normal(&corners);
+            let len = norm(n);
+            for p in corners {
+                mesh.indices.push((mesh.vertices.len() / 3) as u32);
+                mesh.vertices.extend(p.map(|c| c as f32));
+                mesh.normals.extend(n.map(|c| (c / len) as f32));
+            }
+        }
+
+        let welded = weld_by_face(&mesh);
+        assert_eq!(welded.positions.len() / 3, 8);
+        assert_eq!(welded.normals.len() / 3, 24);
+        assert_eq!(welded.corners.len(), 36);
+        for n in welded.normals.chunks_exact(3) {
+            let axis_aligned = n.iter().filter(|c| c.abs() > 0.999).count();
+            assert_eq!(axis_aligned, 1, "normal {:?} is not a face normal", n);
+        }
+    }
+
+    #[test]
+    fn test_weld_vertices_across_cell_boundaries() {
+        // A power of two, so cell boundaries fall on exact f32 values.
+        let tol = 1.0 / 128.0;
+        // Straddling a rounding boundary (half a cell) and a floor boundary.
+        for (below, above) in [(0.4999999, 0.5000001), (0.999999, 1.000001)] {
+            let points = [
+                [below * tol, 0.0, 0.0],
+                [above * tol, 0.0, 0.0],
+                [0.0, 1.0, 0.0],
+                [1.0, 0.0, 0.0],
+            ];
+            let mut mesh = RenderMesh {
+                vertices: points.iter().flatten().map(|&c| c as f32).collect(),
+                normals: Vec::new(),
+                indices: vec![0, 3, 2, 1, 2, 3],
+                face_ranges: Vec::new(),
+            };
+            assert_ne!(mesh.vertices[0], mesh.vertices[3]);
+            assert_eq!(weld_vertices(&mut mesh, tol), 1, "at {} tol", below);
+            assert_eq!(mesh.vertices.len(), 9);
+            assert_eq!(mesh.indices, vec![0, 2, 1, 0, 1, 2]);
+        }
+
+        // Just over the tolerance apart stays separate.
+        let mut apart = triangle_mesh();
+        apart.vertices[3..6].copy_from_slice(&[(1.01 * tol) as f32, -1.0, 2.0]);
+        apart.vertices[0..3].copy_from_slice(&[0.0, -1.0, 2.0]);
+        assert_eq!(weld_vertices(&mut apart, tol), 0);
+        assert_eq!(apart.indices, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn test_weld_vertices_rewelds_flat_shaded_box() {
+        let mut flat = flat_shaded(&box_mesh([0.25, -3.0, 7.5]));
+        assert_eq!(weld_vertices(&mut flat, 1e-4), 28);
+        assert_eq!(flat.vertices.len() / 3, 8);
+        assert_eq!(flat.normals.len(), flat.vertices.len());
+        assert_eq!(flat.face_ranges.len(), 6);
+        assert!(validate_mesh(&flat).is_closed_manifold());
+        assert!((signed_volume(&flat) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_weld_vertices_tolerance_for_micro_parts() {
+        let micro = || {
+            let mut mesh = flat_shaded(&box_mesh([0.0; 3]));
+            mesh.vertices.iter_mut().for_each(|c| *c *= 5e-6);
+            mesh
+        };
+        // A tolerance sized for millimetre parts swallows the whole box.
+        let mut coarse = micro();
+        weld_vertices(&mut coarse, 1e-5);
+        assert_eq!(coarse.vertices.len() / 3, 1);
+
+        let mut fine = micro();
+        weld_vertices(&mut fine, 1e-8);
+        assert_eq!(fine.vertices.len() / 3, 8);
+        assert!(validate_mesh(&fine).is_closed_manifold());
+    }
+
+    #[test]
+    fn test_recompute_normals_keeps_box_edges_crisp() {
+        let mut mesh = box_mesh([0.0; 3]);
+        recompute_normals(&mut mesh, 30.0);
+
+        // Each corner splits into one vertex per face that meets there.
+        assert_eq!(mesh.vertices.len(), 24 * 3);
+        assert_eq!(mesh.normals.len(), mesh.vertices.len());
+        assert_eq!(mesh.face_ranges.len(), 6);
+        for (face, range) in mesh.face_ranges.iter().enumerate() {
+            // Faces run -z, +z, -y, +y, -x, +x.
+            let axis = 2 - face / 2;
+            let sign = if face % 2 == 0 { -1.0 } else { 1.0 };
+            for &v in &mesh.indices[range.start_index as usize..range.end_index as usize] {
+                let n = &mesh.normals[v as usize * 3..v as usize * 3 + 3];
+                assert_eq!(n[axis], sign, "face {} vertex {}", face, v);
+            }
+        }
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+    }
+
+    #[test]
+    fn test_recompute_normals_smooths_cylinder_wall() {
+        // Welded cylinder: two rings plus a centre vertex per cap.
+        let segments = 32u32;
+        let mut vertices = Vec::new();
+        for z in [0.0f32, 2.0] {
+            for i in 0..segments {
+                let angle = i as f32 / segments as f32 * std::f32::consts::TAU;
+                vertices.extend([angle.cos(), angle.sin(), z]);
+            }
+        }
+        vertices.extend([0.0, 0.0, 0.0, 0.0, 0.0, 2.0]);
+        let (bottom, top) = (2 * segments, 2 * segments + 1);
+        let mut indices = Vec::new();
+        for i in 0..segments {
+            let j = (i + 1) % segments;
+            indices.extend([i, j, segments + j, i, segments + j, segments + i]);
+            indices.extend([bottom, j, i, top, segments + i, segments + j]);
+        }
+        let mut mesh = RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices,
+            face_ranges: Vec::new(),
+        };
+
+        recompute_normals(&mut mesh, 30.0);
+        // Rim vertices split between wall and cap; cap centres stay whole.
+        assert_eq!(mesh.vertices.len() / 3, 4 * segments as usize + 2);
+        let mut wall = 0;
+        for (p, n) in mesh.vertices.chunks_exact(3).zip(mesh.normals.chunks_exact(3)) {
+            if n[2].abs() > 0.99 {
+                continue;
+            }
+            wall += 1;
+            assert!(n[2].abs() < 1e-6, "wall normal {:?}", n);
+            assert!(n[0] * p[0] + n[1] * p[1] > 0.999, "{:?} at {:?}", n, p);
+        }
+        assert_eq!(wall, 2 * segments as usize);
+    }
+
+    #[test]
+    fn test_flat_shaded_box_unwelds_with_face_normals() {
+        let welded = box_mesh([0.0; 3]);
+        assert_eq!(welded.vertices.len() / 3, 8);
+
+        let flat = flat_shaded(&welded);
+        assert_eq!(flat.vertices.len() / 3, 36);
+        assert_eq!(flat.normals.len(), flat.vertices.len());
+        assert_eq!(flat.indices, (0..36).collect::<Vec<u32>>());
+        assert_eq!(flat.face_ranges.len(), 6);
+        assert!((signed_volume(&flat) - 1.0).abs() < 1e-6);
+        // Box faces run -z, +z, -y, +y, -x, +x, two triangles each.
+        let expected = [
+            [0.0, 0.0, -1.0],
+            [0.0, 0.0, 1.0],
+            [0.0, -1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [-1.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+        ];
+        for (corner, n) in flat.normals.chunks_exact(3).enumerate() {
+            assert_eq!(n, expected[corner / 6], "corner {}", corner);
+        }
+    }
+
+    #[test]
+    fn test_mesh_chunks_split_sphere_and_reassemble() {
+        let sphere = crate::mesh_primitives::icosphere([0.0; 3], 1.0, 5).unwrap();
+        let total = sphere.indices.len() / 3;
+        assert_eq!(total, 20 * 4usize.pow(5));
+
+        let chunks = mesh_chunks(&sphere, 5000);
+        assert_eq!(chunks.len(), total.div_ceil(5000));
+        let mut triangles = Vec::new();
+        for chunk in &chunks {
+            let count = chunk.indices.len() / 3;
+            assert!(count > 0 && count <= 5000);
+            // Self-contained: every vertex is used, and none is out of range.
+            let used: std::collections::HashSet<u32> = chunk.indices.iter().copied().collect();
+            assert_eq!(used.len(), chunk.vertices.len() / 3);
+            assert_eq!(chunk.normals.len(), chunk.vertices.len());
+            triangles.extend(mesh_triangles(chunk));
+        }
+        assert_eq!(triangles.len(), total);
+        assert!(triangles.into_iter().eq(mesh_triangles(&sphere)));
+    }
+
+    #[test]
+    fn test_decimate_sphere_stays_watertight() {
+        let sphere = crate::mesh_primitives::icosphere([0.0; 3], 1.0, 4).unwrap();
+        assert_eq!(sphere.indices.len() / 3, 5120);
+
+        let decimated = decimate_mesh(&sphere, 500);
+        let triangles = decimated.indices.len() / 3;
+        assert!(
+            triangles <= 500 && triangles > 400,
+            "{} triangles",
+            triangles
+        );
+        assert!(validate_mesh(&decimated).is_closed_manifold());
+        assert!(directed_edges_unique(&decimated));
+        assert_eq!(decimated.normals.len(), decimated.vertices.len());
+
+        // Still a sphere: every vertex near the surface, volume close to it.
+        for v in decimated.vertices.chunks_exact(3) {
+            let r = norm([v[0] as f64, v[1] as f64, v[2] as f64]);
+            assert!((r - 1.0).abs() < 0.05, "vertex at radius {}", r);
+        }
+        let sphere_volume = 4.0 / 3.0 * std::f64::consts::PI;
+        let volume = signed_volume(&decimated);
+        assert!(
+            (volume / sphere_volume - 1.0).abs() < 0.05,
+            "volume {}",
+            volume
+        );
+    }
+
+    #[test]
+    fn test_decimate_keeps_mesh_already_under_target() {
+        let mesh = box_mesh([0.0; 3]);
+        let decimated = decimate_mesh(&mesh, 12);
+        assert_eq!(decimated.vertices, mesh.vertices);
+        assert_eq!(decimated.indices, mesh.indices);
+    }
+
+    #[test]
+    fn test_decimate_open_box_keeps_its_border() {
+        // Drop the +x face: its four corners form the border.
+        let mut mesh = box_mesh([0.0; 3]);
+        mesh.indices.truncate(30);
+        mesh.face_ranges.pop();
+
+        let decimated = decimate_mesh(&mesh, 0);
+        let validation = validate_mesh(&decimated);
+        assert_eq!(validation.boundary_edges, 4);
+        assert!(validation.is_valid(Solidity::Sheet));
+        let border = decimated
+            .vertices
+            .chunks_exact(3)
+            .filter(|v| v[0] == 1.0)
+            .count();
+        assert_eq!(border, 4);
+    }
+
+    #[test]
+    fn test_mesh_chunks_clip_face_ranges() {
+        let mesh = box_mesh([0.0; 3]);
+        let chunks = mesh_chunks(&mesh, 5);
+        assert_eq!(chunks.len(), 3);
+        // Triangles 5..10 cover the second half of face 2 and all of faces 3
+        // and 4.
+        let ranges: Vec<(u64, u32, u32)> = chunks[1]
+            .face_ranges
+            .iter()
+            .map(|r| (r.face_id.0, r.start_index, r.end_index))
+            .collect();
+        assert_eq!(ranges, vec![(2, 0, 3), (3, 3, 9), (4, 9, 15)]);
+        assert_eq!(mesh_chunks(&mesh, 0).len(), 12);
+    }
+
+    #[test]
+    fn test_defeature_removes_sliver_face() {
+        // A unit box whose top/back edge is bevelled by a 1e-4 wide strip.
+        let e = 1e-4f32;
+        let vertices: Vec<f32> = [
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 1.0],
+            [1.0, 1.0 - e, 1.0],
+            [0.0, 1.0 - e, 1.0],
+            [1.0, 1.0, 1.0 - e],
+            [0.0, 1.0, 1.0 - e],
+        ]
+        .concat();
+        let faces: [&[u32]; 7] = [
+            &[0, 3, 2, 1],    // bottom
+            &[4, 5, 6, 7],    // top
+            &[0, 1, 5, 4],    // front
+            &[3, 9, 8, 2],    // back
+            &[0, 4, 7, 9, 3], // left
+            &[1, 2, 8, 6, 5], // right
+            &[7, 6, 8, 9],    // sliver
+        ];
+        let mut mesh = RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            vertices,
+            indices: Vec::new(),
+            face_ranges: Vec::new(),
+        };
+        for (f, polygon) in faces.iter().enumerate() {
+            let start_index = mesh.indices.len() as u32;
+            for k in 1..polygon.len() - 1 {
+                mesh.indices
+                    .extend([polygon[0], polygon[k], polygon[k + 1]]);
+            }
+            mesh.face_ranges.push(FaceRange {
+                face_id: KernelId(f as u64),
+                start_index,
+                end_index: mesh.indices.len() as u32,
+            });
+        }
+        repair_winding(&mut mesh);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        let volume_before = signed_volume(&mesh);
+
+        let report = defeature(&mut mesh, 1e-3, 1e-3);
+
+        assert_eq!(report.removed_faces, 1);
+        assert_eq!(report.collapsed_edges, 2);
+        assert!(mesh.face_ranges.iter().all(|r| r.face_id != KernelId(6)));
+        assert_eq!(mesh.face_ranges.len(), 6);
+        assert!(validate_mesh(&mesh).is_closed_manifold());
+        // The strip's corners meet at their centroid, which tilts the top
+        // and back faces by about half the strip width.
+        let tilt = e as f64;
+        assert!((signed_volume(&mesh) - volume_before).abs() < tilt);
+        assert!((signed_volume(&mesh) - 1.0).abs() < tilt);
+    }
+
+    #[test]
+    fn test_defeature_leaves_clean_box_alone() {
+        let mut mesh = box_mesh([0.0; 3]);
+        let before = mesh.clone();
+        assert_eq!(defeature(&mut mesh, 1e-3, 1e-3), DefeatureReport::default());
+        assert_eq!(mesh.indices, before.indices);
+        assert_eq!(mesh.vertices, before.vertices);
+    }
+
+    #[test]
+    fn test_bounding_box_of_triangle() {
+        let (min, max) = bounding_box(&triangle_mesh()).unwrap();
+        assert_eq!(min, [0.0, -1.0, -5.0]);
+        assert_eq!(max, [3.0, 4.0, 2.0]);
+    }
+
+    #[test]
+    fn test_bounding_box_of_empty_mesh() {
+        let mesh = RenderMesh {
+            vertices: Vec::new(),
+            normals: Vec::new(),
+            indices: Vec::new(),
+            face_ranges: Vec::new(),
+        };
+        assert!(bounding_box(&mesh).is_none());
+    }
+}
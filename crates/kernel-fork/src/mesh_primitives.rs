@@ -0,0 +1,298 @@
+//! Primitive shapes built directly as `RenderMesh`, without B-Rep topology.
+//!
+//! Useful for shapes truck can't sweep (helical paths) and for test inputs.
+//! The results can be brought into a kernel with `TruckKernel::import_mesh`.
+
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use crate::mesh;
+use crate::types::{KernelError, RenderMesh};
+
+/// Vertices around the wire cross-section of [`spring`].
+const SPRING_RING_SEGMENTS: usize = 16;
+
+/// Upper bound on [`icosphere`] subdivisions (about 1.3 million triangles).
+const ICOSPHERE_MAX_SUBDIVISIONS: u32 = 8;
+
+/// A coil spring: a circular wire swept along a helix about +Z, starting
+/// at `(coil_radius, 0, 0)`, with flat caps at both ends.
+///
+/// The cross-section lies in the plane through the Z axis, so the spring's
+/// centreline rises `pitch * turns` and its overall height is that plus
+/// `2 * wire_radius`. The pitch must exceed the wire diameter so coils don't
+/// touch.
+pub fn spring(
+    wire_radius: f64,
+    coil_radius: f64,
+    pitch: f64,
+    turns: f64,
+    segments_per_turn: usize,
+) -> Result<RenderMesh, KernelError> {
+    if wire_radius <= 0.0 || turns <= 0.0 || segments_per_turn < 3 {
+        return Err(invalid(
+            "spring needs a positive wire radius, turns and >= 3 segments",
+        ));
+    }
+    if coil_radius <= wire_radius {
+        return Err(invalid("spring coil radius must exceed the wire radius"));
+    }
+    if pitch <= 2.0 * wire_radius {
+        return Err(invalid("spring pitch must exceed the wire diameter"));
+    }
+
+    let steps = (turns * segments_per_turn as f64).ceil() as usize;
+    let sweep = turns * TAU;
+    let rise = pitch / TAU;
+    let m = SPRING_RING_SEGMENTS;
+    let mut out = RenderMesh {
+        vertices: Vec::new(),
+        normals: Vec::new(),
+        indices: Vec::new(),
+        face_ranges: Vec::new(),
+    };
+    let push = |out: &mut RenderMesh, p: [f64; 3], n: [f64; 3]| {
+        out.vertices.extend(p.map(|c| c as f32));
+        out.normals.extend(n.map(|c| c as f32));
+        (out.vertices.len() / 3 - 1) as u32
+    };
+    let ring_point = |theta: f64, phi: f64| {
+        let radial = [theta.cos(), theta.sin(), 0.0];
+        let n = [radial[0] * phi.cos(), radial[1] * phi.cos(), phi.sin()];
+        let p = [
+            coil_radius * radial[0] + wire_radius * n[0],
+            coil_radius * radial[1] + wire_radius * n[1],
+            rise * theta + wire_radius * n[2],
+        ];
+        (p, n)
+    };
+
+    // Tube wall.
+    let thetas: Vec<f64> = (0..=steps)
+        .map(|i| sweep * i as f64 / steps as f64)
+        .collect();
+    for &theta in &thetas {
+        for k in 0..m {
+            let (p, n) = ring_point(theta, TAU * k as f64 / m as f64);
+            push(&mut out, p, n);
+        }
+    }
+    for i in 0..steps as u32 {
+        for k in 0..m as u32 {
+            let a = i * m as u32 + k;
+            let b = i * m as u32 + (k + 1) % m as u32;
+            let (c, d) = (a + m as u32, b + m as u32);
+            out.indices.extend([a, b, d, a, d, c]);
+        }
+    }
+
+    // End caps, fanned from the centreline with the tangent as normal. Rim
+    // positions are copied from the wall so the seam welds exactly.
+    for (ring, sign) in [(0, -1.0), (steps, 1.0)] {
+        let theta = thetas[ring];
+        let tangent = {
+            let t = [-coil_radius * theta.sin(), coil_radius * theta.cos(), rise];
+            let len = (t[0] * t[0] + t[1] * t[1] + t[2] * t[2]).sqrt();
+            t.map(|c| sign * c / len)
+        };
+        let centre = [
+            coil_radius * theta.cos(),
+            coil_radius * theta.sin(),
+            rise * theta,
+        ];
+        let hub = push(&mut out, centre, tangent);
+        let first = hub + 1;
+        for k in 0..m {
+            let v = (ring * m + k) * 3;
+            let p = [0, 1, 2].map(|c| out.vertices[v + c] as f64);
+            push(&mut out, p, tangent);
+        }
+        for k in 0..m as u32 {
+            out.indices
+                .extend([hub, first + k, first + (k + 1) % m as u32]);
+        }
+    }
+
+    // The wall and caps were stitched without regard to winding.
+    mesh::repair_winding(&mut out);
+    Ok(out)
+}
+
+/// A sphere tessellated by subdividing an icosahedron `subdivisions` times
+/// and projecting onto the sphere.
+///
+/// Unlike a UV grid there are no poles, so triangles are near-uniform and
+/// every vertex has five or six neighbours. The mesh has `20 * 4^n`
+/// triangles and is closed and outward-wound.
+pub fn icosphere(
+    center: [f64; 3],
+    radius: f64,
+    subdivisions: u32,
+) -> Result<RenderMesh, KernelError> {
+    if radius <= 0.0 {
+        return Err(invalid("icosphere needs a positive radius"));
+    }
+    if subdivisions > ICOSPHERE_MAX_SUBDIVISIONS {
+        return Err(invalid(
+            "icosphere subdivisions exceed the supported maximum",
+        ));
+    }
+
+    let t = (1.0 + 5f64.sqrt()) / 2.0;
+    let mut directions: Vec<[f64; 3]> = [
+        [-1.0, t, 0.0],
+        [1.0, t, 0.0],
+        [-1.0, -t, 0.0],
+        [1.0, -t, 0.0],
+        [0.0, -1.0, t],
+        [0.0, 1.0, t],
+        [0.0, -1.0, -t],
+        [0.0, 1.0, -t],
+        [t, 0.0, -1.0],
+        [t, 0.0, 1.0],
+        [-t, 0.0, -1.0],
+        [-t, 0.0, 1.0],
+    ]
+    .iter()
+    .map(|&d| unit(d))
+    .collect();
+    let mut triangles: Vec<[u32; 3]> = vec![
+        [0, 11, 5],
+        [0, 5, 1],
+        [0, 1, 7],
+        [0, 7, 10],
+        [0, 10, 11],
+        [1, 5, 9],
+        [5, 11, 4],
+        [11, 10, 2],
+        [10, 7, 6],
+        [7, 1, 8],
+        [3, 9, 4],
+        [3, 4, 2],
+        [3, 2, 6],
+        [3, 6, 8],
+        [3, 8, 9],
+        [4, 9, 5],
+        [2, 4, 11],
+        [6, 2, 10],
+        [8, 6, 7],
+        [9, 8, 1],
+    ];
+
+    for _ in 0..subdivisions {
+        // Midpoints are shared between the two triangles on each edge.
+        let mut midpoints: HashMap<(u32, u32), u32> = HashMap::new();
+        let mut midpoint = |a: u32, b: u32, directions: &mut Vec<[f64; 3]>| {
+            *midpoints
+                .entry(mesh::undirected_edge(a, b))
+                .or_insert_with(|| {
+                    let (p, q) = (directions[a as usize], directions[b as usize]);
+                    directions.push(unit([p[0] + q[0], p[1] + q[1], p[2] + q[2]]));
+                    (directions.len() - 1) as u32
+                })
+        };
+        triangles = triangles
+            .iter()
+            .flat_map(|&[a, b, c]| {
+                let ab = midpoint(a, b, &mut directions);
+                let bc = midpoint(b, c, &mut directions);
+                let ca = midpoint(c, a, &mut directions);
+                [[a, ab, ca], [b, bc, ab], [c, ca, bc], [ab, bc, ca]]
+            })
+            .collect();
+    }
+
+    let mut out = RenderMesh {
+        vertices: Vec::with_capacity(directions.len() * 3),
+        normals: Vec::with_capacity(directions.len() * 3),
+        indices: triangles.into_iter().flatten().collect(),
+        face_ranges: Vec::new(),
+    };
+    for d in directions {
+        out.vertices
+            .extend([0, 1, 2].map(|k| (center[k] + radius * d[k]) as f32));
+        out.normals.extend(d.map(|c| c as f32));
+    }
+    Ok(out)
+}
+
+fn unit(v: [f64; 3]) -> [f64; 3] {
+    let len = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    v.map(|c| c / len)
+}
+
+fn invalid(message: &str) -> KernelError {
+    KernelError::Other {
+        message: message.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spring_is_closed_with_expected_height_and_volume() {
+        let (wire, coil, pitch, turns) = (0.5, 5.0, 2.0, 5.0);
+        let spring = spring(wire, coil, pitch, turns, 48).unwrap();
+
+        assert!(mesh::validate_mesh(&spring).is_closed_manifold());
+
+        let (min, max) = mesh::bounding_box(&spring).unwrap();
+        let centreline_height = max[2] - min[2] - 2.0 * wire;
+        assert!((centreline_height - pitch * turns).abs() < 1e-4);
+
+        // Pappus: wire area times helix length, less faceting.
+        let helix = turns * ((std::f64::consts::TAU * coil).powi(2) + pitch * pitch).sqrt();
+        let expected = std::f64::consts::PI * wire * wire * helix;
+        let volume = mesh::signed_volume(&spring);
+        assert!(volume > 0.0);
+        assert!(
+            (volume - expected).abs() / expected < 0.05,
+            "volume = {}",
+            volume
+        );
+    }
+
+    #[test]
+    fn test_spring_rejects_touching_coils() {
+        assert!(spring(1.0, 5.0, 1.5, 3.0, 32).is_err());
+    }
+
+    #[test]
+    fn test_icosphere_is_closed_and_more_uniform_than_uv_sphere() {
+        let ico = icosphere([1.0, 2.0, 3.0], 2.0, 3).unwrap();
+        assert_eq!(ico.indices.len() / 3, 20 * 4usize.pow(3));
+        assert!(mesh::validate_mesh(&ico).is_closed_manifold());
+
+        let volume = mesh::signed_volume(&ico);
+        let exact = 4.0 / 3.0 * std::f64::consts::PI * 8.0;
+        assert!(volume > 0.0 && (volume - exact).abs() / exact < 0.02);
+        for v in ico.vertices.chunks_exact(3) {
+            let d = [v[0] as f64 - 1.0, v[1] as f64 - 2.0, v[2] as f64 - 3.0];
+            let r = (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt();
+            assert!((r - 2.0).abs() < 1e-5);
+        }
+
+        let mut next_id = 0;
+        let uv = crate::tessellation::tessellate_solid(
+            &crate::primitives::make_sphere(2.0),
+            0.05,
+            &mut next_id,
+        )
+        .unwrap();
+        let (ico_q, uv_q) = (mesh::mesh_quality(&ico), mesh::mesh_quality(&uv));
+        assert!(
+            ico_q.area_variation < uv_q.area_variation,
+            "icosphere {:?} vs UV sphere {:?}",
+            ico_q,
+            uv_q
+        );
+        assert!(ico_q.min_angle_degrees > 40.0);
+    }
+
+    #[test]
+    fn test_icosphere_rejects_bad_radius() {
+        assert!(icosphere([0.0; 3], 0.0, 1).is_err());
+    }
+}
@@ -1652,4 +1652,30 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_edge_convexity_box_all_convex() {
+        let mut kernel = MockKernel::new();
+        let (handle, solid) = kernel.make_box_solid(2.0, 3.0, 4.0);
+        kernel.solids.insert(handle.id(), solid);
+
+        for edge in kernel.list_edges(&handle) {
+            assert_eq!(kernel.edge_convexity(edge), Some(Convexity::Convex));
+        }
+        assert_eq!(kernel.edge_convexity(KernelId(999_999)), None);
+    }
+
+    #[test]
+    fn test_edge_convexity_of_border_edge_is_none() {
+        let mut kernel = MockKernel::new();
+        let (handle, mut solid) = kernel.make_box_solid(2.0, 3.0, 4.0);
+        // Leave the box open on one side.
+        let removed = solid.faces.pop().unwrap();
+        kernel.solids.insert(handle.id(), solid);
+
+        for edge in removed.edges {
+            assert_eq!(kernel.edge_faces(edge).len(), 1);
+            assert_eq!(kernel.edge_convexity(edge), None);
+        }
+    }
 }
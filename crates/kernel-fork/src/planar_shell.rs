@@ -0,0 +1,199 @@
+//! Hollowing of solids bounded by planes.
+//!
+//! Every kept face gets an inner copy, offset into the material by the wall
+//! thickness and turned to face the cavity. Each inner vertex is where the
+//! offset planes of the faces around its outer vertex meet, with the planes
+//! of removed faces left in place. A removed face becomes a rim: its own
+//! outline with the inner outline as a hole, joining the two skins.
+
+use std::collections::HashMap;
+
+use truck_modeling::geometry::{Curve, Line, Plane, Surface};
+use truck_modeling::topology::{Edge, Face, Shell, Solid, Wire};
+use truck_modeling::{builder, InnerSpace, Vector3};
+
+use crate::types::KernelError;
+
+/// How far, relative to the wall thickness, an inner vertex may miss the
+/// offset plane of one of its faces before the vertex is rejected.
+const PLANE_FIT_TOLERANCE: f64 = 1e-6;
+
+fn shell_failed(reason: impl Into<String>) -> KernelError {
+    KernelError::ShellFailed {
+        reason: reason.into(),
+    }
+}
+
+/// Hollow `solid` to walls `thickness` thick, opening the faces at the
+/// indices in `remove` (in `face_iter` order of its single shell).
+///
+/// With nothing removed the result is a closed solid with an inner void.
+/// Every face must be planar, at most three faces may meet at a vertex
+/// (more only if their offset planes still meet in a point), removed faces
+/// must have no holes and share no edge, and the thickness must not turn
+/// any inner edge around.
+pub fn shell_planar_solid(
+    solid: &Solid,
+    remove: &[usize],
+    thickness: f64,
+) -> Result<Solid, KernelError> {
+    let [shell] = solid.boundaries().as_slice() else {
+        return Err(shell_failed("solids with inner voids cannot be shelled"));
+    };
+    let faces: Vec<&Face> = shell.face_iter().collect();
+    if let Some(i) = remove.iter().find(|&&i| i >= faces.len()) {
+        return Err(shell_failed(format!("face {} not found in solid", i)));
+    }
+    let removed: Vec<bool> = (0..faces.len()).map(|f| remove.contains(&f)).collect();
+    if removed.iter().all(|&r| r) {
+        return Err(shell_failed("cannot remove every face"));
+    }
+    let planes = faces
+        .iter()
+        .map(|face| match face.oriented_surface() {
+            Surface::Plane(plane) => Ok(plane),
+            _ => Err(shell_failed("only solids bounded by planes can be shelled")),
+        })
+        .collect::<Result<Vec<Plane>, _>>()?;
+    if faces
+        .iter()
+        .zip(&removed)
+        .any(|(face, &r)| r && face.boundaries().len() > 1)
+    {
+        return Err(shell_failed("removed faces must not have holes"));
+    }
+
+    // Faces around every vertex and every edge.
+    let mut vertex_faces = HashMap::new();
+    let mut edge_faces = HashMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for edge in face.boundaries().iter().flat_map(|wire| wire.iter()) {
+            let v = edge.front();
+            vertex_faces
+                .entry(v.id())
+                .or_insert_with(|| (v.point(), Vec::new()))
+                .1
+                .push(f);
+            edge_faces
+                .entry(edge.id())
+                .or_insert_with(|| (edge.clone(), Vec::new()))
+                .1
+                .push(f);
+        }
+    }
+
+    let mut inner_vertices = HashMap::new();
+    for (id, (point, around)) in &vertex_faces {
+        let offsets: Vec<(Vector3, f64)> = around
+            .iter()
+            .map(|&f| {
+                let depth = if removed[f] { 0.0 } else { thickness };
+                let n = planes[f].normal();
+                (n, n.dot(planes[f].origin() - *point) - depth)
+            })
+            .collect();
+        let p = *point + meet(&offsets, thickness)?;
+        inner_vertices.insert(*id, builder::vertex(p));
+    }
+
+    let mut inner_edges = HashMap::new();
+    for (id, (edge, around)) in &edge_faces {
+        if around.iter().all(|&f| removed[f]) {
+            return Err(shell_failed("removed faces must not share an edge"));
+        }
+        let (a, b) = (
+            &inner_vertices[&edge.absolute_front().id()],
+            &inner_vertices[&edge.absolute_back().id()],
+        );
+        let outer = edge.absolute_back().point() - edge.absolute_front().point();
+        if (b.point() - a.point()).dot(outer) <= 0.0 {
+            return Err(shell_failed(format!(
+                "thickness {} is too large for the solid",
+                thickness
+            )));
+        }
+        let line = Curve::Line(Line(a.point(), b.point()));
+        inner_edges.insert(*id, Edge::new(a, b, line));
+    }
+    // The inner copy of a wire, running the same way round.
+    let inner_wire = |wire: &Wire| -> Wire {
+        wire.iter()
+            .map(|edge| {
+                let inner = &inner_edges[&edge.id()];
+                if edge.orientation() {
+                    inner.clone()
+                } else {
+                    inner.inverse()
+                }
+            })
+            .collect()
+    };
+
+    let mut outer_faces = Vec::new();
+    let mut inner_faces = Vec::new();
+    for (f, face) in faces.iter().enumerate() {
+        let boundaries = face.boundaries();
+        if removed[f] {
+            let wires = vec![boundaries[0].clone(), inner_wire(&boundaries[0]).inverse()];
+            let rim = Face::try_new(wires, Surface::Plane(planes[f]))
+                .map_err(|e| shell_failed(format!("rim face: {}", e)))?;
+            outer_faces.push(rim);
+            continue;
+        }
+        outer_faces.push((*face).clone());
+        let plane = &planes[f];
+        let origin = plane.origin() - plane.normal() * thickness;
+        // Swapped axes turn the offset plane to face the cavity.
+        let surface = Plane::new(origin, origin + plane.v_axis(), origin + plane.u_axis());
+        let wires = boundaries.iter().map(|w| inner_wire(w).inverse()).collect();
+        let inner = Face::try_new(wires, Surface::Plane(surface))
+            .map_err(|e| shell_failed(format!("inner face: {}", e)))?;
+        inner_faces.push(inner);
+    }
+
+    let shells = if remove.is_empty() {
+        vec![Shell::from(outer_faces), Shell::from(inner_faces)]
+    } else {
+        outer_faces.extend(inner_faces);
+        vec![Shell::from(outer_faces)]
+    };
+    Solid::try_new(shells).map_err(|e| shell_failed(e.to_string()))
+}
+
+/// Offset from an outer vertex to its inner one, given `n · offset = d` for
+/// each `(n, d)` of the faces around it: the least-squares solution, which
+/// must satisfy every face to within the tolerance.
+fn meet(faces: &[(Vector3, f64)], thickness: f64) -> Result<Vector3, KernelError> {
+    // Normal equations A x = b with A = sum n n^T and b = sum d n.
+    let mut a = [[0.0; 3]; 3];
+    let mut b = Vector3::new(0.0, 0.0, 0.0);
+    for &(n, d) in faces {
+        for (i, row) in a.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell += n[i] * n[j];
+            }
+        }
+        b += n * d;
+    }
+    let columns = [0, 1, 2].map(|j| Vector3::new(a[0][j], a[1][j], a[2][j]));
+    let det = columns[0].dot(columns[1].cross(columns[2]));
+    if det.abs() < 1e-12 {
+        return Err(shell_failed("faces at a vertex do not meet in a point"));
+    }
+    // Cramer's rule.
+    let solve = |k: usize| {
+        let mut c = columns;
+        c[k] = b;
+        c[0].dot(c[1].cross(c[2])) / det
+    };
+    let offset = Vector3::new(solve(0), solve(1), solve(2));
+    if faces
+        .iter()
+        .any(|&(n, d)| (n.dot(offset) - d).abs() > PLANE_FIT_TOLERANCE * thickness)
+    {
+        return Err(shell_failed(
+            "more than three faces meet at a vertex and their offsets disagree",
+        ));
+    }
+    Ok(offset)
+}
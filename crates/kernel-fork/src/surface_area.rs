@@ -0,0 +1,254 @@
+//! Surface area of B-Rep solids measured on the exact geometry.
+//!
+//! Planar faces are measured with Green's theorem around their boundary
+//! curves, and swept NURBS faces that cover their whole parameter rectangle
+//! by integrating `|Su x Sv|` over it, both with adaptive Gauss-Legendre
+//! quadrature, so the result does not depend on how finely the solid would be
+//! tessellated. Every other face falls back to a fine tessellation: revolved
+//! surfaces (spheres included, which may be partial turns), and non-planar
+//! faces that are trimmed or have holes, such as a cylinder wall cut short by
+//! a boolean.
+
+use std::ops::Bound;
+
+use truck_meshalgo::prelude::*;
+use truck_meshalgo::tessellation::MeshableShape;
+use truck_modeling::geometry::Surface;
+use truck_modeling::topology::{Face, Solid};
+use truck_modeling::{
+    BoundedCurve, InnerSpace, ParametricCurve, ParametricSurface, Point3, SPHint2D, SearchParameter,
+};
+
+use crate::curve_sampling::GAUSS_LEGENDRE_5;
+
+/// Relative accuracy requested from the adaptive quadrature.
+const QUADRATURE_TOLERANCE: f64 = 1e-13;
+
+/// Subdivision depth after which a quadrature interval is accepted as is.
+const QUADRATURE_MAX_DEPTH: u32 = 30;
+
+/// Chord tolerance for faces measured on their tessellation.
+const FALLBACK_TOLERANCE: f64 = 1e-3;
+
+/// Newton iterations allowed when mapping a boundary point to (u, v).
+const SEARCH_TRIALS: usize = 100;
+
+/// Distance, relative to the parameter span, at which a boundary point
+/// still counts as on the edge of the parameter rectangle.
+const BORDER_TOLERANCE: f64 = 1e-6;
+
+/// Total area of every face of `solid`.
+pub fn solid_surface_area(solid: &Solid) -> f64 {
+    let faces: Vec<&Face> = solid
+        .boundaries()
+        .iter()
+        .flat_map(|shell| shell.face_iter())
+        .collect();
+    let exact: Vec<Option<f64>> = faces.iter().map(|face| face_area(face)).collect();
+    if exact.iter().all(Option::is_some) {
+        return exact.into_iter().flatten().sum();
+    }
+
+    // Meshed faces come back in the same order as the solid's faces.
+    let meshed = solid.triangulation(FALLBACK_TOLERANCE);
+    let meshed_faces = meshed
+        .boundaries()
+        .iter()
+        .flat_map(|shell| shell.face_iter().map(|face| face.surface()))
+        .collect::<Vec<Option<PolygonMesh>>>();
+    exact
+        .into_iter()
+        .zip(meshed_faces)
+        .map(|(area, mesh)| area.unwrap_or_else(|| mesh.as_ref().map_or(0.0, polygon_area)))
+        .sum()
+}
+
+/// Exact area of one face, or `None` if it has to be measured on its
+/// tessellation: a revolved surface, a surface with no bounded domain, or a
+/// non-planar face that does not cover its whole parameter rectangle.
+fn face_area(face: &Face) -> Option<f64> {
+    match face.oriented_surface() {
+        Surface::Plane(plane) => Some(planar_face_area(face, plane.normal())),
+        Surface::RevolutedCurve(_) => None,
+        surface => {
+            let (u_range, v_range) = surface.parameter_range();
+            let (u0, u1) = bounded(u_range)?;
+            let (v0, v1) = bounded(v_range)?;
+            if !spans_parameter_domain(face, &surface, (u0, u1), (v0, v1)) {
+                return None;
+            }
+            Some(integrate(
+                &|u| {
+                    integrate(
+                        &|v| surface.uder(u, v).cross(surface.vder(u, v)).magnitude(),
+                        v0,
+                        v1,
+                    )
+                },
+                u0,
+                u1,
+            ))
+        }
+    }
+}
+
+/// Green's theorem in the face plane: half the circulation of `p x dp`
+/// around every boundary wire. Hole wires run the other way and subtract.
+fn planar_face_area(face: &Face, normal: truck_modeling::Vector3) -> f64 {
+    let Some(origin) = face
+        .boundaries()
+        .first()
+        .and_then(|wire| wire.front_vertex())
+        .map(|v| v.point())
+    else {
+        return 0.0;
+    };
+    let circulation: f64 = face
+        .boundaries()
+        .iter()
+        .flat_map(|wire| wire.edge_iter().map(|edge| edge.oriented_curve()))
+        .map(|curve| {
+            let (t0, t1) = curve.range_tuple();
+            integrate(
+                &|t| normal.dot((curve.subs(t) - origin).cross(curve.der(t))),
+                t0,
+                t1,
+            )
+        })
+        .sum();
+    circulation.abs() / 2.0
+}
+
+/// Whether `face` is its surface's whole parameter rectangle: a single wire
+/// whose edges (checked at their ends and midpoints) all run along the
+/// rectangle's border. Trimmed faces and faces with holes are not.
+fn spans_parameter_domain(
+    face: &Face,
+    surface: &Surface,
+    (u0, u1): (f64, f64),
+    (v0, v1): (f64, f64),
+) -> bool {
+    if face.boundaries().len() != 1 {
+        return false;
+    }
+    let on_border = |x: f64, lo: f64, hi: f64| {
+        let tolerance = BORDER_TOLERANCE * (hi - lo).abs().max(1.0);
+        (x - lo).abs() <= tolerance || (x - hi).abs() <= tolerance
+    };
+    face.boundaries()[0].edge_iter().all(|edge| {
+        let curve = edge.oriented_curve();
+        let (t0, t1) = curve.range_tuple();
+        [t0, (t0 + t1) / 2.0, t1].into_iter().all(|t| {
+            match surface.search_parameter(curve.subs(t), SPHint2D::None, SEARCH_TRIALS) {
+                Some((u, v)) => on_border(u, u0, u1) || on_border(v, v0, v1),
+                None => false,
+            }
+        })
+    })
+}
+
+fn polygon_area(mesh: &PolygonMesh) -> f64 {
+    let positions = mesh.positions();
+    mesh.tri_faces()
+        .iter()
+        .map(|tri| {
+            let [a, b, c]: [Point3; 3] = [0, 1, 2].map(|k| positions[tri[k].pos]);
+            (b - a).cross(c - a).magnitude() / 2.0
+        })
+        .sum()
+}
+
+fn bounded((start, end): (Bound<f64>, Bound<f64>)) -> Option<(f64, f64)> {
+    match (start, end) {
+        (Bound::Included(a) | Bound::Excluded(a), Bound::Included(b) | Bound::Excluded(b)) => {
+            Some((a, b))
+        }
+        _ => None,
+    }
+}
+
+/// Adaptive Gauss-Legendre quadrature of `f` over `[a, b]`.
+///
+/// Intervals are halved until the two halves agree with the whole, which
+/// copes with the derivative kinks at NURBS knots.
+fn integrate(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    let whole = gauss_legendre(f, a, b);
+    // Tolerance relative to the integral of |f|, so integrands that cancel
+    // out (or vanish) don't chase rounding noise.
+    let scale = gauss_legendre(&|t| f(t).abs(), a, b).max(f64::MIN_POSITIVE);
+    refine(f, a, b, whole, scale, 0)
+}
+
+fn refine(f: &dyn Fn(f64) -> f64, a: f64, b: f64, whole: f64, scale: f64, depth: u32) -> f64 {
+    let mid = (a + b) / 2.0;
+    let (left, right) = (gauss_legendre(f, a, mid), gauss_legendre(f, mid, b));
+    if depth >= QUADRATURE_MAX_DEPTH || (left + right - whole).abs() <= QUADRATURE_TOLERANCE * scale
+    {
+        return left + right;
+    }
+    refine(f, a, mid, left, scale, depth + 1) + refine(f, mid, b, right, scale, depth + 1)
+}
+
+fn gauss_legendre(f: &dyn Fn(f64) -> f64, a: f64, b: f64) -> f64 {
+    let (mid, half) = ((a + b) / 2.0, (b - a) / 2.0);
+    GAUSS_LEGENDRE_5
+        .iter()
+        .map(|&(x, w)| w * f(mid + x * half))
+        .sum::<f64>()
+        * half
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_unit_cube_area() {
+        let area = solid_surface_area(&primitives::make_box(1.0, 1.0, 1.0));
+        assert!((area - 6.0).abs() < 1e-12, "area = {}", area);
+    }
+
+    #[test]
+    fn test_box_area() {
+        let area = solid_surface_area(&primitives::make_box(2.0, 3.0, 4.0));
+        assert!((area - 52.0).abs() < 1e-9, "area = {}", area);
+    }
+
+    #[test]
+    fn test_cylinder_area_is_exact() {
+        let (r, h) = (0.75, 2.5);
+        let area = solid_surface_area(&primitives::make_cylinder(r, h));
+        let expected = 2.0 * PI * r * r + 2.0 * PI * r * h;
+        assert!(
+            (area - expected).abs() < 1e-9,
+            "area = {}, expected {}",
+            area,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_plate_with_hole_area() {
+        use truck_modeling::{builder, Rad, Vector3};
+
+        // A 4x4x1 plate punched by a taller r=1 cylinder: the hole wall is
+        // the cylinder's side trimmed to the plate's thickness, and the top
+        // and bottom lose the disk. 2(16 - pi) + 4*4*1 + 2*pi*1*1 = 48.
+        // The tool is built like the one in truck_kernel's box-cylinder
+        // boolean test, which truck can subtract.
+        let plate = primitives::make_box(4.0, 4.0, 1.0);
+        let v = builder::vertex(Point3::new(2.0, 1.0, -0.5));
+        let w = builder::rsweep(&v, Point3::new(2.0, 2.0, 0.0), Vector3::unit_z(), Rad(7.0));
+        let f = builder::try_attach_plane(&[w]).unwrap();
+        let mut tool = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+        tool.not();
+        let punched = truck_shapeops::and(&plate, &tool, 0.05).expect("plate boolean");
+
+        let area = solid_surface_area(&punched);
+        // The trimmed wall is measured on its tessellation, not the full
+        // 2*pi*1*2 of the untrimmed cylinder side.
+        assert!((area - 48.0).abs() < 0.02, "area = {}", area);
+    }
+}
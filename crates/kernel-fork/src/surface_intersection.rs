@@ -0,0 +1,261 @@
+//! Closed-form intersection curves of analytic surfaces, for sectioning and
+//! for callers building their own operations.
+//!
+//! truck's booleans intersect surfaces numerically inside truck-shapeops and
+//! do not expose the curves, so this covers the analytic pairs directly.
+
+use truck_modeling::{InnerSpace, Vector3};
+
+use crate::types::KernelError;
+
+/// Below this, directions count as parallel and distances as zero.
+const EPS: f64 = 1e-9;
+
+/// An unbounded analytic surface.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AnalyticSurface {
+    /// Plane through `origin` with the given normal.
+    Plane { origin: [f64; 3], normal: [f64; 3] },
+    /// Infinite cylinder around the line through `origin` along `axis`.
+    Cylinder {
+        origin: [f64; 3],
+        axis: [f64; 3],
+        radius: f64,
+    },
+    /// Sphere around `center`.
+    Sphere { center: [f64; 3], radius: f64 },
+}
+
+/// An intersection curve. Directions and axes are unit length unless noted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IntersectionCurve {
+    /// Infinite line through `origin`.
+    Line {
+        origin: [f64; 3],
+        direction: [f64; 3],
+    },
+    /// Circle in the plane through `center` with the given normal.
+    Circle {
+        center: [f64; 3],
+        normal: [f64; 3],
+        radius: f64,
+    },
+    /// Ellipse given by its semi-axis vectors, whose lengths are the
+    /// semi-axis lengths (`major_axis` is the longer).
+    Ellipse {
+        center: [f64; 3],
+        major_axis: [f64; 3],
+        minor_axis: [f64; 3],
+    },
+}
+
+/// Intersection curves of two surfaces, in either order.
+///
+/// Supported pairs are plane/plane, plane/cylinder and plane/sphere; others
+/// fail with `NotSupported`. Disjoint surfaces give no curves, and so do
+/// coincident planes and single tangent points, which are not curves.
+pub fn intersect_surfaces(
+    a: &AnalyticSurface,
+    b: &AnalyticSurface,
+) -> Result<Vec<IntersectionCurve>, KernelError> {
+    use AnalyticSurface::*;
+    match (*a, *b) {
+        (Plane { origin: o1, normal: n1 }, Plane { origin: o2, normal: n2 }) => {
+            Ok(plane_plane(vec3(o1), unit(n1)?, vec3(o2), unit(n2)?))
+        }
+        (Plane { origin, normal }, Cylinder { origin: c, axis, radius })
+        | (Cylinder { origin: c, axis, radius }, Plane { origin, normal }) => Ok(plane_cylinder(
+            vec3(origin),
+            unit(normal)?,
+            vec3(c),
+            unit(axis)?,
+            radius,
+        )),
+        (Plane { origin, normal }, Sphere { center, radius })
+        | (Sphere { center, radius }, Plane { origin, normal }) => Ok(plane_sphere(
+            vec3(origin),
+            unit(normal)?,
+            vec3(center),
+            radius,
+        )),
+        _ => Err(KernelError::NotSupported {
+            operation: format!("intersection of {:?} and {:?}", a, b),
+        }),
+    }
+}
+
+fn plane_plane(o1: Vector3, n1: Vector3, o2: Vector3, n2: Vector3) -> Vec<IntersectionCurve> {
+    let direction = n1.cross(n2);
+    let len2 = direction.magnitude2();
+    if len2 < EPS * EPS {
+        return Vec::new();
+    }
+    // The point on the line closest to the world origin.
+    let (h1, h2, k) = (n1.dot(o1), n2.dot(o2), n1.dot(n2));
+    let origin = (n1 * (h1 - h2 * k) + n2 * (h2 - h1 * k)) / len2;
+    vec![IntersectionCurve::Line {
+        origin: origin.into(),
+        direction: (direction / len2.sqrt()).into(),
+    }]
+}
+
+fn plane_cylinder(
+    origin: Vector3,
+    normal: Vector3,
+    c: Vector3,
+    axis: Vector3,
+    radius: f64,
+) -> Vec<IntersectionCurve> {
+    let cos = normal.dot(axis);
+    // Signed distance of the axis point from the plane.
+    let offset = normal.dot(c - origin);
+    if cos.abs() < EPS {
+        // Plane parallel to the axis: up to two rulings.
+        if offset.abs() > radius + EPS {
+            return Vec::new();
+        }
+        let foot = c - normal * offset;
+        let half_width = (radius * radius - offset * offset).max(0.0).sqrt();
+        let across = axis.cross(normal);
+        let line = |p: Vector3| IntersectionCurve::Line {
+            origin: p.into(),
+            direction: axis.into(),
+        };
+        return if half_width < EPS {
+            vec![line(foot)]
+        } else {
+            vec![line(foot + across * half_width), line(foot - across * half_width)]
+        };
+    }
+
+    let center = c - axis * (offset / cos);
+    if (1.0 - cos.abs()) < EPS {
+        return vec![IntersectionCurve::Circle {
+            center: center.into(),
+            normal: normal.into(),
+            radius,
+        }];
+    }
+    // The minor axis lies along the plane's trace perpendicular to the axis;
+    // the major axis is stretched by 1 / cos of the tilt.
+    let minor = axis.cross(normal).normalize();
+    let major = normal.cross(minor);
+    vec![IntersectionCurve::Ellipse {
+        center: center.into(),
+        major_axis: (major * (radius / cos.abs())).into(),
+        minor_axis: (minor * radius).into(),
+    }]
+}
+
+fn plane_sphere(
+    origin: Vector3,
+    normal: Vector3,
+    center: Vector3,
+    radius: f64,
+) -> Vec<IntersectionCurve> {
+    let offset = normal.dot(center - origin);
+    let circle_radius = (radius * radius - offset * offset).max(0.0).sqrt();
+    if offset.abs() > radius || circle_radius < EPS {
+        return Vec::new();
+    }
+    vec![IntersectionCurve::Circle {
+        center: (center - normal * offset).into(),
+        normal: normal.into(),
+        radius: circle_radius,
+    }]
+}
+
+fn vec3(v: [f64; 3]) -> Vector3 {
+    Vector3::new(v[0], v[1], v[2])
+}
+
+fn unit(v: [f64; 3]) -> Result<Vector3, KernelError> {
+    let v = vec3(v);
+    let len = v.magnitude();
+    if len < EPS {
+        return Err(KernelError::Other {
+            message: "surface direction has zero length".to_string(),
+        });
+    }
+    Ok(v / len)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_near(a: [f64; 3], b: [f64; 3]) {
+        let d = vec3(a) - vec3(b);
+        assert!(d.magnitude() < 1e-9, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn test_perpendicular_planes_meet_in_a_line() {
+        let floor = AnalyticSurface::Plane {
+            origin: [0.0, 0.0, 0.0],
+            normal: [0.0, 0.0, 1.0],
+        };
+        let wall = AnalyticSurface::Plane {
+            origin: [1.0, 5.0, 3.0],
+            normal: [2.0, 0.0, 0.0],
+        };
+        let curves = intersect_surfaces(&floor, &wall).unwrap();
+        let [IntersectionCurve::Line { origin, direction }] = curves[..] else {
+            panic!("expected one line, got {:?}", curves);
+        };
+        assert_near(origin, [1.0, 0.0, 0.0]);
+        assert!((direction[1].abs() - 1.0).abs() < 1e-12, "{:?}", direction);
+
+        let parallel = AnalyticSurface::Plane {
+            origin: [0.0, 0.0, 2.0],
+            normal: [0.0, 0.0, -1.0],
+        };
+        assert!(intersect_surfaces(&floor, &parallel).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tilted_plane_cuts_cylinder_in_an_ellipse() {
+        let cylinder = AnalyticSurface::Cylinder {
+            origin: [0.0, 0.0, -4.0],
+            axis: [0.0, 0.0, 1.0],
+            radius: 1.0,
+        };
+        // 45 degrees to the axis, through (0, 0, 1).
+        let plane = AnalyticSurface::Plane {
+            origin: [0.0, 0.0, 1.0],
+            normal: [1.0, 0.0, 1.0],
+        };
+        let curves = intersect_surfaces(&cylinder, &plane).unwrap();
+        let [IntersectionCurve::Ellipse {
+            center,
+            major_axis,
+            minor_axis,
+        }] = curves[..]
+        else {
+            panic!("expected one ellipse, got {:?}", curves);
+        };
+        assert_near(center, [0.0, 0.0, 1.0]);
+        assert!((vec3(major_axis).magnitude() - 2f64.sqrt()).abs() < 1e-9);
+        assert!((vec3(minor_axis).magnitude() - 1.0).abs() < 1e-9);
+        assert!(vec3(minor_axis).dot(Vector3::unit_z()).abs() < 1e-9);
+
+        // Cutting straight across gives a circle, along the axis two lines.
+        let across = AnalyticSurface::Plane {
+            origin: [0.0, 0.0, 1.0],
+            normal: [0.0, 0.0, 1.0],
+        };
+        assert!(matches!(
+            intersect_surfaces(&plane, &cylinder).unwrap()[..],
+            [IntersectionCurve::Ellipse { .. }]
+        ));
+        assert!(matches!(
+            intersect_surfaces(&cylinder, &across).unwrap()[..],
+            [IntersectionCurve::Circle { radius, .. }] if (radius - 1.0).abs() < 1e-12
+        ));
+        let along = AnalyticSurface::Plane {
+            origin: [0.5, 0.0, 0.0],
+            normal: [1.0, 0.0, 0.0],
+        };
+        assert_eq!(intersect_surfaces(&cylinder, &along).unwrap().len(), 2);
+    }
+}
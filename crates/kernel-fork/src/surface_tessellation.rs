@@ -0,0 +1,437 @@
+//! Adaptive tessellation of a single parametric surface.
+//!
+//! The parameter rectangle is refined as a quadtree: a cell is split along a
+//! direction only where its edges along that direction stray from the
+//! surface, so flat or gently curved regions keep large triangles. Leaf cells
+//! are stitched at T-junctions, so the mesh has no cracks. Grid lines that
+//! collapse to a point, like the poles of a sphere, become a single vertex
+//! with a fan of triangles around it.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use truck_modeling::{
+    EuclideanSpace, InnerSpace, MetricSpace, ParametricSurface, Point3, Vector3,
+};
+
+use crate::types::{KernelError, RenderMesh};
+
+/// Resolution of the integer parameter grid cells are placed on. Cells
+/// never get narrower than one grid step, which bounds the recursion.
+const GRID: u32 = 1 << 12;
+
+/// Cells per direction before any refinement, so a surface sampled only at
+/// its corners and edge midpoints can't look flat by coincidence.
+const INITIAL_DIVISIONS: u32 = 4;
+
+/// Below this `|Su x Sv|` the normal is taken from a nearby point instead.
+const DEGENERATE_NORMAL: f64 = 1e-12;
+
+/// Fraction of the chord tolerance within which every point of a grid line
+/// must lie for the line to count as collapsed to a single point.
+const COLLAPSED_LINE: f64 = 1e-6;
+
+/// Smallest chord tolerance allowed, as a fraction of the surface's size.
+/// Anything finer would refine curved cells down to single grid steps.
+const MIN_RELATIVE_TOLERANCE: f64 = 1e-4;
+
+/// Samples per direction used to measure the surface's size.
+const SIZE_SAMPLES: u32 = 8;
+
+/// Tessellate `surface` over `u_range x v_range` so that every triangle edge
+/// midpoint lies within about `chord_tolerance` of the surface.
+///
+/// A cell is split in `u` when one of its `u`-direction edges deviates from
+/// the surface at its midpoint by more than the tolerance, likewise in `v`,
+/// and across its longer side when only its diagonal does. Normals are
+/// `Su x Sv`, and triangles wind counter-clockwise about them.
+///
+/// The tolerance must be positive and finite. It is raised to at least
+/// 1e-4 of the surface's size, so a tiny tolerance can't refine every cell
+/// down to the grid resolution.
+pub fn tessellate_surface_adaptive<S>(
+    surface: &S,
+    u_range: (f64, f64),
+    v_range: (f64, f64),
+    chord_tolerance: f64,
+) -> Result<RenderMesh, KernelError>
+where
+    S: ParametricSurface<Point = Point3, Vector = Vector3>,
+{
+    if !chord_tolerance.is_finite() || chord_tolerance <= 0.0 {
+        return Err(KernelError::TessellationFailed {
+            reason: format!(
+                "chord tolerance must be positive and finite, got {}",
+                chord_tolerance
+            ),
+        });
+    }
+    let mut domain = Domain {
+        surface,
+        u_range,
+        v_range,
+        tolerance: chord_tolerance,
+    };
+    domain.tolerance = chord_tolerance.max(MIN_RELATIVE_TOLERANCE * domain.size());
+    let step = GRID / INITIAL_DIVISIONS;
+    let mut leaves = Vec::new();
+    for i in 0..INITIAL_DIVISIONS {
+        for j in 0..INITIAL_DIVISIONS {
+            let cell = Cell {
+                u0: i * step,
+                u1: (i + 1) * step,
+                v0: j * step,
+                v1: (j + 1) * step,
+            };
+            domain.refine(cell, &mut leaves);
+        }
+    }
+    Ok(domain.triangulate(&leaves))
+}
+
+/// A cell of the parameter rectangle, in grid steps.
+#[derive(Debug, Clone, Copy)]
+struct Cell {
+    u0: u32,
+    u1: u32,
+    v0: u32,
+    v1: u32,
+}
+
+struct Domain<'a, S> {
+    surface: &'a S,
+    u_range: (f64, f64),
+    v_range: (f64, f64),
+    tolerance: f64,
+}
+
+impl<S> Domain<'_, S>
+where
+    S: ParametricSurface<Point = Point3, Vector = Vector3>,
+{
+    fn param(&self, u: f64, v: f64) -> (f64, f64) {
+        let (u0, u1) = self.u_range;
+        let (v0, v1) = self.v_range;
+        let g = GRID as f64;
+        (u0 + (u1 - u0) * u / g, v0 + (v1 - v0) * v / g)
+    }
+
+    fn point(&self, u: f64, v: f64) -> Point3 {
+        let (u, v) = self.param(u, v);
+        self.surface.subs(u, v)
+    }
+
+    /// Diagonal of the bounding box of a coarse grid of surface points.
+    fn size(&self) -> f64 {
+        let step = GRID as f64 / SIZE_SAMPLES as f64;
+        let mut points = (0..=SIZE_SAMPLES).flat_map(|i| {
+            (0..=SIZE_SAMPLES).map(move |j| self.point(i as f64 * step, j as f64 * step))
+        });
+        let Some(first) = points.next() else {
+            return 0.0;
+        };
+        let (min, max) = points.fold((first, first), |(min, max), p| {
+            (
+                Point3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z)),
+                Point3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z)),
+            )
+        });
+        let size = min.distance(max);
+        if size.is_finite() {
+            size
+        } else {
+            0.0
+        }
+    }
+
+    /// Whether the chord between two grid points misses the surface by more
+    /// than the tolerance at its midpoint.
+    fn deviates(&self, a: (u32, u32), b: (u32, u32)) -> bool {
+        let (pa, pb) = (
+            self.point(a.0 as f64, a.1 as f64),
+            self.point(b.0 as f64, b.1 as f64),
+        );
+        let on_surface = self.point(
+            (a.0 as f64 + b.0 as f64) / 2.0,
+            (a.1 as f64 + b.1 as f64) / 2.0,
+        );
+        pa.midpoint(pb).distance(on_surface) > self.tolerance
+    }
+
+    fn length(&self, a: (u32, u32), b: (u32, u32)) -> f64 {
+        self.point(a.0 as f64, a.1 as f64)
+            .distance(self.point(b.0 as f64, b.1 as f64))
+    }
+
+    fn refine(&self, cell: Cell, leaves: &mut Vec<Cell>) {
+        let Cell { u0, u1, v0, v1 } = cell;
+        let (can_split_u, can_split_v) = (u1 - u0 > 1, v1 - v0 > 1);
+        let mut split_u =
+            can_split_u && (self.deviates((u0, v0), (u1, v0)) || self.deviates((u0, v1), (u1, v1)));
+        let mut split_v =
+            can_split_v && (self.deviates((u0, v0), (u0, v1)) || self.deviates((u1, v0), (u1, v1)));
+        if !split_u && !split_v && self.deviates((u0, v0), (u1, v1)) {
+            // Only the diagonal is off: halving the longer side is enough.
+            let u_length = self.length((u0, v0), (u1, v0)).max(self.length((u0, v1), (u1, v1)));
+            let v_length = self.length((u0, v0), (u0, v1)).max(self.length((u1, v0), (u1, v1)));
+            if can_split_u && (u_length >= v_length || !can_split_v) {
+                split_u = true;
+            } else {
+                split_v = can_split_v;
+            }
+        }
+
+        let us = if split_u {
+            vec![(u0, (u0 + u1) / 2), ((u0 + u1) / 2, u1)]
+        } else {
+            vec![(u0, u1)]
+        };
+        let vs = if split_v {
+            vec![(v0, (v0 + v1) / 2), ((v0 + v1) / 2, v1)]
+        } else {
+            vec![(v0, v1)]
+        };
+        if us.len() == 1 && vs.len() == 1 {
+            leaves.push(cell);
+            return;
+        }
+        for &(u0, u1) in &us {
+            for &(v0, v1) in &vs {
+                self.refine(Cell { u0, u1, v0, v1 }, leaves);
+            }
+        }
+    }
+
+    /// Whether all the grid points land on the same point of the surface.
+    fn collapses(&self, mut points: impl Iterator<Item = (u32, u32)>) -> bool {
+        let Some((u, v)) = points.next() else {
+            return false;
+        };
+        let first = self.point(u as f64, v as f64);
+        points.all(|(u, v)| {
+            self.point(u as f64, v as f64).distance(first) <= self.tolerance * COLLAPSED_LINE
+        })
+    }
+
+    fn normal(&self, u: f64, v: f64) -> Vector3 {
+        let (pu, pv) = self.param(u, v);
+        let n = self.surface.uder(pu, pv).cross(self.surface.vder(pu, pv));
+        if n.magnitude() > DEGENERATE_NORMAL {
+            return n.normalize();
+        }
+        // Singular point such as a pole: step slightly toward the middle of
+        // the domain, where the parameterization is regular.
+        let half = GRID as f64 / 2.0;
+        let (pu, pv) = self.param(u + (half - u) * 1e-6, v + (half - v) * 1e-6);
+        let n = self.surface.uder(pu, pv).cross(self.surface.vder(pu, pv));
+        if n.magnitude() > 0.0 {
+            n.normalize()
+        } else {
+            Vector3::unit_z()
+        }
+    }
+
+    /// Two triangles per leaf, or a fan around the leaf's centre when finer
+    /// neighbours put extra vertices on its edges. Triangles with two
+    /// corners on the same collapsed line are left out.
+    fn triangulate(&self, leaves: &[Cell]) -> RenderMesh {
+        // Leaf corners along each grid row (fixed v) and column (fixed u).
+        let mut rows: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+        let mut cols: BTreeMap<u32, BTreeSet<u32>> = BTreeMap::new();
+        for c in leaves {
+            for (u, v) in [(c.u0, c.v0), (c.u1, c.v0), (c.u1, c.v1), (c.u0, c.v1)] {
+                rows.entry(v).or_default().insert(u);
+                cols.entry(u).or_default().insert(v);
+            }
+        }
+        // Every point of a collapsed row or column shares the vertex at the
+        // line's first point.
+        let collapsed_rows: HashMap<u32, u32> = rows
+            .iter()
+            .filter(|(v, us)| self.collapses(us.iter().map(|&u| (u, **v))))
+            .map(|(&v, us)| (v, us.first().copied().unwrap_or(0)))
+            .collect();
+        let collapsed_cols: HashMap<u32, u32> = cols
+            .iter()
+            .filter(|(u, vs)| self.collapses(vs.iter().map(|&v| (**u, v))))
+            .map(|(&u, vs)| (u, vs.first().copied().unwrap_or(0)))
+            .collect();
+        let shared = |u: u32, v: u32| {
+            if let Some(&v0) = collapsed_cols.get(&u) {
+                (u, v0)
+            } else if let Some(&u0) = collapsed_rows.get(&v) {
+                (u0, v)
+            } else {
+                (u, v)
+            }
+        };
+
+        let mut mesh = RenderMesh {
+            vertices: Vec::new(),
+            normals: Vec::new(),
+            indices: Vec::new(),
+            face_ranges: Vec::new(),
+        };
+        let mut grid_vertices: HashMap<(u32, u32), u32> = HashMap::new();
+        let push_vertex = |mesh: &mut RenderMesh, u: f64, v: f64| {
+            let (p, n) = (self.point(u, v), self.normal(u, v));
+            mesh.vertices.extend([p.x as f32, p.y as f32, p.z as f32]);
+            mesh.normals.extend([n.x as f32, n.y as f32, n.z as f32]);
+            (mesh.vertices.len() / 3 - 1) as u32
+        };
+        let push_triangle = |mesh: &mut RenderMesh, [a, b, c]: [u32; 3]| {
+            if a != b && b != c && c != a {
+                mesh.indices.extend([a, b, c]);
+            }
+        };
+
+        for c in leaves {
+            // Counter-clockwise around the cell, including T-junctions.
+            let mut ring = vec![(c.u0, c.v0)];
+            ring.extend(rows[&c.v0].range(c.u0 + 1..c.u1).map(|&u| (u, c.v0)));
+            ring.push((c.u1, c.v0));
+            ring.extend(cols[&c.u1].range(c.v0 + 1..c.v1).map(|&v| (c.u1, v)));
+            ring.push((c.u1, c.v1));
+            ring.extend(rows[&c.v1].range(c.u0 + 1..c.u1).rev().map(|&u| (u, c.v1)));
+            ring.push((c.u0, c.v1));
+            ring.extend(cols[&c.u0].range(c.v0 + 1..c.v1).rev().map(|&v| (c.u0, v)));
+
+            let ring: Vec<u32> = ring
+                .into_iter()
+                .map(|(u, v)| {
+                    let (u, v) = shared(u, v);
+                    *grid_vertices
+                        .entry((u, v))
+                        .or_insert_with(|| push_vertex(&mut mesh, u as f64, v as f64))
+                })
+                .collect();
+            if ring.len() == 4 {
+                push_triangle(&mut mesh, [ring[0], ring[1], ring[2]]);
+                push_triangle(&mut mesh, [ring[0], ring[2], ring[3]]);
+            } else {
+                let centre = push_vertex(
+                    &mut mesh,
+                    (c.u0 + c.u1) as f64 / 2.0,
+                    (c.v0 + c.v1) as f64 / 2.0,
+                );
+                let next = ring.iter().cycle().skip(1);
+                for (&a, &b) in ring.iter().zip(next) {
+                    push_triangle(&mut mesh, [centre, a, b]);
+                }
+            }
+        }
+        mesh
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::{PI, TAU};
+    use truck_modeling::Sphere;
+
+    #[test]
+    fn test_adaptive_sphere_is_within_tolerance_and_sparser_than_a_grid() {
+        let (radius, tolerance) = (6.0, 0.05);
+        let sphere = Sphere::new(Point3::origin(), radius);
+        let mesh = tessellate_surface_adaptive(&sphere, (0.0, PI), (0.0, TAU), tolerance).unwrap();
+
+        let uniform_triangles = 48 * 36 * 2;
+        let triangles = mesh.indices.len() / 3;
+        assert!(
+            triangles < uniform_triangles * 3 / 4,
+            "{} triangles vs {} on a uniform grid",
+            triangles,
+            uniform_triangles
+        );
+
+        // On a sphere the surface point nearest an edge midpoint is straight
+        // out from the centre, so the deviation is radius - |midpoint|.
+        let position = |i: u32| {
+            let v = &mesh.vertices[i as usize * 3..i as usize * 3 + 3];
+            Vector3::new(v[0] as f64, v[1] as f64, v[2] as f64)
+        };
+        let worst = mesh
+            .indices
+            .chunks_exact(3)
+            .flat_map(|t| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])])
+            .map(|(a, b)| radius - ((position(a) + position(b)) / 2.0).magnitude())
+            .fold(0.0, f64::max);
+        assert!(worst < tolerance, "worst chord deviation {}", worst);
+
+        // Normals are radial, including at the poles.
+        for (p, n) in mesh.vertices.chunks_exact(3).zip(mesh.normals.chunks_exact(3)) {
+            let (p, n) = (
+                Vector3::new(p[0] as f64, p[1] as f64, p[2] as f64),
+                Vector3::new(n[0] as f64, n[1] as f64, n[2] as f64),
+            );
+            assert!((n.magnitude() - 1.0).abs() < 1e-5, "{:?}", n);
+            assert!((n.dot(p).abs() / radius - 1.0).abs() < 1e-3, "{:?} at {:?}", n, p);
+        }
+    }
+
+    #[test]
+    fn test_sphere_poles_become_single_vertices() {
+        let radius = 2.0;
+        let sphere = Sphere::new(Point3::origin(), radius);
+        let mut mesh = tessellate_surface_adaptive(&sphere, (0.0, PI), (0.0, TAU), 0.02).unwrap();
+
+        let position = |i: u32| {
+            let v = &mesh.vertices[i as usize * 3..i as usize * 3 + 3];
+            Point3::new(v[0] as f64, v[1] as f64, v[2] as f64)
+        };
+        for t in mesh.indices.chunks_exact(3) {
+            let (a, b, c) = (position(t[0]), position(t[1]), position(t[2]));
+            let area = (b - a).cross(c - a).magnitude() / 2.0;
+            assert!(area > 1e-8, "degenerate triangle {:?}", t);
+        }
+        for u in [0.0, PI] {
+            let pole = sphere.subs(u, 0.0);
+            let copies = (0..mesh.vertices.len() as u32 / 3)
+                .filter(|&i| position(i).distance(pole) < 1e-5)
+                .count();
+            assert_eq!(copies, 1, "pole at u = {}", u);
+        }
+
+        // Only the seam at v = 0 and v = 2pi is left to weld.
+        crate::mesh::weld_vertices(&mut mesh, 1e-6);
+        assert!(crate::mesh::validate_mesh(&mesh).is_closed_manifold());
+    }
+
+    #[test]
+    fn test_plane_needs_no_refinement() {
+        let plane = truck_modeling::Plane::new(
+            Point3::origin(),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        );
+        let mesh = tessellate_surface_adaptive(&plane, (0.0, 10.0), (0.0, 5.0), 1e-3).unwrap();
+        let cells = (INITIAL_DIVISIONS * INITIAL_DIVISIONS) as usize;
+        assert_eq!(mesh.indices.len() / 3, 2 * cells);
+        assert_eq!(mesh.vertices.len() / 3, 25);
+    }
+
+    #[test]
+    fn test_rejects_unusable_tolerances() {
+        let sphere = Sphere::new(Point3::origin(), 1.0);
+        for tolerance in [0.0, -0.1, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                tessellate_surface_adaptive(&sphere, (0.0, PI), (0.0, TAU), tolerance),
+                Err(KernelError::TessellationFailed { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_tiny_tolerance_is_clamped_to_the_surface_size() {
+        let sphere = Sphere::new(Point3::origin(), 1.0);
+        let tessellate = |tolerance| {
+            tessellate_surface_adaptive(&sphere, (0.0, PI), (0.0, TAU), tolerance)
+                .unwrap()
+                .indices
+                .len()
+        };
+        // Both are clamped to the same floor, well short of the full grid.
+        assert_eq!(tessellate(1e-12), tessellate(1e-9));
+        assert!(tessellate(1e-12) / 3 < 100_000);
+    }
+}
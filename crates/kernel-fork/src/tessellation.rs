@@ -4,6 +4,7 @@
 //! that map triangle index ranges to logical faces for GPU picking.
 
 use crate::types::*;
+use std::collections::{HashMap, VecDeque};
 use truck_meshalgo::prelude::*;
 use truck_meshalgo::tessellation::MeshableShape;
 
@@ -103,6 +104,92 @@ pub fn tessellate_solid(
     })
 }
 
+/// Most meshes a [`TessellationCache`] keeps before evicting the least
+/// recently used.
+pub const TESSELLATION_CACHE_CAPACITY: usize = 64;
+
+/// Cache of tessellated meshes, keyed by solid handle and tolerance.
+///
+/// Stored solids are never mutated in place and handles are never reused
+/// (every operation stores a new solid under a fresh handle), so an edit
+/// always misses. Superseded solids are not dropped from the kernel, so
+/// the cache holds at most [`TESSELLATION_CACHE_CAPACITY`] meshes and
+/// evicts the least recently used beyond that.
+#[derive(Debug, Default)]
+pub struct TessellationCache {
+    meshes: HashMap<(u64, u64), RenderMesh>,
+    /// Keys of `meshes`, least recently used first.
+    recency: VecDeque<(u64, u64)>,
+    hits: u64,
+    misses: u64,
+}
+
+impl TessellationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Look up a cached mesh, counting the lookup as a hit or a miss.
+    pub fn get(&mut self, handle: u64, tolerance: f64) -> Option<RenderMesh> {
+        let key = (handle, tolerance.to_bits());
+        match self.meshes.get(&key) {
+            Some(mesh) => {
+                self.hits += 1;
+                self.touch(key);
+                Some(mesh.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store a freshly tessellated mesh, evicting the least recently used
+    /// one if the cache is full.
+    pub fn insert(&mut self, handle: u64, tolerance: f64, mesh: RenderMesh) {
+        let key = (handle, tolerance.to_bits());
+        if self.meshes.insert(key, mesh).is_some() {
+            self.touch(key);
+            return;
+        }
+        self.recency.push_back(key);
+        if self.recency.len() > TESSELLATION_CACHE_CAPACITY {
+            if let Some(oldest) = self.recency.pop_front() {
+                self.meshes.remove(&oldest);
+            }
+        }
+    }
+
+    /// Number of meshes currently cached.
+    pub fn len(&self) -> usize {
+        self.meshes.len()
+    }
+
+    /// Whether no meshes are cached.
+    pub fn is_empty(&self) -> bool {
+        self.meshes.is_empty()
+    }
+
+    /// Move `key` to the most recently used end.
+    fn touch(&mut self, key: (u64, u64)) {
+        if let Some(i) = self.recency.iter().position(|k| *k == key) {
+            self.recency.remove(i);
+            self.recency.push_back(key);
+        }
+    }
+
+    /// Number of lookups answered from the cache.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that required a fresh tessellation.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
 /// Extract edge polylines from a solid for rendering edge overlays.
 ///
 /// Each edge curve is sampled into a polyline at the given tolerance.
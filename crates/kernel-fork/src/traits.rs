@@ -123,4 +123,65 @@ pub trait KernelIntrospect {
         solid: &KernelSolidHandle,
         kind: TopoKind,
     ) -> Vec<(KernelId, TopoSignature)>;
+
+    /// Face-adjacency graph of a solid as pairs of indices into
+    /// [`list_faces`](Self::list_faces), one pair per face-sharing edge.
+    ///
+    /// Pairs are `(lower, higher)`, sorted and deduplicated, so two faces
+    /// meeting along several edges appear once. Self-adjacent seam edges are
+    /// skipped.
+    fn face_adjacency(&self, solid: &KernelSolidHandle) -> Vec<(usize, usize)> {
+        let index: HashMap<KernelId, usize> = self
+            .list_faces(solid)
+            .into_iter()
+            .enumerate()
+            .map(|(i, f)| (f, i))
+            .collect();
+        let mut pairs = Vec::new();
+        for edge in self.list_edges(solid) {
+            let faces: Vec<usize> = self
+                .edge_faces(edge)
+                .iter()
+                .filter_map(|f| index.get(f).copied())
+                .collect();
+            for (i, &a) in faces.iter().enumerate() {
+                for &b in &faces[i + 1..] {
+                    if a != b {
+                        pairs.push((a.min(b), a.max(b)));
+                    }
+                }
+            }
+        }
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Whether an edge is a convex or concave crease, a tangent join, or a
+    /// seam within one face. `None` if the edge is unknown or is not shared
+    /// by exactly two face sides.
+    ///
+    /// The default takes face normals from [`compute_signature`] and steps
+    /// from the edge midpoint towards the face centroid, which is exact for
+    /// convex planar faces. Kernels with exact geometry should override it.
+    ///
+    /// [`compute_signature`]: Self::compute_signature
+    fn edge_convexity(&self, edge: KernelId) -> Option<Convexity> {
+        let faces = self.edge_faces(edge);
+        let (a, b) = match faces[..] {
+            [a, b] if a == b => return Some(Convexity::Smooth),
+            [a, b] => (a, b),
+            _ => return None,
+        };
+        let sig_a = self.compute_signature(a, TopoKind::Face);
+        let sig_b = self.compute_signature(b, TopoKind::Face);
+        let midpoint = self.compute_signature(edge, TopoKind::Edge).centroid?;
+        let centroid_a = sig_a.centroid?;
+        let into_a = [
+            centroid_a[0] - midpoint[0],
+            centroid_a[1] - midpoint[1],
+            centroid_a[2] - midpoint[2],
+        ];
+        Some(Convexity::classify(sig_a.normal?, sig_b.normal?, into_a))
+    }
 }
@@ -0,0 +1,240 @@
+//! Affine transforms of 3D points, directions and meshes.
+
+use crate::types::RenderMesh;
+
+/// An affine transform stored as a row-major 3×4 matrix: a linear part in
+/// the first three columns and a translation in the last.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub rows: [[f64; 4]; 3],
+}
+
+impl Transform {
+    /// The transform that leaves every point in place.
+    pub fn identity() -> Self {
+        Self::from_linear([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+    }
+
+    /// Move every point by `offset`.
+    pub fn translation(offset: [f64; 3]) -> Self {
+        let mut t = Self::identity();
+        for (row, d) in t.rows.iter_mut().zip(offset) {
+            row[3] = d;
+        }
+        t
+    }
+
+    /// Rotate by `angle` radians about `axis` through the origin,
+    /// counter-clockwise looking down the axis. Returns `None` for a zero axis.
+    pub fn rotation(axis: [f64; 3], angle: f64) -> Option<Self> {
+        let len = (axis[0] * axis[0] + axis[1] * axis[1] + axis[2] * axis[2]).sqrt();
+        if len == 0.0 {
+            return None;
+        }
+        let [x, y, z] = axis.map(|c| c / len);
+        let (s, c) = angle.sin_cos();
+        let t = 1.0 - c;
+        // Rodrigues' rotation formula.
+        Some(Self::from_linear([
+            [t * x * x + c, t * x * y - s * z, t * x * z + s * y],
+            [t * x * y + s * z, t * y * y + c, t * y * z - s * x],
+            [t * x * z - s * y, t * y * z + s * x, t * z * z + c],
+        ]))
+    }
+
+    /// Scale independently along each axis about the origin.
+    pub fn scaling(factors: [f64; 3]) -> Self {
+        Self::from_linear([
+            [factors[0], 0.0, 0.0],
+            [0.0, factors[1], 0.0],
+            [0.0, 0.0, factors[2]],
+        ])
+    }
+
+    fn from_linear(m: [[f64; 3]; 3]) -> Self {
+        Self {
+            rows: m.map(|r| [r[0], r[1], r[2], 0.0]),
+        }
+    }
+
+    /// The transform that applies `other` first and then `self`.
+    pub fn compose(&self, other: &Transform) -> Transform {
+        let mut rows = [[0.0; 4]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                *cell = (0..3).map(|k| self.rows[i][k] * other.rows[k][j]).sum();
+            }
+            row[3] += self.rows[i][3];
+        }
+        Transform { rows }
+    }
+
+    /// Determinant of the linear part; negative for mirroring transforms.
+    pub fn determinant(&self) -> f64 {
+        let m = &self.rows;
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    }
+
+    /// The transform that undoes this one, or `None` if it collapses space
+    /// (zero determinant).
+    pub fn invert(&self) -> Option<Transform> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        // The inverse is the adjugate (transposed cofactors) over the
+        // determinant; the translation is undone after it.
+        let cof = self.linear_cofactors();
+        let mut rows = [[0.0; 4]; 3];
+        for (i, row) in rows.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().take(3).enumerate() {
+                *cell = cof[j][i] / det;
+            }
+            row[3] = -(0..3).map(|k| row[k] * self.rows[k][3]).sum::<f64>();
+        }
+        Some(Transform { rows })
+    }
+
+    /// Cofactor matrix of the linear part.
+    fn linear_cofactors(&self) -> [[f64; 3]; 3] {
+        let m = &self.rows;
+        let mut cof = [[0.0; 3]; 3];
+        for (i, row) in cof.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+                let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+                *cell = m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+            }
+        }
+        cof
+    }
+
+    /// Apply the full transform to a position.
+    pub fn transform_point(&self, p: [f64; 3]) -> [f64; 3] {
+        self.rows
+            .map(|r| r[0] * p[0] + r[1] * p[1] + r[2] * p[2] + r[3])
+    }
+
+    /// Apply only the linear part, for directions and offsets.
+    pub fn transform_vector(&self, v: [f64; 3]) -> [f64; 3] {
+        self.rows.map(|r| r[0] * v[0] + r[1] * v[1] + r[2] * v[2])
+    }
+
+    /// Map a surface normal with the inverse transpose of the linear part, so
+    /// it stays perpendicular to transformed tangents. The result is unit
+    /// length (zero if `n` is zero).
+    ///
+    /// The cofactor matrix is the inverse transpose scaled by the
+    /// determinant, so its sign is applied to keep normals pointing the same
+    /// side of the surface under mirroring.
+    pub fn transform_normal(&self, n: [f64; 3]) -> [f64; 3] {
+        let cof = self.linear_cofactors();
+        let sign = self.determinant().signum();
+        let m = cof.map(|r| sign * (r[0] * n[0] + r[1] * n[1] + r[2] * n[2]));
+        let len = (m[0] * m[0] + m[1] * m[1] + m[2] * m[2]).sqrt();
+        if len > 0.0 {
+            m.map(|c| c / len)
+        } else {
+            [0.0; 3]
+        }
+    }
+
+    /// Transform a mesh's vertices and normals in place. Mirroring
+    /// transforms also reverse the triangle winding so faces keep pointing
+    /// outward.
+    pub fn apply_to_mesh(&self, mesh: &mut RenderMesh) {
+        for v in mesh.vertices.chunks_exact_mut(3) {
+            let p = self.transform_point([v[0] as f64, v[1] as f64, v[2] as f64]);
+            for (c, x) in v.iter_mut().zip(p) {
+                *c = x as f32;
+            }
+        }
+        for n in mesh.normals.chunks_exact_mut(3) {
+            let m = self.transform_normal([n[0] as f64, n[1] as f64, n[2] as f64]);
+            for (c, x) in n.iter_mut().zip(m) {
+                *c = x as f32;
+            }
+        }
+        if self.determinant() < 0.0 {
+            for tri in mesh.indices.chunks_exact_mut(3) {
+                tri.swap(1, 2);
+            }
+        }
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f64::consts::PI;
+
+    fn assert_near(a: [f64; 3], b: [f64; 3], tol: f64) {
+        for k in 0..3 {
+            assert!((a[k] - b[k]).abs() < tol, "{:?} != {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_compose_then_invert_round_trips() {
+        let rotate = Transform::rotation([1.0, 2.0, -0.5], 0.7).unwrap();
+        let shift = Transform::translation([3.0, -4.0, 12.5]);
+        let both = shift.compose(&rotate);
+
+        let p = [0.25, -1.5, 7.0];
+        let moved = both.transform_point(p);
+        assert_near(
+            moved,
+            shift.transform_point(rotate.transform_point(p)),
+            1e-12,
+        );
+        let back = both.invert().unwrap().transform_point(moved);
+        assert_near(back, p, 1e-12);
+        assert_near(
+            both.compose(&both.invert().unwrap()).transform_point(p),
+            p,
+            1e-12,
+        );
+    }
+
+    #[test]
+    fn test_rotation_quarter_turn_about_z() {
+        let t = Transform::rotation([0.0, 0.0, 2.0], PI / 2.0).unwrap();
+        assert_near(t.transform_point([1.0, 0.0, 5.0]), [0.0, 1.0, 5.0], 1e-15);
+        assert!(Transform::rotation([0.0; 3], 1.0).is_none());
+    }
+
+    #[test]
+    fn test_normal_stays_perpendicular_under_non_uniform_scale() {
+        let t = Transform::scaling([1.0, 4.0, 1.0]);
+        // A 45° plane through the origin, tangent (1, 1, 0), normal (1, -1, 0).
+        let tangent = t.transform_vector([1.0, 1.0, 0.0]);
+        let normal = t.transform_normal([1.0, -1.0, 0.0]);
+        let dot: f64 = (0..3).map(|k| tangent[k] * normal[k]).sum();
+        assert!(dot.abs() < 1e-12);
+        assert!(normal[0] > 0.0);
+        assert!(Transform::scaling([1.0, 0.0, 1.0]).invert().is_none());
+    }
+
+    #[test]
+    fn test_mirroring_mesh_keeps_outward_winding() {
+        let mut mesh = RenderMesh {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 1, 2],
+            face_ranges: Vec::new(),
+        };
+        Transform::scaling([-1.0, 1.0, 1.0]).apply_to_mesh(&mut mesh);
+        assert_eq!(mesh.indices, vec![0, 2, 1]);
+        assert_eq!(&mesh.vertices[3..6], &[-1.0, 0.0, 0.0]);
+        // The winding normal of the flipped triangle still points along +z.
+        assert_eq!(&mesh.normals[0..3], &[0.0, 0.0, 1.0]);
+    }
+}
@@ -5,7 +5,13 @@ use crate::truck_kernel::TruckKernel;
 use crate::types::*;
 
 use truck_modeling::geometry::Surface;
-use truck_modeling::topology::{Edge, Face, Solid, Vertex};
+use truck_modeling::topology::{Edge, Face, Shell, Solid, Vertex};
+use truck_modeling::{
+    BoundedCurve, InnerSpace, ParametricCurve, ParametricSurface, SPHint2D, SearchParameter,
+};
+
+/// Newton iterations allowed when locating an edge point on a face surface.
+const SEARCH_TRIALS: usize = 100;
 
 /// KernelIntrospect implementation that delegates to TruckKernel's stored solids.
 pub struct TruckIntrospect<'a> {
@@ -58,6 +64,10 @@ impl KernelIntrospect for TruckIntrospect<'_> {
     ) -> Vec<(KernelId, TopoSignature)> {
         compute_all_signatures_impl(self, solid, kind)
     }
+
+    fn edge_convexity(&self, edge: KernelId) -> Option<Convexity> {
+        edge_convexity_impl(edge, |h| self.kernel.get_solid(h))
+    }
 }
 
 /// Direct KernelIntrospect implementation on TruckKernel.
@@ -102,6 +112,10 @@ impl KernelIntrospect for TruckKernel {
     ) -> Vec<(KernelId, TopoSignature)> {
         compute_all_signatures_impl(self, solid, kind)
     }
+
+    fn edge_convexity(&self, edge: KernelId) -> Option<Convexity> {
+        edge_convexity_impl(edge, |h| self.get_solid(h))
+    }
 }
 
 // ── Shared implementation functions ─────────────────────────────────────
@@ -380,6 +394,70 @@ where
     TopoSignature::empty()
 }
 
+fn edge_convexity_impl<'a, F>(edge: KernelId, get_solid: F) -> Option<Convexity>
+where
+    F: Fn(&KernelSolidHandle) -> Option<&'a Solid>,
+{
+    let handle_id = edge.0 / 10000;
+    let edge_offset = (edge.0 % 10000).checked_sub(1000)? as usize;
+    let truck_solid = get_solid(&KernelSolidHandle(handle_id))?;
+
+    for shell in truck_solid.boundaries().iter() {
+        let mut seen = std::collections::HashSet::new();
+        let Some(target) = shell
+            .edge_iter()
+            .filter(|e| seen.insert(e.id()))
+            .nth(edge_offset)
+        else {
+            continue;
+        };
+        return shell_edge_convexity(shell, &target);
+    }
+    None
+}
+
+/// Convexity of `target` where the faces of `shell` meet along it, or
+/// `None` unless exactly two face sides use it.
+pub(crate) fn shell_edge_convexity(shell: &Shell, target: &Edge) -> Option<Convexity> {
+    // Every use of the edge in a face boundary, as (face index, normal,
+    // direction into the face).
+    let mut sides = Vec::new();
+    for (fi, face) in shell.face_iter().enumerate() {
+        for wire in face.boundaries() {
+            for e in wire.edge_iter().filter(|e| e.id() == target.id()) {
+                let (normal, into_face) = edge_side(face, e)?;
+                sides.push((fi, normal, into_face));
+            }
+        }
+    }
+    match sides[..] {
+        [(a, ..), (b, ..)] if a == b => Some(Convexity::Smooth),
+        [(_, normal_a, into_a), (_, normal_b, _)] => {
+            Some(Convexity::classify(normal_a, normal_b, into_a))
+        }
+        _ => None,
+    }
+}
+
+/// Outward normal of `face` at the midpoint of `edge`, and the direction
+/// from the edge into the face. Boundary wires run with the face on their
+/// left, so that direction is `normal x tangent`.
+fn edge_side(face: &Face, edge: &Edge) -> Option<([f64; 3], [f64; 3])> {
+    let curve = edge.oriented_curve();
+    let (t0, t1) = curve.range_tuple();
+    let t = (t0 + t1) / 2.0;
+    let tangent = curve.der(t);
+
+    let surface = face.oriented_surface();
+    let (u, v) = surface.search_parameter(curve.subs(t), SPHint2D::None, SEARCH_TRIALS)?;
+    let normal = surface.uder(u, v).cross(surface.vder(u, v)).normalize();
+    let into_face = normal.cross(tangent);
+    Some((
+        [normal[0], normal[1], normal[2]],
+        [into_face[0], into_face[1], into_face[2]],
+    ))
+}
+
 fn compute_all_signatures_impl(
     introspect: &dyn KernelIntrospect,
     solid: &KernelSolidHandle,
@@ -562,6 +640,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_face_adjacency_box() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+
+        let pairs = kernel.face_adjacency(&handle);
+        assert_eq!(pairs.len(), 12, "each box edge joins two faces");
+        assert!(pairs.iter().all(|(a, b)| a < b));
+        for face in 0..6 {
+            let degree = pairs
+                .iter()
+                .filter(|(a, b)| *a == face || *b == face)
+                .count();
+            assert_eq!(degree, 4);
+        }
+    }
+
     /// TruckKernel directly implements KernelIntrospect (no TruckIntrospect wrapper needed).
     /// This means TruckKernel satisfies the KernelBundle blanket impl.
     #[test]
@@ -585,4 +680,53 @@ mod tests {
             assert_eq!(sig.surface_type.as_deref(), Some("planar"));
         }
     }
+
+    #[test]
+    fn test_edge_convexity_box_all_convex() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(1.0, 2.0, 3.0));
+
+        let edges = kernel.list_edges(&handle);
+        assert_eq!(edges.len(), 12);
+        for edge in edges {
+            assert_eq!(kernel.edge_convexity(edge), Some(Convexity::Convex));
+        }
+    }
+
+    /// A small block standing on a larger one: the four edges where the
+    /// block meets the top face form an inside corner.
+    #[test]
+    fn test_edge_convexity_union_notch_is_concave() {
+        use truck_modeling::{builder, Point3, Vector3};
+
+        let make_block = |origin: [f64; 3], size: [f64; 3]| -> Solid {
+            let v = builder::vertex(Point3::new(origin[0], origin[1], origin[2]));
+            let e = builder::tsweep(&v, Vector3::new(size[0], 0.0, 0.0));
+            let f = builder::tsweep(&e, Vector3::new(0.0, size[1], 0.0));
+            builder::tsweep(&f, Vector3::new(0.0, 0.0, size[2]))
+        };
+        let base = make_block([0.0, 0.0, 0.0], [2.0, 2.0, 2.0]);
+        let post = make_block([0.5, 0.5, 1.0], [1.0, 1.0, 2.0]);
+        let union = truck_shapeops::or(&base, &post, 0.05).expect("union should succeed");
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(union);
+
+        let mut concave = Vec::new();
+        for edge in kernel.list_edges(&handle) {
+            match kernel.edge_convexity(edge) {
+                Some(Convexity::Concave) => concave.push(edge),
+                Some(_) => {}
+                None => panic!("edge {:?} should classify", edge),
+            }
+        }
+        assert_eq!(concave.len(), 4, "the post's foot has four inside edges");
+        for edge in concave {
+            let mid = kernel
+                .compute_signature(edge, TopoKind::Edge)
+                .centroid
+                .unwrap();
+            assert!((mid[2] - 2.0).abs() < 1e-6, "concave edge at z={}", mid[2]);
+        }
+    }
 }
@@ -1,14 +1,24 @@
 //! TruckKernel — real geometry kernel wrapping truck's API.
 
+use crate::coplanar;
+use crate::curvature::{self, Curvatures};
+use crate::curve_sampling;
+use crate::face_uv;
+use crate::loft;
+use crate::mesh::{self, MeshValidation, RepairReport};
+use crate::planar_shell;
+use crate::surface_area;
 use crate::tessellation;
+use crate::transform::Transform;
+use crate::truck_introspect;
 use crate::traits::Kernel;
 use crate::types::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 // Import truck types selectively to avoid shadowing std::result::Result
 use truck_modeling::builder;
-use truck_modeling::topology::{Edge, Face, Solid, Wire};
-use truck_modeling::{InnerSpace, Point3, Rad, Vector3};
+use truck_modeling::topology::{Edge, Face, Shell, Solid, Wire};
+use truck_modeling::{EuclideanSpace, InnerSpace, Matrix4, Point3, Rad, Vector3, Vector4};
 
 /// Real geometry kernel backed by the truck BREP library.
 pub struct TruckKernel {
@@ -17,6 +27,14 @@ pub struct TruckKernel {
     solids: HashMap<u64, Solid>,
     /// Standalone faces created by make_faces_from_profiles, awaiting extrude.
     standalone_faces: HashMap<u64, Face>,
+    /// Meshes from previous `tessellate` calls.
+    tess_cache: tessellation::TessellationCache,
+    /// Non-parametric bodies imported as triangle meshes (e.g. reference STLs).
+    imported_meshes: HashMap<u64, RenderMesh>,
+    /// Face triangulations kept by `face_uv_at` for the last body queried.
+    trim_meshes: (u64, face_uv::TrimMeshes),
+    /// Imported bodies tagged as open sheets; every other body is a solid.
+    sheets: HashSet<u64>,
 }
 
 impl TruckKernel {
@@ -26,6 +44,10 @@ impl TruckKernel {
             next_id: 1,
             solids: HashMap::new(),
             standalone_faces: HashMap::new(),
+            tess_cache: tessellation::TessellationCache::new(),
+            imported_meshes: HashMap::new(),
+            trim_meshes: (0, face_uv::TrimMeshes::new()),
+            sheets: HashSet::new(),
         }
     }
 
@@ -51,6 +73,603 @@ impl TruckKernel {
         self.solids.get(&handle.id())
     }
 
+    /// Store a triangle mesh as a non-parametric imported body.
+    ///
+    /// Imported bodies can be tessellated (returning the mesh unchanged) and
+    /// measured, but have no B-Rep topology, so modeling operations on them
+    /// fail with `NotSupported`. They are imported as solids; call
+    /// [`set_solidity`](Self::set_solidity) for surface models.
+    pub fn import_mesh(&mut self, mesh: RenderMesh) -> KernelSolidHandle {
+        let handle = self.alloc_handle();
+        self.imported_meshes.insert(handle.id(), mesh);
+        handle
+    }
+
+    /// Store a mesh derived from the imported body `source`, keeping its
+    /// solidity.
+    fn import_derived(
+        &mut self,
+        source: &KernelSolidHandle,
+        mesh: RenderMesh,
+    ) -> KernelSolidHandle {
+        let handle = self.import_mesh(mesh);
+        if self.sheets.contains(&source.id()) {
+            self.sheets.insert(handle.id());
+        }
+        handle
+    }
+
+    /// Whether a handle refers to an imported mesh rather than a B-Rep solid.
+    pub fn is_imported(&self, handle: &KernelSolidHandle) -> bool {
+        self.imported_meshes.contains_key(&handle.id())
+    }
+
+    /// Whether a body encloses a volume or is an open sheet.
+    ///
+    /// This is what the body is meant to be, not what its mesh looks like:
+    /// an imported solid with holes in it is still a solid, and
+    /// [`mesh::validate_mesh`] reports its border edges as defects.
+    pub fn solidity(&self, handle: &KernelSolidHandle) -> Solidity {
+        if self.sheets.contains(&handle.id()) {
+            Solidity::Sheet
+        } else {
+            Solidity::Solid
+        }
+    }
+
+    /// Tag an imported body as a solid or an open sheet.
+    ///
+    /// truck only builds solids from closed shells, so B-Rep bodies can't be
+    /// sheets.
+    pub fn set_solidity(
+        &mut self,
+        handle: &KernelSolidHandle,
+        solidity: Solidity,
+    ) -> Result<(), KernelError> {
+        if !self.is_imported(handle) {
+            if self.get_solid(handle).is_none() {
+                return Err(KernelError::EntityNotFound {
+                    id: KernelId(handle.id()),
+                });
+            }
+            if solidity == Solidity::Sheet {
+                return Err(KernelError::NotSupported {
+                    operation: "sheet B-Rep body".to_string(),
+                });
+            }
+            return Ok(());
+        }
+        match solidity {
+            Solidity::Solid => self.sheets.remove(&handle.id()),
+            Solidity::Sheet => self.sheets.insert(handle.id()),
+        };
+        Ok(())
+    }
+
+    /// Fix duplicate faces and winding of an imported mesh, see
+    /// [`mesh::repair_and_verify`], and report how it validates afterwards.
+    ///
+    /// The repaired mesh is stored as a new imported body and its handle
+    /// returned; the original is left as it was, like every other operation.
+    /// B-Rep solids are built consistently oriented by truck, so they are
+    /// only validated, from their tessellation at `tolerance`, and come back
+    /// under their own handle.
+    pub fn repair_and_verify(
+        &mut self,
+        handle: &KernelSolidHandle,
+        tolerance: f64,
+    ) -> Result<(KernelSolidHandle, RepairReport, MeshValidation), KernelError> {
+        match self.imported_meshes.get(&handle.id()) {
+            Some(imported) => {
+                let mut repaired = imported.clone();
+                let (report, validation) = mesh::repair_and_verify(&mut repaired);
+                Ok((self.import_derived(handle, repaired), report, validation))
+            }
+            None => {
+                let render_mesh = self.tessellate(handle, tolerance)?;
+                let validation = mesh::validate_mesh(&render_mesh);
+                Ok((handle.clone(), RepairReport::default(), validation))
+            }
+        }
+    }
+
+    /// Axis-aligned bounding box of a body as `(min, max)`.
+    ///
+    /// B-Rep solids are measured from their tessellation at `tolerance`.
+    pub fn bounding_box(
+        &mut self,
+        handle: &KernelSolidHandle,
+        tolerance: f64,
+    ) -> Result<([f64; 3], [f64; 3]), KernelError> {
+        let render_mesh = self.tessellate(handle, tolerance)?;
+        mesh::bounding_box(&render_mesh).ok_or_else(|| KernelError::Other {
+            message: "cannot measure an empty body".to_string(),
+        })
+    }
+
+    /// Section outline of a body cut by the plane through `point` with
+    /// `normal`, see [`mesh::section`].
+    ///
+    /// B-Rep solids are cut through their tessellation at `tolerance`.
+    pub fn section(
+        &mut self,
+        handle: &KernelSolidHandle,
+        tolerance: f64,
+        point: [f64; 3],
+        normal: [f64; 3],
+    ) -> Result<Vec<Vec<[f64; 3]>>, KernelError> {
+        let render_mesh = self.tessellate(handle, tolerance)?;
+        Ok(mesh::section(&render_mesh, point, normal))
+    }
+
+    /// Total face area of a body.
+    ///
+    /// Measured on the exact geometry, see [`surface_area::solid_surface_area`].
+    /// Imported meshes have no exact geometry and are measured by their
+    /// triangles, see [`mesh::surface_area`].
+    pub fn solid_surface_area(&self, handle: &KernelSolidHandle) -> Result<f64, KernelError> {
+        if let Some(imported) = self.imported_meshes.get(&handle.id()) {
+            return Ok(mesh::surface_area(imported));
+        }
+        let solid = self.get_solid(handle).ok_or(KernelError::EntityNotFound {
+            id: KernelId(handle.id()),
+        })?;
+        Ok(surface_area::solid_surface_area(solid))
+    }
+
+    /// Surface (u, v) of `point` on the `face_index`-th face of a body, see
+    /// [`face_uv::face_uv_at`]. `None` when the point is not on that face.
+    ///
+    /// The faces of the body last queried stay triangulated, so probing
+    /// many points on one body meshes each face once.
+    pub fn face_uv_at(
+        &mut self,
+        handle: &KernelSolidHandle,
+        face_index: usize,
+        point: [f64; 3],
+    ) -> Result<Option<(f64, f64)>, KernelError> {
+        self.reject_imported(handle, "face parameters")?;
+        self.use_trim_meshes_of(handle);
+        let solid = self
+            .solids
+            .get(&handle.id())
+            .ok_or(KernelError::EntityNotFound {
+                id: KernelId(handle.id()),
+            })?;
+        Ok(face_uv::face_uv_at(
+            solid,
+            face_index,
+            point,
+            &mut self.trim_meshes.1,
+        ))
+    }
+
+    /// Switch the kept face triangulations to `handle`'s body. Solids are
+    /// never changed in place, so they stay valid until another body is
+    /// queried.
+    fn use_trim_meshes_of(&mut self, handle: &KernelSolidHandle) {
+        if self.trim_meshes.0 != handle.id() {
+            self.trim_meshes = (handle.id(), face_uv::TrimMeshes::new());
+        }
+    }
+
+    /// Principal, Gaussian and mean curvature of the `face_index`-th face of
+    /// a body at `point`, see [`curvature::face_curvatures_at`]. `None` when
+    /// the point is not on that face.
+    pub fn face_curvatures_at(
+        &mut self,
+        handle: &KernelSolidHandle,
+        face_index: usize,
+        point: [f64; 3],
+    ) -> Result<Option<Curvatures>, KernelError> {
+        self.reject_imported(handle, "face curvature")?;
+        self.use_trim_meshes_of(handle);
+        let solid = self
+            .solids
+            .get(&handle.id())
+            .ok_or(KernelError::EntityNotFound {
+                id: KernelId(handle.id()),
+            })?;
+        Ok(curvature::face_curvatures_at(
+            solid,
+            face_index,
+            point,
+            &mut self.trim_meshes.1,
+        ))
+    }
+
+    /// Faces of a body whose normals point into the material.
+    ///
+    /// Checked on the tessellation at `tolerance`; see [`mesh::misoriented_faces`].
+    /// A correct solid, including cavities left by a subtract, reports none.
+    pub fn validate_solid_orientation(
+        &mut self,
+        handle: &KernelSolidHandle,
+        tolerance: f64,
+    ) -> Result<Vec<KernelId>, KernelError> {
+        let render_mesh = self.tessellate(handle, tolerance)?;
+        Ok(mesh::misoriented_faces(&render_mesh))
+    }
+
+    /// Cut a solid with a plane, keeping the part on the negative side of `normal`.
+    ///
+    /// The positive half-space is stood in for by a slab reaching well past the
+    /// solid's bounds, which is subtracted so the boolean caps the cut face.
+    /// Curved solids are subject to truck's boolean limitations (see M7 in
+    /// the kernel-fork plan) and may fail with `BooleanFailed`.
+    pub fn cut_with_plane(
+        &mut self,
+        handle: &KernelSolidHandle,
+        origin: [f64; 3],
+        normal: [f64; 3],
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.reject_imported(handle, "cut_with_plane")?;
+        let n = Vector3::from(normal);
+        if n.magnitude2() < 1e-24 {
+            return Err(KernelError::Other {
+                message: "cut plane normal must be non-zero".to_string(),
+            });
+        }
+        let n = n.normalize();
+        let solid = self
+            .get_solid(handle)
+            .ok_or(KernelError::EntityNotFound {
+                id: KernelId(handle.id()),
+            })?
+            .clone();
+
+        let (min, max) = self.bounding_box(handle, 0.1)?;
+        let (min, max) = (Point3::from(min), Point3::from(max));
+        let origin = Point3::from(origin);
+        let center = min.midpoint(max);
+        let reach = 2.0 * ((center - origin).magnitude() + (max - min).magnitude()) + 1.0;
+
+        // (u, v, n) right-handed so the swept slab is outward-oriented.
+        let helper = if n.x.abs() < 0.9 {
+            Vector3::unit_x()
+        } else {
+            Vector3::unit_y()
+        };
+        let u = helper.cross(n).normalize();
+        let v = n.cross(u);
+        let corner = origin - u * reach - v * reach;
+        let vertex = builder::vertex(corner);
+        let edge = builder::tsweep(&vertex, u * (2.0 * reach));
+        let face = builder::tsweep(&edge, v * (2.0 * reach));
+        let mut slab: Solid = builder::tsweep(&face, n * reach);
+        slab.not();
+
+        let result =
+            truck_shapeops::and(&solid, &slab, 0.05).ok_or_else(|| KernelError::BooleanFailed {
+                reason: "plane cut: truck and() returned None".to_string(),
+            })?;
+        Ok(self.store_solid(result))
+    }
+
+    /// Chamfer the corner of a body at `vertex`: each of the three edges
+    /// meeting there is cut back by `distance`, measured along the edge, and
+    /// the corner is replaced by a flat triangular face.
+    ///
+    /// The cut is a [`cut_with_plane`](Self::cut_with_plane) through the three
+    /// cut-back points and shares its boolean limitations. An infinite plane
+    /// only removes the corner alone when the corner is convex, so corners
+    /// with a concave or smooth edge, and corners where more or fewer than
+    /// three edges meet, fail with `NotSupported`.
+    pub fn chamfer_vertex(
+        &mut self,
+        handle: &KernelSolidHandle,
+        vertex: [f64; 3],
+        distance: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.reject_imported(handle, "chamfer_vertex")?;
+        if distance <= 0.0 {
+            return Err(KernelError::Other {
+                message: "chamfer distance must be positive".to_string(),
+            });
+        }
+        let solid = self.get_solid(handle).ok_or(KernelError::EntityNotFound {
+            id: KernelId(handle.id()),
+        })?;
+
+        let corner = Point3::from(vertex);
+        let at_corner = |p: Point3| (p - corner).magnitude() < 1e-6;
+        let mut seen = std::collections::HashSet::new();
+        let mut points = Vec::new();
+        let edges = solid
+            .boundaries()
+            .iter()
+            .flat_map(|shell| shell.edge_iter().map(move |edge| (shell, edge)));
+        for (shell, edge) in edges {
+            if !seen.insert(edge.id()) {
+                continue;
+            }
+            let from_front = at_corner(edge.front().point());
+            if !from_front && !at_corner(edge.back().point()) {
+                continue;
+            }
+            if truck_introspect::shell_edge_convexity(shell, &edge) != Some(Convexity::Convex) {
+                return Err(KernelError::NotSupported {
+                    operation: "chamfer_vertex at a non-convex corner".to_string(),
+                });
+            }
+            let curve = edge.oriented_curve();
+            let length = curve_sampling::curve_length(&curve);
+            if distance >= length {
+                return Err(KernelError::Other {
+                    message: format!(
+                        "chamfer distance {} is not shorter than edge length {}",
+                        distance, length
+                    ),
+                });
+            }
+            let s = if from_front { distance } else { length - distance };
+            points.push(curve_sampling::evaluate_arc_length(&curve, s, length));
+        }
+
+        let [a, b, c] = points[..] else {
+            return Err(KernelError::NotSupported {
+                operation: format!("chamfer_vertex at a corner of {} edges", points.len()),
+            });
+        };
+        // Point the normal at the corner so the cut removes it.
+        let mut normal = (b - a).cross(c - a);
+        if normal.dot(corner - a) < 0.0 {
+            normal = -normal;
+        }
+        self.cut_with_plane(handle, a.into(), normal.into())
+    }
+
+    /// Translate a body along `build_normal` so its lowest point in that
+    /// direction sits on the plane through the origin, e.g. `[0, 0, 1]` puts
+    /// the bottom of the part at z = 0 for printing.
+    ///
+    /// The lowest point is found on the tessellation at `tolerance`, whose
+    /// vertices lie on the exact surface. Imported meshes are moved too and
+    /// come back as a new imported body.
+    pub fn drop_to_plate(
+        &mut self,
+        handle: &KernelSolidHandle,
+        build_normal: [f64; 3],
+        tolerance: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let n = Vector3::from(build_normal);
+        if n.magnitude2() < 1e-24 {
+            return Err(KernelError::Other {
+                message: "build plate normal must be non-zero".to_string(),
+            });
+        }
+        let n = n.normalize();
+        let render_mesh = self.tessellate(handle, tolerance)?;
+        let lowest = render_mesh
+            .vertices
+            .chunks_exact(3)
+            .map(|v| n.dot(Vector3::new(v[0] as f64, v[1] as f64, v[2] as f64)))
+            .fold(f64::INFINITY, f64::min);
+        if !lowest.is_finite() {
+            return Err(KernelError::Other {
+                message: "cannot drop an empty body".to_string(),
+            });
+        }
+        let offset = n * -lowest;
+
+        if self.is_imported(handle) {
+            let mut moved = render_mesh;
+            Transform::translation(offset.into()).apply_to_mesh(&mut moved);
+            return Ok(self.import_derived(handle, moved));
+        }
+        let solid = self
+            .get_solid(handle)
+            .ok_or(KernelError::EntityNotFound {
+                id: KernelId(handle.id()),
+            })?;
+        let moved = builder::translated(solid, offset);
+        Ok(self.store_solid(moved))
+    }
+
+    /// A copy of a body moved by `transform`, as a new body.
+    ///
+    /// Vertices, edge curves and face surfaces are all mapped, so rotations
+    /// and translations keep the solid watertight. Non-uniform scaling is
+    /// allowed: truck's planes, B-splines and revolved surfaces all carry an
+    /// affine map exactly, so a stretched cylinder becomes an elliptic one
+    /// rather than an approximation. Transforms that flatten space are
+    /// rejected. Mirroring transforms
+    /// turn every face over so the result still points outward. Imported
+    /// meshes are moved vertex by vertex and come back as a new imported body.
+    pub fn transform_solid(
+        &mut self,
+        handle: &KernelSolidHandle,
+        transform: &Transform,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let det = transform.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return Err(KernelError::Other {
+                message: "transform collapses the body".to_string(),
+            });
+        }
+        if let Some(imported) = self.imported_meshes.get(&handle.id()) {
+            let mut moved = imported.clone();
+            transform.apply_to_mesh(&mut moved);
+            return Ok(self.import_derived(handle, moved));
+        }
+        let solid = self.get_solid(handle).ok_or(KernelError::EntityNotFound {
+            id: KernelId(handle.id()),
+        })?;
+        let [r0, r1, r2] = transform.rows;
+        let column = |j: usize| Vector4::new(r0[j], r1[j], r2[j], if j == 3 { 1.0 } else { 0.0 });
+        let matrix = Matrix4::from_cols(column(0), column(1), column(2), column(3));
+        let mut moved = builder::transformed(solid, matrix);
+        if det < 0.0 {
+            moved.not();
+        }
+        Ok(self.store_solid(moved))
+    }
+
+    /// Extrude a standalone face like [`Kernel::extrude_face`], with the side
+    /// walls leaning in by `draft_angle` radians (out when negative), see
+    /// [`loft::extrude_with_draft`]. The face must be bounded by straight
+    /// edges and have no holes.
+    pub fn extrude_face_with_draft(
+        &mut self,
+        face: KernelId,
+        direction: [f64; 3],
+        depth: f64,
+        draft_angle: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let truck_face = self
+            .standalone_faces
+            .get(&face.0)
+            .ok_or(KernelError::EntityNotFound { id: face })?;
+        let dir = Vector3::from(direction);
+        if dir.magnitude() < 1e-12 {
+            return Err(KernelError::Other {
+                message: "extrude direction has zero length".to_string(),
+            });
+        }
+        let boundaries = truck_face.boundaries();
+        let straight = boundaries[0]
+            .iter()
+            .all(|edge| matches!(edge.curve(), truck_modeling::geometry::Curve::Line(_)));
+        if boundaries.len() != 1 || !straight {
+            return Err(KernelError::NotSupported {
+                operation: "drafted extrude of a face with holes or curved edges".to_string(),
+            });
+        }
+        let profile: Vec<Point3> = boundaries[0].vertex_iter().map(|v| v.point()).collect();
+        let solid = loft::extrude_with_draft(&profile, dir.normalize() * depth, draft_angle)?;
+        self.standalone_faces.remove(&face.0);
+        Ok(self.store_solid(solid))
+    }
+
+    /// A solid lofted between two planar polygons, see [`loft::loft_profiles`].
+    pub fn loft_profiles(
+        &mut self,
+        bottom: &[[f64; 3]],
+        top: &[[f64; 3]],
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let points = |profile: &[[f64; 3]]| -> Vec<Point3> {
+            profile.iter().map(|&p| Point3::from(p)).collect()
+        };
+        let solid = loft::loft_profiles(&points(bottom), &points(top))?;
+        Ok(self.store_solid(solid))
+    }
+
+    /// A copy of the solid with adjacent coplanar faces merged, such as the
+    /// split walls a union of abutting boxes leaves behind, see
+    /// [`coplanar::merge_coplanar_faces`].
+    pub fn merge_coplanar_faces(
+        &mut self,
+        handle: &KernelSolidHandle,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.reject_imported(handle, "merge_coplanar_faces")?;
+        let solid = self.get_solid(handle).ok_or(KernelError::EntityNotFound {
+            id: KernelId(handle.id()),
+        })?;
+        let merged = coplanar::merge_coplanar_faces(solid)?;
+        Ok(self.store_solid(merged))
+    }
+
+    /// Boolean of `a` and `b` with each separate piece of the result stored
+    /// as its own solid, largest by volume first.
+    ///
+    /// A subtraction that cuts a solid in two gives two handles, where the
+    /// `Kernel` subtract and intersect keep only the largest piece.
+    pub fn boolean_lumps(
+        &mut self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+        op: BooleanOp,
+    ) -> Result<Vec<KernelSolidHandle>, KernelError> {
+        let lumps = split_lumps(self.boolean_solid(a, b, op)?)?;
+        Ok(lumps.into_iter().map(|lump| self.store_solid(lump)).collect())
+    }
+
+    /// Subtract or intersect, keeping only the largest piece of the result.
+    /// Unions keep every piece: joining two bodies that don't touch must not
+    /// throw one of them away.
+    fn boolean_largest(
+        &mut self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+        op: BooleanOp,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let largest = split_lumps(self.boolean_solid(a, b, op)?)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| KernelError::BooleanFailed {
+                reason: "boolean result is empty".to_string(),
+            })?;
+        Ok(self.store_solid(largest))
+    }
+
+    fn boolean_solid(
+        &self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+        op: BooleanOp,
+    ) -> Result<Solid, KernelError> {
+        let operation = match op {
+            BooleanOp::Union => "boolean_union",
+            BooleanOp::Subtract => "boolean_subtract",
+            BooleanOp::Intersect => "boolean_intersect",
+        };
+        self.reject_imported(a, operation)?;
+        self.reject_imported(b, operation)?;
+        let solid = |handle: &KernelSolidHandle| {
+            self.get_solid(handle)
+                .cloned()
+                .ok_or(KernelError::EntityNotFound {
+                    id: KernelId(handle.id()),
+                })
+        };
+        let (solid_a, mut solid_b) = (solid(a)?, solid(b)?);
+
+        let (result, reason) = match op {
+            BooleanOp::Union => (
+                truck_shapeops::or(&solid_a, &solid_b, 0.05),
+                "truck or() returned None",
+            ),
+            BooleanOp::Subtract => {
+                // Subtraction = A ∩ ¬B. not() mutates in place.
+                solid_b.not();
+                (
+                    truck_shapeops::and(&solid_a, &solid_b, 0.05),
+                    "truck and() returned None for subtraction",
+                )
+            }
+            BooleanOp::Intersect => (
+                truck_shapeops::and(&solid_a, &solid_b, 0.05),
+                "truck and() returned None",
+            ),
+        };
+        result.ok_or_else(|| KernelError::BooleanFailed {
+            reason: reason.to_string(),
+        })
+    }
+
+    fn reject_imported(
+        &self,
+        handle: &KernelSolidHandle,
+        operation: &str,
+    ) -> Result<(), KernelError> {
+        if self.is_imported(handle) {
+            return Err(KernelError::NotSupported {
+                operation: format!("{} on an imported mesh", operation),
+            });
+        }
+        Ok(())
+    }
+
+    /// Number of `tessellate` calls answered from the tessellation cache.
+    pub fn tessellation_cache_hits(&self) -> u64 {
+        self.tess_cache.hits()
+    }
+
+    /// Number of `tessellate` calls that had to tessellate afresh.
+    pub fn tessellation_cache_misses(&self) -> u64 {
+        self.tess_cache.misses()
+    }
+
     /// Export a solid to STEP AP203 format string.
     pub fn export_step(
         &self,
@@ -60,6 +679,8 @@ impl TruckKernel {
         use truck_stepio::out::*;
         use truck_topology::compress::CompressedSolid;
 
+        self.reject_imported(handle, "export_step")?;
+
         let solid = self
             .solids
             .get(&handle.id())
@@ -78,6 +699,57 @@ impl TruckKernel {
     }
 }
 
+/// Chord tolerance for the meshes that measure and nest result shells.
+const LUMP_TOLERANCE: f64 = 0.1;
+
+/// The separate pieces of `solid`, largest by volume first.
+///
+/// Every outward-facing boundary shell starts a piece, and every inward-
+/// facing one (a void) joins the smallest piece that encloses it.
+fn split_lumps(solid: Solid) -> Result<Vec<Solid>, KernelError> {
+    if solid.boundaries().len() < 2 {
+        return Ok(vec![solid]);
+    }
+    let lump_failed = |e: truck_topology::errors::Error| KernelError::BooleanFailed {
+        reason: format!("boolean result shell: {}", e),
+    };
+    let mut ids = 0;
+    let mut lumps: Vec<(Vec<Shell>, RenderMesh, f64)> = Vec::new();
+    let mut voids = Vec::new();
+    for shell in solid.boundaries() {
+        let alone = Solid::try_new(vec![shell.clone()]).map_err(lump_failed)?;
+        let mesh = tessellation::tessellate_solid(&alone, LUMP_TOLERANCE, &mut ids)?;
+        let volume = mesh::signed_volume(&mesh);
+        if volume < 0.0 {
+            voids.push(shell.clone());
+        } else {
+            lumps.push((vec![shell.clone()], mesh, volume));
+        }
+    }
+    for void in voids {
+        let Some(p) = void.vertex_iter().next().map(|v| v.point()) else {
+            continue;
+        };
+        let around = lumps
+            .iter_mut()
+            .filter(|(_, mesh, _)| mesh::contains_point(mesh, [p.x, p.y, p.z]))
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        match around {
+            Some((shells, _, _)) => shells.push(void),
+            None => {
+                return Err(KernelError::BooleanFailed {
+                    reason: "boolean result has a void outside every piece".to_string(),
+                })
+            }
+        }
+    }
+    lumps.sort_by(|a, b| b.2.total_cmp(&a.2));
+    lumps
+        .into_iter()
+        .map(|(shells, _, _)| Solid::try_new(shells).map_err(lump_failed))
+        .collect()
+}
+
 impl Default for TruckKernel {
     fn default() -> Self {
         Self::new()
@@ -138,27 +810,8 @@ impl Kernel for TruckKernel {
         a: &KernelSolidHandle,
         b: &KernelSolidHandle,
     ) -> Result<KernelSolidHandle, KernelError> {
-        let solid_a = self
-            .solids
-            .get(&a.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(a.id()),
-            })?
-            .clone();
-        let solid_b = self
-            .solids
-            .get(&b.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(b.id()),
-            })?
-            .clone();
-
-        let result = truck_shapeops::or(&solid_a, &solid_b, 0.05).ok_or_else(|| {
-            KernelError::BooleanFailed {
-                reason: "truck or() returned None".to_string(),
-            }
-        })?;
-        Ok(self.store_solid(result))
+        let union = self.boolean_solid(a, b, BooleanOp::Union)?;
+        Ok(self.store_solid(union))
     }
 
     fn boolean_subtract(
@@ -166,29 +819,7 @@ impl Kernel for TruckKernel {
         a: &KernelSolidHandle,
         b: &KernelSolidHandle,
     ) -> Result<KernelSolidHandle, KernelError> {
-        let solid_a = self
-            .solids
-            .get(&a.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(a.id()),
-            })?
-            .clone();
-        let mut solid_b = self
-            .solids
-            .get(&b.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(b.id()),
-            })?
-            .clone();
-
-        // Subtraction = A ∩ ¬B. not() mutates in place.
-        solid_b.not();
-        let result = truck_shapeops::and(&solid_a, &solid_b, 0.05).ok_or_else(|| {
-            KernelError::BooleanFailed {
-                reason: "truck and() returned None for subtraction".to_string(),
-            }
-        })?;
-        Ok(self.store_solid(result))
+        self.boolean_largest(a, b, BooleanOp::Subtract)
     }
 
     fn boolean_intersect(
@@ -196,35 +827,16 @@ impl Kernel for TruckKernel {
         a: &KernelSolidHandle,
         b: &KernelSolidHandle,
     ) -> Result<KernelSolidHandle, KernelError> {
-        let solid_a = self
-            .solids
-            .get(&a.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(a.id()),
-            })?
-            .clone();
-        let solid_b = self
-            .solids
-            .get(&b.id())
-            .ok_or(KernelError::EntityNotFound {
-                id: KernelId(b.id()),
-            })?
-            .clone();
-
-        let result = truck_shapeops::and(&solid_a, &solid_b, 0.05).ok_or_else(|| {
-            KernelError::BooleanFailed {
-                reason: "truck and() returned None".to_string(),
-            }
-        })?;
-        Ok(self.store_solid(result))
+        self.boolean_largest(a, b, BooleanOp::Intersect)
     }
 
     fn fillet_edges(
         &mut self,
-        _solid: &KernelSolidHandle,
+        solid: &KernelSolidHandle,
         _edges: &[KernelId],
         _radius: f64,
     ) -> Result<KernelSolidHandle, KernelError> {
+        self.reject_imported(solid, "fillet_edges")?;
         Err(KernelError::NotSupported {
             operation: "fillet_edges".to_string(),
         })
@@ -241,15 +853,34 @@ impl Kernel for TruckKernel {
         })
     }
 
+    /// Solids bounded by planes only, see [`planar_shell::shell_planar_solid`].
     fn shell(
         &mut self,
-        _solid: &KernelSolidHandle,
-        _faces_to_remove: &[KernelId],
-        _thickness: f64,
+        solid: &KernelSolidHandle,
+        faces_to_remove: &[KernelId],
+        thickness: f64,
     ) -> Result<KernelSolidHandle, KernelError> {
-        Err(KernelError::NotSupported {
-            operation: "shell".to_string(),
-        })
+        self.reject_imported(solid, "shell")?;
+        if thickness <= 0.0 {
+            return Err(KernelError::ShellFailed {
+                reason: "thickness must be positive".to_string(),
+            });
+        }
+        let truck_solid = self.get_solid(solid).ok_or(KernelError::EntityNotFound {
+            id: KernelId(solid.id()),
+        })?;
+        // Face ids are `handle * 10000 + index`, see truck_introspect.
+        let remove = faces_to_remove
+            .iter()
+            .map(|face| match (face.0 / 10000, face.0 % 10000) {
+                (handle, index) if handle == solid.id() && index < 1000 => Ok(index as usize),
+                _ => Err(KernelError::ShellFailed {
+                    reason: format!("face {:?} not found in solid", face),
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let shelled = planar_shell::shell_planar_solid(truck_solid, &remove, thickness)?;
+        Ok(self.store_solid(shelled))
     }
 
     fn tessellate(
@@ -257,6 +888,10 @@ impl Kernel for TruckKernel {
         solid: &KernelSolidHandle,
         tolerance: f64,
     ) -> Result<RenderMesh, KernelError> {
+        if let Some(imported) = self.imported_meshes.get(&solid.id()) {
+            return Ok(imported.clone());
+        }
+
         let truck_solid = self
             .solids
             .get(&solid.id())
@@ -264,7 +899,13 @@ impl Kernel for TruckKernel {
                 id: KernelId(solid.id()),
             })?;
 
-        tessellation::tessellate_solid(truck_solid, tolerance, &mut self.next_id)
+        if let Some(mesh) = self.tess_cache.get(solid.id(), tolerance) {
+            return Ok(mesh);
+        }
+
+        let mesh = tessellation::tessellate_solid(truck_solid, tolerance, &mut self.next_id)?;
+        self.tess_cache.insert(solid.id(), tolerance, mesh.clone());
+        Ok(mesh)
     }
 
     fn extract_edges(
@@ -272,6 +913,14 @@ impl Kernel for TruckKernel {
         solid: &KernelSolidHandle,
         tolerance: f64,
     ) -> Result<EdgeRenderData, KernelError> {
+        // Imported meshes carry no B-Rep edges to overlay.
+        if self.is_imported(solid) {
+            return Ok(EdgeRenderData {
+                vertices: Vec::new(),
+                edge_ranges: Vec::new(),
+            });
+        }
+
         let truck_solid = self
             .solids
             .get(&solid.id())
@@ -899,6 +1548,666 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_welding_box_tessellation_keeps_face_ranges() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        let split = kernel.tessellate(&handle, 0.1).unwrap();
+
+        let mut welded = split.clone();
+        assert!(mesh::weld_vertices(&mut welded, 1e-6) > 0);
+
+        // Indices are remapped in place, so each face keeps its range and
+        // its triangles land on the same corners.
+        let bounds = |mesh: &RenderMesh| -> Vec<(KernelId, u32, u32)> {
+            mesh.face_ranges
+                .iter()
+                .map(|r| (r.face_id, r.start_index, r.end_index))
+                .collect()
+        };
+        assert_eq!(bounds(&welded), bounds(&split));
+        let corner = |mesh: &RenderMesh, i: u32| {
+            mesh.vertices[i as usize * 3..i as usize * 3 + 3].to_vec()
+        };
+        for (a, b) in split.indices.iter().zip(&welded.indices) {
+            assert_eq!(corner(&split, *a), corner(&welded, *b));
+        }
+    }
+
+    #[test]
+    fn test_tessellation_cache_hits_unchanged_solid() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+
+        let first = kernel.tessellate(&handle, 0.1).unwrap();
+        assert_eq!(kernel.tessellation_cache_hits(), 0);
+        assert_eq!(kernel.tessellation_cache_misses(), 1);
+        let second = kernel.tessellate(&handle, 0.1).unwrap();
+        assert_eq!(kernel.tessellation_cache_hits(), 1);
+        assert_eq!(kernel.tessellation_cache_misses(), 1);
+        assert_eq!(first.indices, second.indices);
+        assert_eq!(first.vertices, second.vertices);
+
+        // An edit stores a new solid under a new handle, which must miss.
+        let edited = kernel.store_solid(primitives::make_box(2.0, 1.0, 1.0));
+        kernel.tessellate(&edited, 0.1).unwrap();
+        assert_eq!(kernel.tessellation_cache_hits(), 1);
+        assert_eq!(kernel.tessellation_cache_misses(), 2);
+
+        // A different tolerance is a different mesh.
+        kernel.tessellate(&handle, 0.05).unwrap();
+        assert_eq!(kernel.tessellation_cache_hits(), 1);
+        assert_eq!(kernel.tessellation_cache_misses(), 3);
+    }
+
+    #[test]
+    fn test_tessellation_cache_evicts_least_recently_used() {
+        use crate::tessellation::{TessellationCache, TESSELLATION_CACHE_CAPACITY};
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        let mesh = kernel.tessellate(&handle, 0.1).unwrap();
+
+        let mut cache = TessellationCache::new();
+        for h in 0..TESSELLATION_CACHE_CAPACITY as u64 {
+            cache.insert(h, 0.1, mesh.clone());
+        }
+        // Using handle 0 makes handle 1 the least recently used.
+        assert!(cache.get(0, 0.1).is_some());
+        cache.insert(TESSELLATION_CACHE_CAPACITY as u64, 0.1, mesh);
+        assert_eq!(cache.len(), TESSELLATION_CACHE_CAPACITY);
+        assert!(cache.get(0, 0.1).is_some());
+        assert!(cache.get(1, 0.1).is_none());
+        assert!(cache.get(2, 0.1).is_some());
+    }
+
+    #[test]
+    fn test_section_of_box_through_its_middle() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(2.0, 3.0, 4.0));
+        let loops = kernel
+            .section(&handle, 0.1, [0.0, 0.0, 2.0], [0.0, 0.0, 1.0])
+            .unwrap();
+        assert_eq!(loops.len(), 1);
+        let rectangle = &loops[0];
+        assert_eq!(rectangle.len(), 4, "{:?}", rectangle);
+        let n = rectangle.len();
+        let area: f64 = (0..n)
+            .map(|i| {
+                let (p, q) = (rectangle[i], rectangle[(i + 1) % n]);
+                p[0] * q[1] - q[0] * p[1]
+            })
+            .sum::<f64>()
+            / 2.0;
+        assert!((area - 6.0).abs() < 1e-6, "area {}", area);
+    }
+
+    #[test]
+    fn test_import_mesh_round_trips_and_measures() {
+        let mut kernel = TruckKernel::new();
+        let source = kernel.store_solid(primitives::make_box(2.0, 3.0, 4.0));
+        let box_mesh = kernel.tessellate(&source, 0.1).unwrap();
+
+        let imported = kernel.import_mesh(box_mesh.clone());
+        assert!(kernel.is_imported(&imported));
+
+        let mesh = kernel.tessellate(&imported, 0.1).unwrap();
+        assert_eq!(mesh.vertices, box_mesh.vertices);
+        assert_eq!(mesh.indices, box_mesh.indices);
+
+        let (min, max) = kernel.bounding_box(&imported, 0.1).unwrap();
+        let expected_max = [2.0, 3.0, 4.0];
+        for axis in 0..3 {
+            assert!(min[axis].abs() < 1e-6);
+            assert!((max[axis] - expected_max[axis]).abs() < 1e-6);
+        }
+        let area = kernel.solid_surface_area(&imported).unwrap();
+        assert!((area - 52.0).abs() < 1e-6, "area {}", area);
+
+        let err = kernel.boolean_union(&imported, &source).unwrap_err();
+        assert!(matches!(err, KernelError::NotSupported { .. }));
+        let err = kernel.fillet_edges(&imported, &[], 0.1).unwrap_err();
+        assert!(matches!(err, KernelError::NotSupported { .. }));
+    }
+
+    #[test]
+    fn test_solidity_of_brep_and_imported_sheet() {
+        let mut kernel = TruckKernel::new();
+        let source = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        assert_eq!(kernel.solidity(&source), Solidity::Solid);
+
+        let mut open = kernel.tessellate(&source, 0.1).unwrap();
+        let closed = kernel.import_mesh(open.clone());
+        assert_eq!(kernel.solidity(&closed), Solidity::Solid);
+
+        let last = open.face_ranges.pop().unwrap();
+        open.indices.truncate(last.start_index as usize);
+        let sheet = kernel.import_mesh(open.clone());
+        // Open borders are a validation finding, not a change of kind.
+        assert_eq!(kernel.solidity(&sheet), Solidity::Solid);
+        let validation = mesh::validate_mesh(&open);
+        assert!(validation.boundary_edges > 0);
+        assert!(!validation.is_valid(kernel.solidity(&sheet)));
+
+        kernel.set_solidity(&sheet, Solidity::Sheet).unwrap();
+        assert_eq!(kernel.solidity(&sheet), Solidity::Sheet);
+        assert!(validation.is_valid(kernel.solidity(&sheet)));
+        let dropped = kernel.drop_to_plate(&sheet, [0.0, 0.0, 1.0], 0.1).unwrap();
+        assert_eq!(kernel.solidity(&dropped), Solidity::Sheet);
+        let moved = kernel
+            .transform_solid(&sheet, &Transform::translation([0.0, 0.0, 1.0]))
+            .unwrap();
+        assert_eq!(kernel.solidity(&moved), Solidity::Sheet);
+
+        let err = kernel.set_solidity(&source, Solidity::Sheet).unwrap_err();
+        assert!(matches!(err, KernelError::NotSupported { .. }));
+        let err = kernel
+            .set_solidity(&KernelSolidHandle(999), Solidity::Solid)
+            .unwrap_err();
+        assert!(matches!(err, KernelError::EntityNotFound { .. }));
+    }
+
+    #[test]
+    fn test_repair_and_verify_fixes_imported_winding() {
+        let mut kernel = TruckKernel::new();
+        let source = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        let mut broken = kernel.tessellate(&source, 0.1).unwrap();
+        // Reverse every triangle of one face.
+        let face = broken.face_ranges[2].clone();
+        for t in (face.start_index as usize / 3)..(face.end_index as usize / 3) {
+            broken.indices.swap(t * 3 + 1, t * 3 + 2);
+        }
+        let flipped = (face.end_index - face.start_index) as usize / 3;
+        assert!(mesh::validate_mesh(&broken).flipped_edges > 0);
+        let imported = kernel.import_mesh(broken);
+
+        let (fixed, report, validation) = kernel.repair_and_verify(&imported, 0.1).unwrap();
+        assert_ne!(fixed.id(), imported.id());
+        assert_eq!(report.rewound_triangles, flipped);
+        assert_eq!(report.merged_duplicates, 0);
+        assert!(validation.is_closed_manifold(), "{:?}", validation);
+        let repaired = kernel.tessellate(&fixed, 0.1).unwrap();
+        assert!(mesh::misoriented_faces(&repaired).is_empty());
+
+        // The imported body itself is untouched.
+        let original = kernel.tessellate(&imported, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&original).flipped_edges > 0);
+
+        // B-Rep solids need nothing.
+        let (same, report, validation) = kernel.repair_and_verify(&source, 0.1).unwrap();
+        assert_eq!(same.id(), source.id());
+        assert!(report.is_clean());
+        assert!(validation.is_closed_manifold());
+    }
+
+    #[test]
+    fn test_tessellate_plate_with_square_hole_leaves_opening() {
+        // A 4x4 face with a concentric 2x2 inner loop, wound the other way.
+        let square = |min: f64, max: f64, clockwise: bool| {
+            let mut corners = vec![
+                Point3::new(min, min, 0.0),
+                Point3::new(max, min, 0.0),
+                Point3::new(max, max, 0.0),
+                Point3::new(min, max, 0.0),
+            ];
+            if clockwise {
+                corners.reverse();
+            }
+            let vertices: Vec<_> = corners.into_iter().map(builder::vertex).collect();
+            (0..4)
+                .map(|k| builder::line(&vertices[k], &vertices[(k + 1) % 4]))
+                .collect::<Wire>()
+        };
+        let face = builder::try_attach_plane(&[square(0.0, 4.0, false), square(1.0, 3.0, true)])
+            .expect("plate face");
+        let plate: Solid = builder::tsweep(&face, Vector3::unit_z());
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(plate);
+        let mesh = kernel.tessellate(&handle, 0.01).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+
+        let corner = |i: u32| {
+            let i = i as usize * 3;
+            [0, 1, 2].map(|k| mesh.vertices[i + k] as f64)
+        };
+        let mut top_area = 0.0;
+        for tri in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [tri[0], tri[1], tri[2]].map(corner);
+            let centroid = [0, 1, 2].map(|k| (a[k] + b[k] + c[k]) / 3.0);
+            let in_hole = |x: f64| x > 1.0 + 1e-6 && x < 3.0 - 1e-6;
+            assert!(
+                !(in_hole(centroid[0]) && in_hole(centroid[1])),
+                "triangle {:?} covers the hole",
+                [a, b, c]
+            );
+            if [a, b, c].iter().all(|p| (p[2] - 1.0).abs() < 1e-9) {
+                let (u, v) = ([b[0] - a[0], b[1] - a[1]], [c[0] - a[0], c[1] - a[1]]);
+                top_area += (u[0] * v[1] - u[1] * v[0]).abs() / 2.0;
+            }
+        }
+        assert!((top_area - 12.0).abs() < 1e-6, "top face area {}", top_area);
+    }
+
+    #[test]
+    fn test_validate_solid_orientation_plate_with_hole() {
+        use truck_modeling::builder;
+
+        // Cube [0,1]^3 pierced by a cylinder of r=0.25 at (0.5, 0.5).
+        let v = builder::vertex(Point3::new(0.0, 0.0, 0.0));
+        let e = builder::tsweep(&v, Vector3::unit_x());
+        let f = builder::tsweep(&e, Vector3::unit_y());
+        let cube: Solid = builder::tsweep(&f, Vector3::unit_z());
+        let v = builder::vertex(Point3::new(0.5, 0.25, -0.5));
+        let w = builder::rsweep(&v, Point3::new(0.5, 0.5, 0.0), Vector3::unit_z(), Rad(7.0));
+        let f = builder::try_attach_plane(&[w]).unwrap();
+        let mut cylinder = builder::tsweep(&f, Vector3::unit_z() * 2.0);
+        cylinder.not();
+        let plate = truck_shapeops::and(&cube, &cylinder, 0.05).unwrap();
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(plate);
+        assert!(kernel
+            .validate_solid_orientation(&handle, 0.05)
+            .unwrap()
+            .is_empty());
+
+        // Flip the hole's wall: every vertex sits on the cylinder.
+        let mut mesh = kernel.tessellate(&handle, 0.05).unwrap();
+        let on_hole = |mesh: &RenderMesh, i: u32| {
+            let i = i as usize * 3;
+            let (x, y) = (mesh.vertices[i] - 0.5, mesh.vertices[i + 1] - 0.5);
+            ((x * x + y * y).sqrt() - 0.25).abs() < 1e-3
+        };
+        let wall = mesh
+            .face_ranges
+            .iter()
+            .find(|r| {
+                (r.start_index..r.end_index).all(|k| on_hole(&mesh, mesh.indices[k as usize]))
+            })
+            .cloned()
+            .expect("hole wall face");
+        for t in wall.start_index / 3..wall.end_index / 3 {
+            mesh.indices.swap(t as usize * 3 + 1, t as usize * 3 + 2);
+        }
+        assert_eq!(mesh::misoriented_faces(&mesh), vec![wall.face_id]);
+    }
+
+    #[test]
+    fn test_cut_with_plane_halves_box() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(2.0, 2.0, 2.0));
+
+        let cut = kernel
+            .cut_with_plane(&handle, [0.0, 0.0, 1.0], [0.0, 0.0, 1.0])
+            .unwrap();
+        let (min, max) = kernel.bounding_box(&cut, 0.1).unwrap();
+        assert!(min[2].abs() < 1e-6 && (max[2] - 1.0).abs() < 1e-6);
+
+        let mesh = kernel.tessellate(&cut, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        assert!((mesh::signed_volume(&mesh) - 4.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_cut_with_plane_caps_sphere() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_sphere(1.0));
+        let full = mesh::signed_volume(&kernel.tessellate(&handle, 0.01).unwrap());
+
+        // Just above the equator rather than through it.
+        let cut = kernel
+            .cut_with_plane(&handle, [0.0, 0.0, 0.1], [0.0, 0.0, 1.0])
+            .unwrap();
+        // Revolved surfaces don't tessellate watertight in truck, so only the
+        // shape is checked here.
+        let mesh = kernel.tessellate(&cut, 0.01).unwrap();
+        let (min, max) = mesh::bounding_box(&mesh).unwrap();
+        assert!((max[2] - 0.1).abs() < 1e-3, "flat cap at the cut");
+        assert!((min[2] + 1.0).abs() < 1e-2, "the bottom is kept");
+        // Below z = h on a unit sphere: 4pi/3 minus the cap pi(1-h)^2(2+h)/3.
+        let h: f64 = 0.1;
+        let expected = 1.0 - (1.0 - h).powi(2) * (2.0 + h) / 4.0;
+        let ratio = mesh::signed_volume(&mesh) / full;
+        assert!((ratio - expected).abs() < 0.01, "ratio = {}", ratio);
+    }
+
+    #[test]
+    fn test_chamfer_vertex_cuts_box_corner() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(2.0, 2.0, 2.0));
+
+        let chamfered = kernel.chamfer_vertex(&handle, [2.0, 2.0, 2.0], 0.5).unwrap();
+        let solid = kernel.get_solid(&chamfered).unwrap();
+        let faces: Vec<_> = solid.boundaries()[0].face_iter().collect();
+        assert_eq!(faces.len(), 7);
+        let triangles = faces
+            .iter()
+            .filter(|f| f.boundaries().iter().map(|w| w.len()).sum::<usize>() == 3)
+            .count();
+        assert_eq!(triangles, 1);
+
+        let mesh = kernel.tessellate(&chamfered, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        let expected = 8.0 - 0.5f64.powi(3) / 6.0;
+        assert!((mesh::signed_volume(&mesh) - expected).abs() < 1e-4);
+
+        assert!(kernel.chamfer_vertex(&handle, [2.0, 2.0, 2.0], 2.5).is_err());
+        assert!(matches!(
+            kernel.chamfer_vertex(&handle, [1.0, 1.0, 1.0], 0.5),
+            Err(KernelError::NotSupported { .. })
+        ));
+    }
+
+    /// An L-shaped block: the inside corner of the L is not convex, so a
+    /// plane through its cut-back points would slice into the arms.
+    #[test]
+    fn test_chamfer_vertex_rejects_inside_corner_of_l_block() {
+        use truck_modeling::builder;
+
+        let corners = [
+            [0.0, 0.0],
+            [2.0, 0.0],
+            [2.0, 1.0],
+            [1.0, 1.0],
+            [1.0, 2.0],
+            [0.0, 2.0],
+        ];
+        let vertices: Vec<_> = corners
+            .iter()
+            .map(|&[x, y]| builder::vertex(Point3::new(x, y, 0.0)))
+            .collect();
+        let wire: Wire = (0..vertices.len())
+            .map(|k| builder::line(&vertices[k], &vertices[(k + 1) % vertices.len()]))
+            .collect();
+        let face = builder::try_attach_plane(&[wire]).expect("L face");
+        let block: Solid = builder::tsweep(&face, Vector3::unit_z());
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(block);
+        for inside in [[1.0, 1.0, 0.0], [1.0, 1.0, 1.0]] {
+            assert!(matches!(
+                kernel.chamfer_vertex(&handle, inside, 0.25),
+                Err(KernelError::NotSupported { .. })
+            ));
+        }
+
+        // An outside corner of the same block still chamfers, removing only
+        // the corner tetrahedron.
+        let chamfered = kernel.chamfer_vertex(&handle, [2.0, 0.0, 1.0], 0.25).unwrap();
+        let solid = kernel.get_solid(&chamfered).unwrap();
+        assert_eq!(solid.boundaries()[0].face_iter().count(), 8 + 1);
+        let mesh = kernel.tessellate(&chamfered, 0.1).unwrap();
+        let expected = 3.0 - 0.25f64.powi(3) / 6.0;
+        assert!((mesh::signed_volume(&mesh) - expected).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_drop_to_plate_box_and_sphere() {
+        use truck_modeling::builder;
+
+        let mut kernel = TruckKernel::new();
+        let raised = builder::translated(
+            &primitives::make_box(2.0, 2.0, 5.0),
+            Vector3::new(1.0, -3.0, 5.0),
+        );
+        let handle = kernel.store_solid(raised);
+        let dropped = kernel.drop_to_plate(&handle, [0.0, 0.0, 1.0], 0.1).unwrap();
+        let (min, max) = kernel.bounding_box(&dropped, 0.1).unwrap();
+        assert!(min[2].abs() < 1e-6 && (max[2] - 5.0).abs() < 1e-6);
+        assert!((min[0] - 1.0).abs() < 1e-6 && (min[1] + 3.0).abs() < 1e-6);
+
+        let sphere = kernel.store_solid(primitives::make_sphere(1.5));
+        let dropped = kernel.drop_to_plate(&sphere, [0.0, 0.0, 2.0], 0.01).unwrap();
+        let (min, max) = kernel.bounding_box(&dropped, 0.01).unwrap();
+        assert!(min[2].abs() < 1e-6, "bottom at {}", min[2]);
+        assert!((max[2] - 3.0).abs() < 1e-3, "top at {}", max[2]);
+
+        let box_mesh = kernel.tessellate(&handle, 0.1).unwrap();
+        let imported = kernel.import_mesh(box_mesh);
+        let dropped = kernel.drop_to_plate(&imported, [0.0, 0.0, 1.0], 0.1).unwrap();
+        assert!(kernel.is_imported(&dropped));
+        let (min, _) = kernel.bounding_box(&dropped, 0.1).unwrap();
+        assert!(min[2].abs() < 1e-6);
+
+        assert!(kernel.drop_to_plate(&handle, [0.0; 3], 0.1).is_err());
+    }
+
+    #[test]
+    fn test_transform_solid_translates_and_rotates_box() {
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(2.0, 1.0, 1.0));
+
+        let shifted = kernel
+            .transform_solid(&handle, &Transform::translation([5.0, 0.0, 0.0]))
+            .unwrap();
+        let (min, max) = kernel.bounding_box(&shifted, 0.1).unwrap();
+        assert!((min[0] - 5.0).abs() < 1e-6 && (max[0] - 7.0).abs() < 1e-6);
+        assert!(min[1].abs() < 1e-6 && (max[1] - 1.0).abs() < 1e-6);
+
+        // A quarter turn about z takes the corner (2, 0, 0) to (0, 2, 0).
+        let turn = Transform::rotation([0.0, 0.0, 1.0], std::f64::consts::FRAC_PI_2).unwrap();
+        let turned = kernel.transform_solid(&handle, &turn).unwrap();
+        let mesh = kernel.tessellate(&turned, 0.1).unwrap();
+        let at = |v: &[f32], p: [f32; 3]| (0..3).all(|k| (v[k] - p[k]).abs() < 1e-5);
+        let corners = mesh.vertices.chunks_exact(3);
+        assert!(corners.clone().any(|v| at(v, [0.0, 2.0, 0.0])));
+        assert!(!corners.clone().any(|v| at(v, [2.0, 0.0, 0.0])));
+        let (min, max) = mesh::bounding_box(&mesh).unwrap();
+        assert!((min[0] + 1.0).abs() < 1e-6 && max[0].abs() < 1e-6);
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        assert!((mesh::signed_volume(&mesh) - 2.0).abs() < 1e-4);
+
+        // Mirrored and stretched, the box still points outward.
+        let mirror = Transform::scaling([-1.0, 3.0, 1.0]);
+        let mirrored = kernel.transform_solid(&handle, &mirror).unwrap();
+        let mesh = kernel.tessellate(&mirrored, 0.1).unwrap();
+        assert!((mesh::signed_volume(&mesh) - 6.0).abs() < 1e-4);
+        assert!(kernel
+            .transform_solid(&handle, &Transform::scaling([1.0, 0.0, 1.0]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_subtract_through_middle_gives_two_solids() {
+        let mut kernel = TruckKernel::new();
+        let wide = kernel.store_solid(primitives::make_box(10.0, 4.0, 4.0));
+        let slab = builder::translated(
+            &primitives::make_box(1.0, 6.0, 6.0),
+            Vector3::new(4.5, -1.0, -1.0),
+        );
+        let slab = kernel.store_solid(slab);
+
+        let lumps = kernel
+            .boolean_lumps(&wide, &slab, BooleanOp::Subtract)
+            .unwrap();
+        assert_eq!(lumps.len(), 2);
+        let mut sides = Vec::new();
+        for lump in &lumps {
+            let mesh = kernel.tessellate(lump, 0.1).unwrap();
+            assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+            assert!((mesh::signed_volume(&mesh) - 72.0).abs() < 1e-3);
+            let (min, max) = mesh::bounding_box(&mesh).unwrap();
+            sides.push(min[0] > 5.0 || max[0] < 5.0);
+        }
+        assert!(sides.iter().all(|&apart| apart));
+
+        // The plain boolean keeps one of the two halves.
+        let half = kernel.boolean_subtract(&wide, &slab).unwrap();
+        let mesh = kernel.tessellate(&half, 0.1).unwrap();
+        assert!((mesh::signed_volume(&mesh) - 72.0).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_union_of_disjoint_boxes_keeps_both() {
+        let mut kernel = TruckKernel::new();
+        let small = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        let large = builder::translated(
+            &primitives::make_box(2.0, 2.0, 2.0),
+            Vector3::new(5.0, 0.0, 0.0),
+        );
+        let large = kernel.store_solid(large);
+
+        let union = kernel.boolean_union(&small, &large).unwrap();
+        assert_eq!(kernel.get_solid(&union).unwrap().boundaries().len(), 2);
+        let mesh = kernel.tessellate(&union, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        assert!((mesh::signed_volume(&mesh) - 9.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_merge_coplanar_faces_joins_split_walls() {
+        // Two unit cubes side by side, as their union leaves them, with both
+        // long walls split in two at x = 1.
+        let xs = [0.0, 1.0, 2.0, 2.0, 1.0, 0.0];
+        let ys = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let profile: Vec<Point3> = (0..6).map(|i| Point3::new(xs[i], ys[i], 0.0)).collect();
+        let slab = loft::extrude_with_draft(&profile, Vector3::unit_z(), 0.0).unwrap();
+        assert_eq!(slab.boundaries()[0].face_iter().count(), 8);
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(slab);
+
+        let merged = kernel.merge_coplanar_faces(&handle).unwrap();
+        let shell = &kernel.get_solid(&merged).unwrap().boundaries()[0];
+        assert_eq!(shell.face_iter().count(), 6);
+        let vertices: std::collections::HashSet<_> = shell.vertex_iter().map(|v| v.id()).collect();
+        assert_eq!(vertices.len(), 8);
+        let mesh = kernel.tessellate(&merged, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        assert!((mesh::signed_volume(&mesh) - 2.0).abs() < 1e-6);
+
+        // A plain box has nothing to merge.
+        let cube = kernel.store_solid(primitives::make_box(1.0, 1.0, 1.0));
+        let same = kernel.merge_coplanar_faces(&cube).unwrap();
+        let shell = &kernel.get_solid(&same).unwrap().boundaries()[0];
+        assert_eq!(shell.face_iter().count(), 6);
+    }
+
+    #[test]
+    fn test_shell_box_open_top_and_closed() {
+        use truck_modeling::geometry::Surface;
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.store_solid(primitives::make_box(10.0, 8.0, 6.0));
+        let top = kernel.get_solid(&handle).unwrap().boundaries()[0]
+            .face_iter()
+            .position(|face| match face.oriented_surface() {
+                Surface::Plane(plane) => plane.normal().z > 0.5,
+                _ => false,
+            })
+            .unwrap();
+        let top = KernelId(handle.id() * 10000 + top as u64);
+
+        // Open at the top: 10 x 8 x 6 outside, 8 x 6 x 5 inside.
+        let cup = kernel.shell(&handle, &[top], 1.0).unwrap();
+        let mesh = kernel.tessellate(&cup, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        let volume = mesh::signed_volume(&mesh);
+        assert!((volume - (480.0 - 240.0)).abs() < 1e-3, "volume {}", volume);
+        let (min, max) = kernel.bounding_box(&cup, 0.1).unwrap();
+        assert!(min[2].abs() < 1e-6 && (max[2] - 6.0).abs() < 1e-6);
+
+        // Nothing removed leaves a sealed void of 8 x 6 x 4.
+        let hollow = kernel.shell(&handle, &[], 1.0).unwrap();
+        let mesh = kernel.tessellate(&hollow, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        assert!((mesh::signed_volume(&mesh) - (480.0 - 192.0)).abs() < 1e-3);
+
+        assert!(matches!(
+            kernel.shell(&handle, &[top], 4.0),
+            Err(KernelError::ShellFailed { .. })
+        ));
+        let cylinder = kernel.store_solid(primitives::make_cylinder(1.0, 2.0));
+        assert!(matches!(
+            kernel.shell(&cylinder, &[], 0.1),
+            Err(KernelError::ShellFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_loft_square_to_turned_square_takes_shortest_twist() {
+        let s = std::f64::consts::SQRT_2;
+        let square = [
+            [1.0, 1.0, 0.0],
+            [-1.0, 1.0, 0.0],
+            [-1.0, -1.0, 0.0],
+            [1.0, -1.0, 0.0],
+        ];
+        // The same square turned 45 degrees, listed clockwise from the far
+        // corner, so the loft has to re-wind and re-pair it.
+        let turned = [[-s, 0.0, 1.0], [0.0, s, 1.0], [s, 0.0, 1.0], [0.0, -s, 1.0]];
+
+        let mut kernel = TruckKernel::new();
+        let handle = kernel.loft_profiles(&square, &turned).unwrap();
+        let solid = kernel.get_solid(&handle).unwrap();
+        assert_eq!(solid.boundaries()[0].face_iter().count(), 4 + 2);
+
+        let mesh = kernel.tessellate(&handle, 0.001).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        // Sections are squares of area 2 r^2 with r^2 quadratic in height,
+        // so Simpson's rule is exact. An eighth turn halfway gives
+        // r^2 = 1 + cos 45; pairing corners a three-eighths turn apart
+        // would pinch the middle to 1 - cos 45.
+        let middle = 2.0 * (1.0 + std::f64::consts::FRAC_1_SQRT_2);
+        let expected = (4.0 + 4.0 * middle + 4.0) / 6.0;
+        let volume = mesh::signed_volume(&mesh);
+        assert!((volume - expected).abs() < 1e-2, "volume {}", volume);
+
+        assert!(matches!(
+            kernel.loft_profiles(&square, &turned[..3]),
+            Err(KernelError::ProfileMismatch { bottom: 4, top: 3 })
+        ));
+    }
+
+    #[test]
+    fn test_extrude_face_with_draft_tapers_square() {
+        let mut kernel = TruckKernel::new();
+        let profile = ClosedProfile {
+            entity_ids: vec![1, 2, 3, 4],
+            is_outer: true,
+        };
+        let mut positions = HashMap::new();
+        positions.insert(1, (0.0, 0.0));
+        positions.insert(2, (10.0, 0.0));
+        positions.insert(3, (10.0, 10.0));
+        positions.insert(4, (0.0, 10.0));
+        let face = kernel
+            .make_faces_from_profiles(
+                &[profile],
+                [0.0, 0.0, 0.0],
+                [0.0, 0.0, 1.0],
+                [1.0, 0.0, 0.0],
+                &positions,
+            )
+            .unwrap()[0];
+
+        let (depth, draft) = (10.0, 5f64.to_radians());
+        let handle = kernel
+            .extrude_face_with_draft(face, [0.0, 0.0, 1.0], depth, draft)
+            .unwrap();
+        let solid = kernel.get_solid(&handle).unwrap();
+        assert_eq!(solid.boundaries()[0].face_iter().count(), 6);
+
+        // Each wall leans in by depth * tan(draft), so the top edge loses
+        // that much at both ends.
+        let top_edge = 10.0 - 2.0 * depth * draft.tan();
+        let mesh = kernel.tessellate(&handle, 0.1).unwrap();
+        assert!(mesh::validate_mesh(&mesh).is_closed_manifold());
+        let top_xs: Vec<f64> = mesh
+            .vertices
+            .chunks_exact(3)
+            .filter(|v| (v[2] as f64 - depth).abs() < 1e-5)
+            .map(|v| v[0] as f64)
+            .collect();
+        let lo = top_xs.iter().cloned().fold(f64::MAX, f64::min);
+        let hi = top_xs.iter().cloned().fold(f64::MIN, f64::max);
+        assert!((hi - lo - top_edge).abs() < 1e-4, "top edge {}", hi - lo);
+
+        let (bottom_area, top_area) = (100.0, top_edge * top_edge);
+        let frustum = depth / 3.0 * (bottom_area + top_area + (bottom_area * top_area).sqrt());
+        assert!((mesh::signed_volume(&mesh) - frustum).abs() < 1e-3);
+    }
+
     #[test]
     fn test_truck_kernel_extract_edges_box() {
         let mut kernel = TruckKernel::new();
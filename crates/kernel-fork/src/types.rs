@@ -32,6 +32,9 @@ pub enum KernelError {
     #[error("shell failed: {reason}")]
     ShellFailed { reason: String },
 
+    #[error("loft profiles have {bottom} and {top} corners")]
+    ProfileMismatch { bottom: usize, top: usize },
+
     #[error("tessellation failed: {reason}")]
     TessellationFailed { reason: String },
 
@@ -89,6 +92,64 @@ pub struct EdgeRange {
     pub end_vertex: u32,
 }
 
+/// Which boolean to apply to two solids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BooleanOp {
+    Union,
+    Subtract,
+    Intersect,
+}
+
+/// Whether a body is meant to enclose a volume or is an open surface.
+///
+/// Sheets (surface models, single patches) legitimately have border edges,
+/// so validation and export skip the watertight requirement for them while
+/// still rejecting non-manifold and flipped edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Solidity {
+    #[default]
+    Solid,
+    Sheet,
+}
+
+/// How two faces meet along an edge, seen from outside the solid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Convexity {
+    /// The faces fold away from each other, like the edges of a box.
+    Convex,
+    /// The faces fold towards each other, like the inside corner of a notch.
+    Concave,
+    /// Two different faces meet with matching normals (no crease).
+    Tangent,
+    /// A seam inside a single face, such as the join of a cylinder wall.
+    Smooth,
+}
+
+impl Convexity {
+    /// Largest `|n_a x n_b|` at which two face normals count as tangent.
+    pub const TANGENT_TOLERANCE: f64 = 1e-3;
+
+    /// Classify the crease between faces `a` and `b` from their outward
+    /// unit normals at the edge and any vector pointing from the edge into
+    /// face `a`.
+    pub fn classify(normal_a: [f64; 3], normal_b: [f64; 3], into_a: [f64; 3]) -> Self {
+        let dot = |p: [f64; 3], q: [f64; 3]| p[0] * q[0] + p[1] * q[1] + p[2] * q[2];
+        let cross = [
+            normal_a[1] * normal_b[2] - normal_a[2] * normal_b[1],
+            normal_a[2] * normal_b[0] - normal_a[0] * normal_b[2],
+            normal_a[0] * normal_b[1] - normal_a[1] * normal_b[0],
+        ];
+        if dot(cross, cross).sqrt() < Self::TANGENT_TOLERANCE && dot(normal_a, normal_b) > 0.0 {
+            Convexity::Tangent
+        } else if dot(normal_b, into_a) < 0.0 {
+            // Face `a` runs off behind face `b`.
+            Convexity::Convex
+        } else {
+            Convexity::Concave
+        }
+    }
+}
+
 // Custom Serialize/Deserialize for KernelId (needed for FaceRange/EdgeRange serialization)
 impl Serialize for KernelId {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
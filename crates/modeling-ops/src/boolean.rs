@@ -1,9 +1,13 @@
 use kernel_fork::{KernelId, KernelSolidHandle};
-use waffle_types::{OutputKey, Role, TopoKind};
+use waffle_types::{FaceOrigin, OutputKey, Role, TopoKind, TopoSignature};
 
 use crate::diff::{self, TopoSnapshot};
 use crate::kernel_ext::KernelBundle;
-use crate::types::{BodyOutput, Diagnostics, OpError, OpResult, Provenance};
+use crate::types::{BodyOutput, Diagnostics, FaceProvenance, OpError, OpResult, Provenance};
+
+/// Below this signature similarity to every operand face, a result face is
+/// treated as new intersection geometry.
+const INHERIT_SIMILARITY: f64 = 0.5;
 
 /// Boolean operation type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,6 +23,22 @@ pub fn execute_boolean(
     body_a: &KernelSolidHandle,
     body_b: &KernelSolidHandle,
     kind: BooleanKind,
+) -> Result<OpResult, OpError> {
+    execute_boolean_with_operand_roles(kb, body_a, body_b, kind, &[], &[])
+}
+
+/// Execute a boolean, carrying the operands' face roles over to the result.
+///
+/// Each result face is matched to its most similar operand face by
+/// signature; `provenance.face_origins` records which operand that was and
+/// the role it had in `roles_a` / `roles_b`.
+pub fn execute_boolean_with_operand_roles(
+    kb: &mut dyn KernelBundle,
+    body_a: &KernelSolidHandle,
+    body_b: &KernelSolidHandle,
+    kind: BooleanKind,
+    roles_a: &[(KernelId, Role)],
+    roles_b: &[(KernelId, Role)],
 ) -> Result<OpResult, OpError> {
     // Snapshot both inputs for provenance
     let snap_a = diff::snapshot(kb.as_introspect(), body_a);
@@ -49,13 +69,21 @@ pub fn execute_boolean(
     let diff_result = diff::diff(&before, &after);
 
     // Assign roles to result faces
-    let role_assignments = assign_boolean_roles(kb.as_introspect(), &handle, &snap_a, &snap_b);
+    let traced = trace_face_origins(
+        kb.as_introspect(),
+        &handle,
+        (&snap_a, roles_a),
+        (&snap_b, roles_b),
+    );
+    let role_assignments = assign_boolean_roles(&traced);
+    let face_origins = traced.into_iter().map(|(origin, _)| origin).collect();
 
     let provenance = Provenance {
         created: diff_result.created,
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins,
     };
 
     Ok(OpResult {
@@ -65,39 +93,68 @@ pub fn execute_boolean(
     })
 }
 
-/// Assign roles to boolean result faces.
-fn assign_boolean_roles(
+/// Match every result face to the operand face it most resembles.
+///
+/// Each face is paired with whether it resembles body B more than body A,
+/// which still holds for intersection faces that match neither well.
+fn trace_face_origins(
     introspect: &dyn kernel_fork::KernelIntrospect,
     result: &KernelSolidHandle,
-    snap_a: &TopoSnapshot,
-    snap_b: &TopoSnapshot,
-) -> Vec<(KernelId, Role)> {
-    let result_faces = introspect.list_faces(result);
-    let mut assignments = Vec::new();
-    let mut a_index = 0;
-    let mut b_index = 0;
+    (snap_a, roles_a): (&TopoSnapshot, &[(KernelId, Role)]),
+    (snap_b, roles_b): (&TopoSnapshot, &[(KernelId, Role)]),
+) -> Vec<(FaceProvenance, bool)> {
+    let best_match = |sig: &TopoSignature, snap: &TopoSnapshot| {
+        snap.faces
+            .iter()
+            .map(|(id, s)| (*id, crate::diff::signature_similarity(sig, s)))
+            .fold((None, 0.0_f64), |best, (id, score)| {
+                if score > best.1 {
+                    (Some(id), score)
+                } else {
+                    best
+                }
+            })
+    };
+    let role_of = |roles: &[(KernelId, Role)], id: Option<KernelId>| {
+        id.and_then(|id| roles.iter().find(|(k, _)| *k == id).map(|(_, r)| r.clone()))
+    };
 
-    for &face_id in &result_faces {
-        let sig = introspect.compute_signature(face_id, TopoKind::Face);
+    introspect
+        .list_faces(result)
+        .into_iter()
+        .map(|face| {
+            let sig = introspect.compute_signature(face, TopoKind::Face);
+            let (id_a, best_a) = best_match(&sig, snap_a);
+            let (id_b, best_b) = best_match(&sig, snap_b);
 
-        let best_a = snap_a
-            .faces
-            .iter()
-            .map(|(_, s)| crate::diff::signature_similarity(&sig, s))
-            .fold(0.0_f64, |a, b| a.max(b));
+            let (origin, role) = if best_a.max(best_b) < INHERIT_SIMILARITY {
+                (FaceOrigin::FromIntersection, None)
+            } else if best_a >= best_b {
+                (FaceOrigin::FromToolA, role_of(roles_a, id_a))
+            } else {
+                (FaceOrigin::FromToolB, role_of(roles_b, id_b))
+            };
+            (FaceProvenance { face, origin, role }, best_b > best_a)
+        })
+        .collect()
+}
 
-        let best_b = snap_b
-            .faces
-            .iter()
-            .map(|(_, s)| crate::diff::signature_similarity(&sig, s))
-            .fold(0.0_f64, |a, b| a.max(b));
+/// Assign roles to boolean result faces.
+///
+/// Faces are numbered per operand, each counted towards the operand it
+/// resembles more, intersection faces included.
+fn assign_boolean_roles(traced: &[(FaceProvenance, bool)]) -> Vec<(KernelId, Role)> {
+    let mut assignments = Vec::new();
+    let mut a_index = 0;
+    let mut b_index = 0;
 
-        if best_a >= best_b {
-            assignments.push((face_id, Role::BooleanBodyAFace { index: a_index }));
-            a_index += 1;
-        } else {
-            assignments.push((face_id, Role::BooleanBodyBFace { index: b_index }));
+    for (provenance, nearer_b) in traced {
+        if *nearer_b {
+            assignments.push((provenance.face, Role::BooleanBodyBFace { index: b_index }));
             b_index += 1;
+        } else {
+            assignments.push((provenance.face, Role::BooleanBodyAFace { index: a_index }));
+            a_index += 1;
         }
     }
 
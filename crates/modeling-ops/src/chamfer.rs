@@ -36,6 +36,7 @@ pub fn execute_chamfer(
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins: Vec::new(),
     };
 
     Ok(OpResult {
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
+
 use kernel_fork::{KernelId, KernelSolidHandle};
-use waffle_types::{OutputKey, Role, TopoKind};
+use waffle_types::{ClosedProfile, OutputKey, Role, TopoKind};
 
 use crate::diff::{self, TopoSnapshot};
 use crate::kernel_ext::KernelBundle;
-use crate::types::{BodyOutput, Diagnostics, OpError, OpResult, Provenance};
+use crate::types::{BodyOutput, Diagnostics, OpError, OpResult, OperationWarning, Provenance};
 
 /// Execute an extrude operation.
 ///
@@ -40,6 +42,7 @@ pub fn execute_extrude(
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins: Vec::new(),
     };
 
     Ok(OpResult {
@@ -49,6 +52,39 @@ pub fn execute_extrude(
     })
 }
 
+/// Reverse a clockwise profile so it winds counter-clockwise in sketch
+/// coordinates.
+///
+/// The kernel orients a profile face by its winding, and a clockwise face
+/// swept along the sketch normal comes out inside-out. Returns the profile
+/// to build from, plus a warning when it had to be reversed so sketch
+/// authoring mistakes stay visible.
+pub fn normalize_profile_winding(
+    profile: &ClosedProfile,
+    positions: &HashMap<u32, (f64, f64)>,
+) -> (ClosedProfile, Option<OperationWarning>) {
+    let points: Vec<(f64, f64)> = profile
+        .entity_ids
+        .iter()
+        .filter_map(|id| positions.get(id).copied())
+        .collect();
+    let twice_area: f64 = (0..points.len())
+        .map(|i| {
+            let (x0, y0) = points[i];
+            let (x1, y1) = points[(i + 1) % points.len()];
+            x0 * y1 - x1 * y0
+        })
+        .sum();
+
+    if twice_area < 0.0 {
+        let mut reversed = profile.clone();
+        reversed.entity_ids.reverse();
+        (reversed, Some(OperationWarning::ClockwiseProfileNormalized))
+    } else {
+        (profile.clone(), None)
+    }
+}
+
 /// Execute a symmetric extrude: extrudes in both +direction and -direction by depth/2.
 /// Produces two solids and boolean-unions them, or extrudes by full depth
 /// centered on the sketch plane.
@@ -90,6 +126,7 @@ pub fn execute_symmetric_extrude(
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins: Vec::new(),
     };
 
     let mut diagnostics = Diagnostics::default();
@@ -169,5 +206,44 @@ fn assign_extrude_roles(
         }
     }
 
+    let profile_face = find_role(&assignments, &Role::EndCapNegative);
+    let cap_face = find_role(&assignments, &Role::EndCapPositive);
+    let edge_roles = assign_sweep_edge_roles(introspect, solid, profile_face, cap_face);
+    assignments.extend(edge_roles);
+
     assignments
 }
+
+/// Tag the edges of a swept solid by where they came from.
+///
+/// Edges bounding the profile face are `ProfileEdge`, edges bounding the
+/// far cap are `CapEdge`, and everything else was traced out by the sweep.
+pub(crate) fn assign_sweep_edge_roles(
+    introspect: &dyn kernel_fork::KernelIntrospect,
+    solid: &KernelSolidHandle,
+    profile_face: Option<KernelId>,
+    cap_face: Option<KernelId>,
+) -> Vec<(KernelId, Role)> {
+    introspect
+        .list_edges(solid)
+        .into_iter()
+        .map(|edge| {
+            let faces = introspect.edge_faces(edge);
+            let role = if profile_face.is_some_and(|f| faces.contains(&f)) {
+                Role::ProfileEdge
+            } else if cap_face.is_some_and(|f| faces.contains(&f)) {
+                Role::CapEdge
+            } else {
+                Role::SweepEdge
+            };
+            (edge, role)
+        })
+        .collect()
+}
+
+pub(crate) fn find_role(assignments: &[(KernelId, Role)], role: &Role) -> Option<KernelId> {
+    assignments
+        .iter()
+        .find(|(_, r)| r == role)
+        .map(|(id, _)| *id)
+}
@@ -37,6 +37,7 @@ pub fn execute_fillet(
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins: Vec::new(),
     };
 
     Ok(OpResult {
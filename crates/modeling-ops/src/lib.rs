@@ -8,12 +8,12 @@ pub mod revolve;
 pub mod shell;
 pub mod types;
 
-pub use boolean::{execute_boolean, BooleanKind};
+pub use boolean::{execute_boolean, execute_boolean_with_operand_roles, BooleanKind};
 pub use chamfer::execute_chamfer;
 pub use diff::{signature_similarity, snapshot, DiffResult, TopoSnapshot};
-pub use extrude::{execute_extrude, execute_symmetric_extrude};
+pub use extrude::{execute_extrude, execute_symmetric_extrude, normalize_profile_winding};
 pub use fillet::execute_fillet;
 pub use kernel_ext::KernelBundle;
-pub use revolve::execute_revolve;
+pub use revolve::{execute_revolve, execute_revolve_about_edge};
 pub use shell::execute_shell;
 pub use types::*;
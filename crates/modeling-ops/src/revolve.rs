@@ -2,6 +2,7 @@ use kernel_fork::{KernelId, KernelSolidHandle};
 use waffle_types::{OutputKey, Role, TopoKind};
 
 use crate::diff::{self, TopoSnapshot};
+use crate::extrude::{assign_sweep_edge_roles, find_role};
 use crate::kernel_ext::KernelBundle;
 use crate::types::{BodyOutput, Diagnostics, OpError, OpResult, Provenance};
 
@@ -34,6 +35,7 @@ pub fn execute_revolve(
         deleted: diff_result.deleted,
         modified: Vec::new(),
         role_assignments,
+        face_origins: Vec::new(),
     };
 
     Ok(OpResult {
@@ -43,6 +45,61 @@ pub fn execute_revolve(
     })
 }
 
+/// Revolve a face about an edge of an existing solid.
+///
+/// The axis runs from the edge's first vertex to its second, matching the
+/// "revolve about this edge" interaction. `edge_index` indexes
+/// `list_edges(solid)`.
+pub fn execute_revolve_about_edge(
+    kb: &mut dyn KernelBundle,
+    face_id: KernelId,
+    solid: &KernelSolidHandle,
+    edge_index: usize,
+    angle: f64,
+    before_snapshot: Option<&TopoSnapshot>,
+) -> Result<OpResult, OpError> {
+    let (axis_origin, axis_direction) = {
+        let introspect = kb.as_introspect();
+        let edges = introspect.list_edges(solid);
+        let edge = *edges
+            .get(edge_index)
+            .ok_or_else(|| OpError::InvalidParameter {
+                reason: format!(
+                    "edge index {} out of range ({} edges)",
+                    edge_index,
+                    edges.len()
+                ),
+            })?;
+        let (start, end) = introspect.edge_vertices(edge);
+        let position = |vertex: KernelId| {
+            introspect
+                .compute_signature(vertex, TopoKind::Vertex)
+                .centroid
+                .ok_or_else(|| OpError::InvalidParameter {
+                    reason: format!("edge {} has no vertex positions", edge_index),
+                })
+        };
+        let (p0, p1) = (position(start)?, position(end)?);
+        let direction = [p1[0] - p0[0], p1[1] - p0[1], p1[2] - p0[2]];
+        let length = (direction[0].powi(2) + direction[1].powi(2) + direction[2].powi(2)).sqrt();
+        if length < 1e-12 {
+            return Err(OpError::InvalidParameter {
+                reason: format!("edge {} is degenerate", edge_index),
+            });
+        }
+        (p0, direction.map(|c| c / length))
+    };
+
+    execute_revolve(
+        kb,
+        face_id,
+        axis_origin,
+        axis_direction,
+        angle,
+        before_snapshot,
+    )
+}
+
 /// Assign semantic roles to faces of a revolved solid.
 fn assign_revolve_roles(
     introspect: &dyn kernel_fork::KernelIntrospect,
@@ -111,5 +168,10 @@ fn assign_revolve_roles(
         }
     }
 
+    let profile_face = find_role(&assignments, &Role::RevStartFace);
+    let cap_face = find_role(&assignments, &Role::RevEndFace);
+    let edge_roles = assign_sweep_edge_roles(introspect, solid, profile_face, cap_face);
+    assignments.extend(edge_roles);
+
     assignments
 }
@@ -1,5 +1,5 @@
 use kernel_fork::{KernelId, KernelSolidHandle, RenderMesh};
-use waffle_types::{OutputKey, Role, TopoKind, TopoSignature};
+use waffle_types::{FaceOrigin, OutputKey, Role, TopoKind, TopoSignature};
 
 /// Complete result of a modeling operation.
 /// Contains everything feature-engine needs to update the model state
@@ -14,6 +14,21 @@ pub struct OpResult {
     pub diagnostics: Diagnostics,
 }
 
+impl OpResult {
+    /// Kernel IDs of the edges tagged with `role` by this operation.
+    pub fn edges_with_role(&self, role: &Role) -> Vec<KernelId> {
+        if !matches!(role, Role::ProfileEdge | Role::SweepEdge | Role::CapEdge) {
+            return Vec::new();
+        }
+        self.provenance
+            .role_assignments
+            .iter()
+            .filter(|(_, r)| r == role)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+}
+
 /// A body output from an operation, with optional pre-computed mesh.
 #[derive(Debug, Clone)]
 pub struct BodyOutput {
@@ -34,6 +49,21 @@ pub struct Provenance {
     pub modified: Vec<Rewrite>,
     /// Semantic role assignments for created/surviving entities.
     pub role_assignments: Vec<(KernelId, Role)>,
+    /// Where each result face came from, in `list_faces` order. Only
+    /// booleans fill this in.
+    pub face_origins: Vec<FaceProvenance>,
+}
+
+/// The boolean operand a result face was inherited from, and the role it
+/// carried there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaceProvenance {
+    /// The result face.
+    pub face: KernelId,
+    /// Which operand it matches best.
+    pub origin: FaceOrigin,
+    /// The operand face's role, if the operand's roles were supplied.
+    pub role: Option<Role>,
 }
 
 /// Record of a topological entity with its kernel ID and signature.
@@ -77,12 +107,32 @@ pub enum RewriteReason {
 pub struct Diagnostics {
     /// Warning messages.
     pub warnings: Vec<String>,
+    /// Typed warnings that callers may want to act on.
+    pub operation_warnings: Vec<OperationWarning>,
     /// Time taken for the kernel operation, in milliseconds.
     pub kernel_time_ms: f64,
     /// Time taken for tessellation, in milliseconds.
     pub tessellation_time_ms: f64,
 }
 
+/// A non-fatal condition an operation corrected on its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationWarning {
+    /// The profile was wound clockwise in sketch coordinates and was
+    /// reversed before extruding, so its caps face outward.
+    ClockwiseProfileNormalized,
+}
+
+impl std::fmt::Display for OperationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OperationWarning::ClockwiseProfileNormalized => {
+                write!(f, "Clockwise profile was reversed to counter-clockwise")
+            }
+        }
+    }
+}
+
 /// Errors from modeling operations.
 #[derive(Debug, Clone, thiserror::Error)]
 pub enum OpError {
@@ -1,16 +1,20 @@
 use std::collections::HashMap;
 
-use kernel_fork::{Kernel, KernelId, KernelIntrospect};
+use kernel_fork::{
+    EdgeRenderData, Kernel, KernelError, KernelId, KernelIntrospect, KernelSolidHandle, RenderMesh,
+};
 use kernel_fork::{MockKernel, TruckKernel};
-use modeling_ops::boolean::{execute_boolean, BooleanKind};
+use modeling_ops::boolean::{execute_boolean, execute_boolean_with_operand_roles, BooleanKind};
 use modeling_ops::chamfer::execute_chamfer;
 use modeling_ops::diff::{self, signature_similarity};
-use modeling_ops::extrude::{execute_extrude, execute_symmetric_extrude};
+use modeling_ops::extrude::{
+    execute_extrude, execute_symmetric_extrude, normalize_profile_winding,
+};
 use modeling_ops::fillet::execute_fillet;
-use modeling_ops::revolve::execute_revolve;
+use modeling_ops::revolve::{execute_revolve, execute_revolve_about_edge};
 use modeling_ops::shell::execute_shell;
-use modeling_ops::types::OpError;
-use waffle_types::{ClosedProfile, OutputKey, Role, TopoKind, TopoSignature};
+use modeling_ops::types::{OpError, OperationWarning};
+use waffle_types::{ClosedProfile, FaceOrigin, OutputKey, Role, TopoKind, TopoSignature};
 
 /// Helper: create a face from a rectangular profile.
 fn make_face(kernel: &mut MockKernel) -> KernelId {
@@ -188,11 +192,40 @@ fn extrude_provenance_tracks_face_count() {
 
     assert_eq!(
         result.provenance.role_assignments.len(),
-        6,
-        "All 6 box faces should get roles"
+        18,
+        "All 6 box faces and 12 box edges should get roles"
     );
 }
 
+#[test]
+fn extrude_tags_profile_cap_and_sweep_edges() {
+    let mut kernel = MockKernel::new();
+    let face_id = make_face(&mut kernel);
+
+    let result = execute_extrude(&mut kernel, face_id, [0.0, 0.0, 1.0], 5.0, None).unwrap();
+
+    let profile = result.edges_with_role(&Role::ProfileEdge);
+    let cap = result.edges_with_role(&Role::CapEdge);
+    let sweep = result.edges_with_role(&Role::SweepEdge);
+    assert_eq!(profile.len(), 4, "bottom cap should have 4 profile edges");
+    assert_eq!(cap.len(), 4, "top cap should have 4 cap edges");
+    assert_eq!(sweep.len(), 4, "4 lateral edges come from the sweep");
+
+    // Sweep edges run along the extrude direction.
+    for edge in sweep {
+        let (a, b) = kernel.edge_vertices(edge);
+        let pa = kernel
+            .compute_signature(a, TopoKind::Vertex)
+            .centroid
+            .unwrap();
+        let pb = kernel
+            .compute_signature(b, TopoKind::Vertex)
+            .centroid
+            .unwrap();
+        assert!((pa[0] - pb[0]).abs() < 1e-9 && (pa[1] - pb[1]).abs() < 1e-9);
+    }
+}
+
 #[test]
 fn extrude_with_different_directions() {
     let mut kernel = MockKernel::new();
@@ -215,6 +248,31 @@ fn extrude_invalid_face_returns_error() {
     assert!(matches!(result, Err(OpError::Kernel(_))));
 }
 
+#[test]
+fn normalize_profile_winding_reverses_clockwise_only() {
+    let mut positions = HashMap::new();
+    positions.insert(1, (0.0, 0.0));
+    positions.insert(2, (2.0, 0.0));
+    positions.insert(3, (2.0, 3.0));
+    positions.insert(4, (0.0, 3.0));
+    let ccw = ClosedProfile {
+        entity_ids: vec![1, 2, 3, 4],
+        is_outer: true,
+    };
+    let cw = ClosedProfile {
+        entity_ids: vec![1, 4, 3, 2],
+        is_outer: true,
+    };
+
+    let (profile, warning) = normalize_profile_winding(&ccw, &positions);
+    assert_eq!(profile.entity_ids, ccw.entity_ids);
+    assert_eq!(warning, None);
+
+    let (profile, warning) = normalize_profile_winding(&cw, &positions);
+    assert_eq!(profile.entity_ids, vec![2, 3, 4, 1]);
+    assert_eq!(warning, Some(OperationWarning::ClockwiseProfileNormalized));
+}
+
 // ── Revolve Tests ──────────────────────────────────────────────────────────
 
 #[test]
@@ -295,6 +353,24 @@ fn revolve_invalid_face_returns_error() {
     assert!(matches!(result, Err(OpError::Kernel(_))));
 }
 
+#[test]
+fn revolve_about_edge_rejects_bad_edge_index() {
+    let mut kernel = MockKernel::new();
+    let face_id = make_face(&mut kernel);
+    let handle = kernel.extrude_face(face_id, [0.0, 0.0, 1.0], 5.0).unwrap();
+    let profile = make_face(&mut kernel);
+
+    let result = execute_revolve_about_edge(
+        &mut kernel,
+        profile,
+        &handle,
+        99,
+        std::f64::consts::PI,
+        None,
+    );
+    assert!(matches!(result, Err(OpError::InvalidParameter { .. })));
+}
+
 // ── Boolean Tests ──────────────────────────────────────────────────────────
 
 #[test]
@@ -1116,16 +1192,389 @@ fn truck_chamfer_returns_not_supported() {
 }
 
 #[test]
-fn truck_shell_returns_not_supported() {
+fn truck_shell_hollows_extruded_box() {
     let mut kernel = TruckKernel::new();
     let face_id = make_truck_face(&mut kernel);
     let result = execute_extrude(&mut kernel, face_id, [0.0, 0.0, 1.0], 5.0, None).unwrap();
     let handle = &result.outputs[0].1.handle;
 
     let faces = kernel.list_faces(handle);
-    let shell_result = execute_shell(&mut kernel, handle, &[faces[0]], 0.5);
-    assert!(
-        shell_result.is_err(),
-        "TruckKernel shell should return error"
+    let top = *faces
+        .iter()
+        .find(|&&f| {
+            let normal = kernel.compute_signature(f, TopoKind::Face).normal.unwrap();
+            normal[2] > 0.5
+        })
+        .expect("extruded box should have a top face");
+    let shelled = execute_shell(&mut kernel, handle, &[top], 0.5).unwrap();
+    let shelled = &shelled.outputs[0].1.handle;
+
+    // 2 x 3 x 5 outside, 1 x 2 x 4.5 inside.
+    let mesh = kernel.tessellate(shelled, 0.1).unwrap();
+    let volume = kernel_fork::mesh::signed_volume(&mesh);
+    assert!((volume - 21.0).abs() < 1e-3, "volume {}", volume);
+    assert_eq!(kernel.list_faces(shelled).len(), 2 * faces.len() - 1);
+}
+
+#[test]
+fn truck_revolve_about_box_edge() {
+    let mut kernel = TruckKernel::new();
+    let base = make_truck_face(&mut kernel);
+    let box_result = execute_extrude(&mut kernel, base, [0.0, 0.0, 1.0], 5.0, None).unwrap();
+    let box_handle = box_result.outputs[0].1.handle.clone();
+
+    // The vertical edge at x = 2, y = 0.
+    let vertex_at = |kernel: &TruckKernel, v: KernelId| {
+        kernel
+            .compute_signature(v, TopoKind::Vertex)
+            .centroid
+            .unwrap()
+    };
+    let edge_index = kernel
+        .list_edges(&box_handle)
+        .iter()
+        .position(|&e| {
+            let (a, b) = kernel.edge_vertices(e);
+            [vertex_at(&kernel, a), vertex_at(&kernel, b)]
+                .iter()
+                .all(|p| (p[0] - 2.0).abs() < 1e-9 && p[1].abs() < 1e-9)
+        })
+        .expect("box should have a vertical edge at (2, 0)");
+
+    // A 1 x 5 profile in the y = 0 plane, one unit away from that edge.
+    let profile = ClosedProfile {
+        entity_ids: vec![1, 2, 3, 4],
+        is_outer: true,
+    };
+    let mut positions = HashMap::new();
+    positions.insert(1, (3.0, 0.0));
+    positions.insert(2, (4.0, 0.0));
+    positions.insert(3, (4.0, 5.0));
+    positions.insert(4, (3.0, 5.0));
+    let face_id = kernel
+        .make_faces_from_profiles(
+            &[profile],
+            [0.0, 0.0, 0.0],
+            [0.0, -1.0, 0.0],
+            [1.0, 0.0, 0.0],
+            &positions,
+        )
+        .unwrap()[0];
+
+    let result = execute_revolve_about_edge(
+        &mut kernel,
+        face_id,
+        &box_handle,
+        edge_index,
+        std::f64::consts::FRAC_PI_2,
+        None,
+    )
+    .unwrap();
+
+    let mesh = kernel
+        .tessellate(&result.outputs[0].1.handle, 0.01)
+        .unwrap();
+    let mut max_abs_y: f64 = 0.0;
+    for v in mesh.vertices.chunks_exact(3) {
+        let (dx, dy) = (v[0] as f64 - 2.0, v[1] as f64);
+        let radius = (dx * dx + dy * dy).sqrt();
+        assert!(
+            (1.0 - 1e-4..=2.0 + 1e-4).contains(&radius),
+            "vertex {:?} is {} from the edge axis",
+            v,
+            radius
+        );
+        assert!((-1e-4..=5.0 + 1e-4).contains(&(v[2] as f64)));
+        max_abs_y = max_abs_y.max(dy.abs());
+    }
+    // A quarter turn sweeps the profile from the x axis onto the y axis.
+    assert!((max_abs_y - 2.0).abs() < 1e-3, "max |y| = {}", max_abs_y);
+}
+
+#[test]
+fn truck_plate_minus_prism_traces_face_origins() {
+    let mut kernel = TruckKernel::new();
+
+    // 4 x 4 x 1 plate.
+    let plate_profile = ClosedProfile {
+        entity_ids: vec![1, 2, 3, 4],
+        is_outer: true,
+    };
+    let mut positions = HashMap::new();
+    positions.insert(1, (0.0, 0.0));
+    positions.insert(2, (4.0, 0.0));
+    positions.insert(3, (4.0, 4.0));
+    positions.insert(4, (0.0, 4.0));
+    let plate_face = kernel
+        .make_faces_from_profiles(
+            &[plate_profile],
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            &positions,
+        )
+        .unwrap()[0];
+    let plate = execute_extrude(&mut kernel, plate_face, [0.0, 0.0, 1.0], 1.0, None).unwrap();
+
+    // Octagonal pin through the middle, poking out of both sides.
+    let pin_profile = ClosedProfile {
+        entity_ids: (10..18).collect(),
+        is_outer: true,
+    };
+    let mut positions = HashMap::new();
+    for k in 0..8u32 {
+        let angle = std::f64::consts::TAU * (k as f64 + 0.5) / 8.0;
+        positions.insert(10 + k, (2.0 + 0.5 * angle.cos(), 2.0 + 0.5 * angle.sin()));
+    }
+    let pin_face = kernel
+        .make_faces_from_profiles(
+            &[pin_profile],
+            [0.0, 0.0, -0.5],
+            [0.0, 0.0, 1.0],
+            [1.0, 0.0, 0.0],
+            &positions,
+        )
+        .unwrap()[0];
+    let pin = execute_extrude(&mut kernel, pin_face, [0.0, 0.0, 1.0], 2.0, None).unwrap();
+
+    let result = execute_boolean_with_operand_roles(
+        &mut kernel,
+        &plate.outputs[0].1.handle,
+        &pin.outputs[0].1.handle,
+        BooleanKind::Subtract,
+        &plate.provenance.role_assignments,
+        &pin.provenance.role_assignments,
+    )
+    .unwrap();
+
+    let origins = &result.provenance.face_origins;
+    assert_eq!(origins.len(), 6 + 8, "plate faces plus the hole walls");
+    let mut hole_walls = 0;
+    for provenance in origins {
+        let sig = kernel.compute_signature(provenance.face, TopoKind::Face);
+        let c = sig.centroid.unwrap();
+        let from_axis = ((c[0] - 2.0).powi(2) + (c[1] - 2.0).powi(2)).sqrt();
+        if from_axis < 0.6 && sig.normal.unwrap()[2].abs() < 1e-6 {
+            hole_walls += 1;
+            assert_eq!(provenance.origin, FaceOrigin::FromToolB);
+            assert!(matches!(provenance.role, Some(Role::SideFace { .. })));
+        } else {
+            assert_eq!(provenance.origin, FaceOrigin::FromToolA);
+            assert!(provenance.role.is_some());
+        }
+    }
+    assert_eq!(hole_walls, 8);
+}
+
+/// MockKernel whose subtract results report their first face as new
+/// geometry, the way a real cut face matches neither operand well.
+struct CutFaceKernel {
+    inner: MockKernel,
+    cut_faces: Vec<KernelId>,
+    cut_signature: TopoSignature,
+}
+
+impl Kernel for CutFaceKernel {
+    fn extrude_face(
+        &mut self,
+        face: KernelId,
+        direction: [f64; 3],
+        depth: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.extrude_face(face, direction, depth)
+    }
+
+    fn revolve_face(
+        &mut self,
+        face: KernelId,
+        axis_origin: [f64; 3],
+        axis_direction: [f64; 3],
+        angle: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner
+            .revolve_face(face, axis_origin, axis_direction, angle)
+    }
+
+    fn boolean_union(
+        &mut self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.boolean_union(a, b)
+    }
+
+    fn boolean_subtract(
+        &mut self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        let result = self.inner.boolean_subtract(a, b)?;
+        self.cut_faces.push(self.inner.list_faces(&result)[0]);
+        Ok(result)
+    }
+
+    fn boolean_intersect(
+        &mut self,
+        a: &KernelSolidHandle,
+        b: &KernelSolidHandle,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.boolean_intersect(a, b)
+    }
+
+    fn fillet_edges(
+        &mut self,
+        solid: &KernelSolidHandle,
+        edges: &[KernelId],
+        radius: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.fillet_edges(solid, edges, radius)
+    }
+
+    fn chamfer_edges(
+        &mut self,
+        solid: &KernelSolidHandle,
+        edges: &[KernelId],
+        distance: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.chamfer_edges(solid, edges, distance)
+    }
+
+    fn shell(
+        &mut self,
+        solid: &KernelSolidHandle,
+        faces_to_remove: &[KernelId],
+        thickness: f64,
+    ) -> Result<KernelSolidHandle, KernelError> {
+        self.inner.shell(solid, faces_to_remove, thickness)
+    }
+
+    fn tessellate(
+        &mut self,
+        solid: &KernelSolidHandle,
+        tolerance: f64,
+    ) -> Result<RenderMesh, KernelError> {
+        self.inner.tessellate(solid, tolerance)
+    }
+
+    fn extract_edges(
+        &mut self,
+        solid: &KernelSolidHandle,
+        tolerance: f64,
+    ) -> Result<EdgeRenderData, KernelError> {
+        self.inner.extract_edges(solid, tolerance)
+    }
+
+    fn make_faces_from_profiles(
+        &mut self,
+        profiles: &[ClosedProfile],
+        plane_origin: [f64; 3],
+        plane_normal: [f64; 3],
+        plane_x_axis: [f64; 3],
+        positions: &HashMap<u32, (f64, f64)>,
+    ) -> Result<Vec<KernelId>, KernelError> {
+        self.inner.make_faces_from_profiles(
+            profiles,
+            plane_origin,
+            plane_normal,
+            plane_x_axis,
+            positions,
+        )
+    }
+}
+
+impl KernelIntrospect for CutFaceKernel {
+    fn list_faces(&self, solid: &KernelSolidHandle) -> Vec<KernelId> {
+        self.inner.list_faces(solid)
+    }
+
+    fn list_edges(&self, solid: &KernelSolidHandle) -> Vec<KernelId> {
+        self.inner.list_edges(solid)
+    }
+
+    fn list_vertices(&self, solid: &KernelSolidHandle) -> Vec<KernelId> {
+        self.inner.list_vertices(solid)
+    }
+
+    fn face_edges(&self, face: KernelId) -> Vec<KernelId> {
+        self.inner.face_edges(face)
+    }
+
+    fn edge_faces(&self, edge: KernelId) -> Vec<KernelId> {
+        self.inner.edge_faces(edge)
+    }
+
+    fn edge_vertices(&self, edge: KernelId) -> (KernelId, KernelId) {
+        self.inner.edge_vertices(edge)
+    }
+
+    fn face_neighbors(&self, face: KernelId) -> Vec<KernelId> {
+        self.inner.face_neighbors(face)
+    }
+
+    fn compute_signature(&self, entity: KernelId, kind: TopoKind) -> TopoSignature {
+        if kind == TopoKind::Face && self.cut_faces.contains(&entity) {
+            return self.cut_signature.clone();
+        }
+        self.inner.compute_signature(entity, kind)
+    }
+
+    fn compute_all_signatures(
+        &self,
+        solid: &KernelSolidHandle,
+        kind: TopoKind,
+    ) -> Vec<(KernelId, TopoSignature)> {
+        self.inner
+            .compute_all_signatures(solid, kind)
+            .into_iter()
+            .map(|(id, sig)| {
+                if kind == TopoKind::Face && self.cut_faces.contains(&id) {
+                    (id, self.cut_signature.clone())
+                } else {
+                    (id, sig)
+                }
+            })
+            .collect()
+    }
+}
+
+#[test]
+fn boolean_subtract_numbers_cut_face_with_the_operand_it_resembles() {
+    let mut kernel = CutFaceKernel {
+        inner: MockKernel::new(),
+        cut_faces: Vec::new(),
+        cut_signature: TopoSignature::empty(),
+    };
+    let face_a = make_face(&mut kernel.inner);
+    let handle_a = kernel.extrude_face(face_a, [0.0, 0.0, 1.0], 2.0).unwrap();
+    let face_b = make_face(&mut kernel.inner);
+    let handle_b = kernel.extrude_face(face_b, [0.0, 0.0, 1.0], 10.0).unwrap();
+
+    // A small curved face just under the tool's top: too unlike any operand
+    // face to inherit a role, but much nearer the tool than the body.
+    let side = 6.0_f64.sqrt();
+    kernel.cut_signature = TopoSignature {
+        surface_type: Some("cylindrical".to_string()),
+        area: Some(0.01),
+        centroid: Some([side / 2.0, side / 2.0, 9.0]),
+        normal: Some([0.0, 0.0, 1.0]),
+        bbox: None,
+        adjacency_hash: None,
+        length: None,
+    };
+
+    let result = execute_boolean(&mut kernel, &handle_a, &handle_b, BooleanKind::Subtract).unwrap();
+    let provenance = &result.provenance;
+    assert_eq!(
+        provenance.face_origins[0].origin,
+        FaceOrigin::FromIntersection
     );
+
+    let roles: Vec<Role> = provenance
+        .role_assignments
+        .iter()
+        .map(|(_, role)| role.clone())
+        .collect();
+    let mut expected = vec![Role::BooleanBodyBFace { index: 0 }];
+    expected.extend((0..5).map(|index| Role::BooleanBodyAFace { index }));
+    assert_eq!(roles, expected);
 }
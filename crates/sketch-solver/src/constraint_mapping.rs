@@ -1,8 +1,8 @@
 use slvs::constraint::{
     Angle, ArcLineTangent, AtMidpoint, Diameter, EqPtLnDistances, EqualAngle, EqualLengthLines,
-    EqualRadius, Horizontal, LengthRatio, Parallel, Perpendicular, PointsCoincident,
-    PtLineDistance, PtOnCircle, PtOnLine, PtPtDistance, SymmetricHoriz, SymmetricLine,
-    SymmetricVert, Vertical, WhereDragged,
+    EqualRadius, Horizontal, LengthDifference, LengthRatio, Parallel, Perpendicular,
+    PointsCoincident, PtLineDistance, PtOnCircle, PtOnLine, PtPtDistance, SymmetricHoriz,
+    SymmetricLine, SymmetricVert, Vertical, WhereDragged,
 };
 
 use crate::entity_mapping::SketchToSlvs;
@@ -10,13 +10,40 @@ use crate::types::{EntityKind, SketchConstraint};
 
 impl SketchToSlvs {
     /// Add all sketch constraints to the slvs system.
-    pub fn add_constraints(&mut self, constraints: &[SketchConstraint]) {
+    ///
+    /// Fails, naming the problem, on a constraint that can't be expressed,
+    /// such as `EqualAngles` over fewer than three lines.
+    pub fn add_constraints(&mut self, constraints: &[SketchConstraint]) -> Result<(), String> {
         for constraint in constraints {
-            self.add_constraint(constraint);
+            self.add_constraint(constraint, constraints)?;
         }
+        Ok(())
     }
 
-    fn add_constraint(&mut self, constraint: &SketchConstraint) {
+    /// Whether `line` meets `arc` at the arc's end point rather than its
+    /// start, by sharing the point or through a `Coincident` constraint.
+    fn tangent_at_arc_end(&self, arc: u32, line: u32, constraints: &[SketchConstraint]) -> bool {
+        let (arc_start, arc_end) = self.endpoints[&arc];
+        let (line_start, line_end) = self.endpoints[&line];
+        let touches = |p: u32| {
+            [line_start, line_end].iter().any(|&q| {
+                p == q
+                    || constraints.iter().any(|c| match c {
+                        SketchConstraint::Coincident { point_a, point_b } => {
+                            (*point_a, *point_b) == (p, q) || (*point_a, *point_b) == (q, p)
+                        }
+                        _ => false,
+                    })
+            })
+        };
+        touches(arc_end) && !touches(arc_start)
+    }
+
+    fn add_constraint(
+        &mut self,
+        constraint: &SketchConstraint,
+        constraints: &[SketchConstraint],
+    ) -> Result<(), String> {
         match constraint {
             SketchConstraint::Coincident { point_a, point_b } => {
                 let pa = self.point_handles[point_a];
@@ -67,13 +94,14 @@ impl SketchToSlvs {
                 match curve_kind {
                     EntityKind::Arc => {
                         let arc = self.arc_handles[curve];
+                        let at_end = self.tangent_at_arc_end(*curve, *line, constraints);
                         self.system
                             .constrain(ArcLineTangent::new(
                                 self.group,
                                 self.workplane,
                                 arc,
                                 line_handle,
-                                false,
+                                at_end,
                             ))
                             .expect("failed to add arc-line tangent constraint");
                     }
@@ -352,6 +380,33 @@ impl SketchToSlvs {
                     .expect("failed to add equal angle constraint");
             }
 
+            SketchConstraint::EqualAngles { lines } => {
+                // The angle at joint i is between line i and line i+1 (the
+                // last joint wraps to the first line); tie each joint to the
+                // next, so n-1 constraints cover all n joints.
+                let n = lines.len();
+                if n < 3 {
+                    return Err(format!(
+                        "EqualAngles needs a closed chain of at least 3 lines, got {}",
+                        n
+                    ));
+                }
+                let handles: Vec<_> = lines.iter().map(|l| self.line_handles[l]).collect();
+                for i in 0..n - 1 {
+                    self.system
+                        .constrain(EqualAngle::new(
+                            self.group,
+                            handles[i],
+                            handles[i + 1],
+                            handles[i + 1],
+                            handles[(i + 2) % n],
+                            Some(self.workplane),
+                            false,
+                        ))
+                        .expect("failed to add equal angle constraint");
+                }
+            }
+
             SketchConstraint::Ratio {
                 entity_a,
                 entity_b,
@@ -370,6 +425,24 @@ impl SketchToSlvs {
                     .expect("failed to add length ratio constraint");
             }
 
+            SketchConstraint::LengthDifference {
+                line_a,
+                line_b,
+                value,
+            } => {
+                let la = self.line_handles[line_a];
+                let lb = self.line_handles[line_b];
+                self.system
+                    .constrain(LengthDifference::new(
+                        self.group,
+                        la,
+                        lb,
+                        *value,
+                        Some(self.workplane),
+                    ))
+                    .expect("failed to add length difference constraint");
+            }
+
             SketchConstraint::EqualPointToLine {
                 point_a,
                 point_b,
@@ -397,6 +470,20 @@ impl SketchToSlvs {
                 // directly exposed in the sketch entity model. This constraint
                 // is reserved for 3D normal alignment. Skip in 2D sketch context.
             }
+
+            SketchConstraint::Concentric { entity_a, entity_b } => {
+                let ca = self.point_handles[&self.centers[entity_a]];
+                let cb = self.point_handles[&self.centers[entity_b]];
+                self.system
+                    .constrain(PointsCoincident::new(
+                        self.group,
+                        ca,
+                        cb,
+                        Some(self.workplane),
+                    ))
+                    .expect("failed to add concentric constraint");
+            }
         }
+        Ok(())
     }
 }
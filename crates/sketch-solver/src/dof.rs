@@ -0,0 +1,77 @@
+use crate::solver::solve_sketch;
+use crate::types::{Sketch, SketchConstraint, SketchEntity, SolveStatus};
+
+/// A sketch's remaining degrees of freedom, split by what they move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DofSummary {
+    /// Rigid-body freedom: the sketch can slide (up to 2) or spin (1) as a
+    /// whole without changing shape.
+    pub position: u32,
+    /// Freedom that changes the shape itself.
+    pub shape: u32,
+}
+
+impl DofSummary {
+    /// All remaining degrees of freedom.
+    pub fn total(&self) -> u32 {
+        self.position + self.shape
+    }
+}
+
+/// Solve the sketch and split its DOF into rigid-body placement and shape.
+///
+/// Translation counts as anchored once any point is dragged (fixed in
+/// place). Rotation counts as anchored by a horizontal, vertical or
+/// axis-symmetry constraint, or by two dragged points; a sketch with fewer
+/// than two points has no rotation to anchor. A sketch that fails to solve
+/// or is over-constrained reports no freedom.
+pub fn dof_summary(sketch: &Sketch) -> DofSummary {
+    let total = match solve_sketch(sketch).status {
+        SolveStatus::UnderConstrained { dof } => dof,
+        _ => 0,
+    };
+
+    let mut dragged: Vec<u32> = sketch
+        .constraints
+        .iter()
+        .filter_map(|c| match c {
+            SketchConstraint::Dragged { point } => Some(*point),
+            _ => None,
+        })
+        .collect();
+    dragged.sort_unstable();
+    dragged.dedup();
+    let points = sketch
+        .entities
+        .iter()
+        .filter(|e| matches!(e, SketchEntity::Point { .. }))
+        .count();
+    let oriented = sketch.constraints.iter().any(|c| {
+        matches!(
+            c,
+            SketchConstraint::Horizontal { .. }
+                | SketchConstraint::Vertical { .. }
+                | SketchConstraint::SymmetricH { .. }
+                | SketchConstraint::SymmetricV { .. }
+        )
+    });
+
+    let translation = if dragged.is_empty() { 2 } else { 0 };
+    let rotation = if points < 2 || oriented || dragged.len() >= 2 {
+        0
+    } else {
+        1
+    };
+    let position = (translation + rotation).min(total);
+    DofSummary {
+        position,
+        shape: total - position,
+    }
+}
+
+/// How many more independent dimensions or geometric constraints would fully
+/// define the sketch's shape, ignoring where it sits in the plane. See
+/// [`dof_summary`].
+pub fn constraints_needed(sketch: &Sketch) -> usize {
+    dof_summary(sketch).shape as usize
+}
@@ -20,6 +20,10 @@ pub struct SketchToSlvs {
     pub line_handles: HashMap<u32, EntityHandle<LineSegment>>,
     pub circle_handles: HashMap<u32, EntityHandle<SlvsCircle>>,
     pub arc_handles: HashMap<u32, EntityHandle<ArcOfCircle>>,
+    /// Start and end point IDs of every line and arc.
+    pub endpoints: HashMap<u32, (u32, u32)>,
+    /// Center point ID of every circle and arc.
+    pub centers: HashMap<u32, u32>,
     pub distance_handles: HashMap<u32, EntityHandle<Distance>>,
     pub normal_on_wp: Option<EntityHandle<Normal>>,
     pub entity_types: HashMap<u32, EntityKind>,
@@ -63,6 +67,8 @@ impl SketchToSlvs {
             line_handles: HashMap::new(),
             circle_handles: HashMap::new(),
             arc_handles: HashMap::new(),
+            endpoints: HashMap::new(),
+            centers: HashMap::new(),
             distance_handles: HashMap::new(),
             normal_on_wp: None,
             entity_types: HashMap::new(),
@@ -117,6 +123,7 @@ impl SketchToSlvs {
                         .sketch(LineSegment::new(self.group, start, end))
                         .expect("failed to add line");
                     self.line_handles.insert(*id, handle);
+                    self.endpoints.insert(*id, (*start_id, *end_id));
                     self.entity_types.insert(*id, EntityKind::Line);
                 }
                 SketchEntity::Circle {
@@ -137,6 +144,7 @@ impl SketchToSlvs {
                         .sketch(SlvsCircle::new(self.group, wp_normal, center, dist))
                         .expect("failed to add circle");
                     self.circle_handles.insert(*id, handle);
+                    self.centers.insert(*id, *center_id);
                     self.entity_types.insert(*id, EntityKind::Circle);
                 }
                 SketchEntity::Arc {
@@ -160,6 +168,8 @@ impl SketchToSlvs {
                         ))
                         .expect("failed to add arc");
                     self.arc_handles.insert(*id, handle);
+                    self.endpoints.insert(*id, (*start_id, *end_id));
+                    self.centers.insert(*id, *center_id);
                     self.entity_types.insert(*id, EntityKind::Arc);
                 }
                 SketchEntity::Point { .. } => {} // already handled
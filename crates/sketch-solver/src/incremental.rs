@@ -0,0 +1,104 @@
+//! Adding constraints one at a time with immediate over-constraint feedback.
+
+use crate::solver::{solve_sketch_subsystems, solve_system};
+use crate::subsystems::{constraint_references, partition};
+use crate::types::{Sketch, SketchConstraint, SolveStatus};
+
+/// Why a constraint was refused by
+/// [`add_constraint_checked`](SketchConstraintExt::add_constraint_checked).
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum ConstraintError {
+    #[error("{constraint} would over-constrain the sketch")]
+    OverConstrained { constraint: String },
+
+    #[error("{constraint} is redundant: it removes no degrees of freedom")]
+    Redundant { constraint: String },
+
+    #[error("constraint references unknown entity {id}")]
+    UnknownEntity { id: u32 },
+
+    #[error("sketch failed to solve with the new constraint: {reason}")]
+    SolveFailed { reason: String },
+}
+
+/// Checked constraint editing for [`Sketch`], which lives in `waffle-types`
+/// and so cannot carry solver methods itself.
+pub trait SketchConstraintExt {
+    /// Add `constraint` only if it removes at least one degree of freedom
+    /// without making the sketch inconsistent, and return the sketch's new
+    /// DOF.
+    ///
+    /// The before/after comparison is made on the subsystem the constraint
+    /// touches alone, so unrelated geometry does not mask a redundant
+    /// constraint. On success the constraint is appended and the sketch's
+    /// solve status and positions are updated; on error it is left as is.
+    fn add_constraint_checked(
+        &mut self,
+        constraint: SketchConstraint,
+    ) -> Result<usize, ConstraintError>;
+}
+
+impl SketchConstraintExt for Sketch {
+    fn add_constraint_checked(
+        &mut self,
+        constraint: SketchConstraint,
+    ) -> Result<usize, ConstraintError> {
+        let refs = constraint_references(&constraint);
+        if let Some(&id) = refs.iter().find(|&&id| self.entities.iter().all(|e| e.id() != id)) {
+            return Err(ConstraintError::UnknownEntity { id });
+        }
+        let describe = || format!("{:?}", constraint);
+
+        let mut trial = self.clone();
+        trial.constraints.push(constraint.clone());
+        let (solved, _) = solve_sketch_subsystems(&trial);
+        let dof = match &solved.status {
+            SolveStatus::FullyConstrained => 0,
+            SolveStatus::UnderConstrained { dof } => *dof,
+            SolveStatus::OverConstrained { .. } => {
+                return Err(ConstraintError::OverConstrained {
+                    constraint: describe(),
+                });
+            }
+            SolveStatus::SolveFailed { reason } => {
+                return Err(ConstraintError::SolveFailed {
+                    reason: reason.clone(),
+                });
+            }
+        };
+
+        // The new constraint is the last one in its subsystem, since
+        // partitioning keeps the original order.
+        let touched = refs.first().and_then(|&first| {
+            partition(&trial.entities, &trial.constraints)
+                .into_iter()
+                .find(|s| s.entities.iter().any(|e| e.id() == first))
+        });
+        if let Some(mut touched) = touched {
+            let after = subsystem_dof(&solve_system(&touched.entities, &touched.constraints).1);
+            touched.constraints.pop();
+            let before = subsystem_dof(&solve_system(&touched.entities, &touched.constraints).1);
+            if let (Some(before), Some(after)) = (before, after) {
+                if after >= before {
+                    return Err(ConstraintError::Redundant {
+                        constraint: describe(),
+                    });
+                }
+            }
+        }
+
+        self.constraints.push(constraint);
+        self.solve_status = solved.status;
+        self.solved_positions = solved.positions;
+        self.solved_profiles = solved.profiles;
+        Ok(dof as usize)
+    }
+}
+
+fn subsystem_dof(status: &SolveStatus) -> Option<u32> {
+    match status {
+        SolveStatus::FullyConstrained => Some(0),
+        SolveStatus::UnderConstrained { dof } => Some(*dof),
+        _ => None,
+    }
+}
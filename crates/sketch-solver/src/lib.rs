@@ -1,10 +1,22 @@
 pub mod constraint_mapping;
+pub mod dof;
 pub mod entity_mapping;
+pub mod incremental;
 pub mod profiles;
+pub mod sensitivity;
 pub mod solver;
 pub mod status;
+pub mod subsystems;
+pub mod suggest;
 pub mod types;
 
-pub use profiles::extract_profiles;
-pub use solver::solve_sketch;
+pub use dof::{constraints_needed, dof_summary, DofSummary};
+pub use incremental::{ConstraintError, SketchConstraintExt};
+pub use profiles::{extract_profiles, ProfileError, SketchProfileExt};
+pub use sensitivity::solve_sketch_with_sensitivity;
+pub use solver::{
+    diagnose_conflicts, solve_sketch, solve_sketch_budgeted, solve_sketch_subsystems,
+    solve_sketch_warm, solve_sketches_parallel, SolveError,
+};
+pub use suggest::suggest_constraints;
 pub use types::*;
@@ -1,6 +1,98 @@
 use std::collections::HashMap;
 
-use crate::types::{ClosedProfile, SketchEntity};
+use crate::types::{ClosedProfile, Sketch, SketchEntity};
+
+/// Why a sketch's curves do not chain into closed loops.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ProfileError {
+    #[error("point {point} ends entity {entity} but no other curve continues from it")]
+    DanglingEndpoint { point: u32, entity: u32 },
+
+    #[error("point {point} joins {count} curves, so the loop through it is ambiguous")]
+    Branching { point: u32, count: usize },
+}
+
+/// Loop extraction for [`Sketch`] that explains failures, for fixing
+/// sketches that produce no profile.
+pub trait SketchProfileExt {
+    /// Chain the sketch's non-construction curves into closed loops.
+    ///
+    /// Each loop lists entity IDs in walking order, every curve sharing an
+    /// endpoint with the next and the last with the first. Circles are loops
+    /// of their own. Unlike [`extract_profiles`] this ignores geometry, so it
+    /// does not split networks where three or more curves meet; those are
+    /// reported as [`ProfileError::Branching`].
+    fn closed_loops(&self) -> Result<Vec<Vec<u32>>, ProfileError>;
+}
+
+impl SketchProfileExt for Sketch {
+    fn closed_loops(&self) -> Result<Vec<Vec<u32>>, ProfileError> {
+        let mut loops = Vec::new();
+        // (entity, start point, end point) for every line and arc.
+        let mut curves: Vec<(u32, u32, u32)> = Vec::new();
+        for entity in &self.entities {
+            match *entity {
+                SketchEntity::Circle {
+                    id,
+                    construction: false,
+                    ..
+                } => loops.push(vec![id]),
+                SketchEntity::Line {
+                    id,
+                    start_id,
+                    end_id,
+                    construction: false,
+                }
+                | SketchEntity::Arc {
+                    id,
+                    start_id,
+                    end_id,
+                    construction: false,
+                    ..
+                } => curves.push((id, start_id, end_id)),
+                _ => {}
+            }
+        }
+
+        let mut incident: HashMap<u32, Vec<usize>> = HashMap::new();
+        for (i, &(_, start, end)) in curves.iter().enumerate() {
+            incident.entry(start).or_default().push(i);
+            incident.entry(end).or_default().push(i);
+        }
+        for &(entity, start, end) in &curves {
+            for point in [start, end] {
+                match incident[&point].len() {
+                    1 => return Err(ProfileError::DanglingEndpoint { point, entity }),
+                    2 => {}
+                    count => return Err(ProfileError::Branching { point, count }),
+                }
+            }
+        }
+
+        // Every point now joins exactly two curves, so each walk returns to
+        // where it started.
+        let mut visited = vec![false; curves.len()];
+        for (first, &(entity, origin, end)) in curves.iter().enumerate() {
+            if visited[first] {
+                continue;
+            }
+            visited[first] = true;
+            let mut chain = vec![entity];
+            let (mut current, mut at) = (first, end);
+            while at != origin {
+                let pair = &incident[&at];
+                let next = if pair[0] == current { pair[1] } else { pair[0] };
+                let (next_entity, start, end) = curves[next];
+                visited[next] = true;
+                chain.push(next_entity);
+                at = if start == at { end } else { start };
+                current = next;
+            }
+            loops.push(chain);
+        }
+        Ok(loops)
+    }
+}
 
 /// Extract closed profiles from solved sketch geometry.
 ///
@@ -0,0 +1,88 @@
+use crate::solver::{solve_sketch, solve_sketch_warm};
+use crate::types::{Sketch, SketchConstraint, SolveStatus, SolvedSketch};
+
+/// Relative step used for the central difference.
+const RELATIVE_STEP: f64 = 1e-4;
+
+/// Solve a sketch and report how each point moves per unit change of one
+/// dimension.
+///
+/// `wrt_constraint` indexes `sketch.constraints` and must be a dimensional
+/// constraint (distance, angle, radius, diameter, ratio or length
+/// difference). slvs does not expose its factored Jacobian, so the
+/// derivative is taken by central difference: the sketch is re-solved from
+/// the converged positions with the dimension nudged either way. Returns no
+/// derivatives when the constraint is not dimensional or any of the solves
+/// fails.
+pub fn solve_sketch_with_sensitivity(
+    sketch: &Sketch,
+    wrt_constraint: usize,
+) -> (SolvedSketch, Vec<(u32, (f64, f64))>) {
+    let solved = solve_sketch(sketch);
+    let Some(value) = sketch.constraints.get(wrt_constraint).and_then(dimension) else {
+        return (solved, Vec::new());
+    };
+    if !converged(&solved.status) {
+        return (solved, Vec::new());
+    }
+
+    let step = RELATIVE_STEP * value.abs().max(1.0);
+    let perturbed = |delta: f64| {
+        let mut trial = sketch.clone();
+        if let Some(v) = trial
+            .constraints
+            .get_mut(wrt_constraint)
+            .and_then(dimension_mut)
+        {
+            *v = value + delta;
+        }
+        solve_sketch_warm(&trial, &solved.positions)
+    };
+    let (plus, minus) = (perturbed(step), perturbed(-step));
+    if !converged(&plus.status) || !converged(&minus.status) {
+        return (solved, Vec::new());
+    }
+
+    let mut derivatives: Vec<(u32, (f64, f64))> = solved
+        .positions
+        .keys()
+        .filter_map(|id| {
+            let (px, py) = plus.positions.get(id)?;
+            let (mx, my) = minus.positions.get(id)?;
+            Some((*id, ((px - mx) / (2.0 * step), (py - my) / (2.0 * step))))
+        })
+        .collect();
+    derivatives.sort_by_key(|(id, _)| *id);
+    (solved, derivatives)
+}
+
+fn converged(status: &SolveStatus) -> bool {
+    matches!(
+        status,
+        SolveStatus::FullyConstrained | SolveStatus::UnderConstrained { .. }
+    )
+}
+
+fn dimension(constraint: &SketchConstraint) -> Option<f64> {
+    match constraint {
+        SketchConstraint::Distance { value, .. }
+        | SketchConstraint::Radius { value, .. }
+        | SketchConstraint::Diameter { value, .. }
+        | SketchConstraint::Ratio { value, .. }
+        | SketchConstraint::LengthDifference { value, .. } => Some(*value),
+        SketchConstraint::Angle { value_degrees, .. } => Some(*value_degrees),
+        _ => None,
+    }
+}
+
+fn dimension_mut(constraint: &mut SketchConstraint) -> Option<&mut f64> {
+    match constraint {
+        SketchConstraint::Distance { value, .. }
+        | SketchConstraint::Radius { value, .. }
+        | SketchConstraint::Diameter { value, .. }
+        | SketchConstraint::Ratio { value, .. }
+        | SketchConstraint::LengthDifference { value, .. } => Some(value),
+        SketchConstraint::Angle { value_degrees, .. } => Some(value_degrees),
+        _ => None,
+    }
+}
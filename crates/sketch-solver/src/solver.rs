@@ -1,21 +1,154 @@
+use rayon::prelude::*;
 use slvs::entity::Point;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 
 use crate::entity_mapping::SketchToSlvs;
 use crate::profiles::extract_profiles;
 use crate::status::classify_status;
-use crate::types::{Sketch, SolveStatus, SolvedSketch};
+use crate::subsystems::{partition, Subsystem};
+use crate::types::{Sketch, SketchConstraint, SketchEntity, SolveStatus, SolvedSketch};
 
 /// Solve a sketch: map entities/constraints to slvs, run solver, extract results.
 pub fn solve_sketch(sketch: &Sketch) -> SolvedSketch {
-    let mut mapping = SketchToSlvs::new();
-    mapping.add_entities(&sketch.entities);
-    mapping.add_constraints(&sketch.constraints);
+    solve_sketch_subsystems(sketch).0
+}
 
-    let result = mapping.system.solve(&mapping.group);
-    let status = classify_status(result);
+/// Solve a sketch starting from the `initial` point positions, such as the
+/// previous solution while a point is dragged, instead of the coordinates
+/// stored on its entities.
+///
+/// Points missing from `initial` start from their stored coordinates.
+/// Where the constraints allow more than one solution, the solver settles
+/// on the one nearest its starting point, so seeding from the last
+/// solution keeps the sketch from jumping to another.
+pub fn solve_sketch_warm(sketch: &Sketch, initial: &HashMap<u32, (f64, f64)>) -> SolvedSketch {
+    let mut seeded = sketch.clone();
+    for entity in &mut seeded.entities {
+        if let SketchEntity::Point { id, x, y, .. } = entity {
+            if let Some(&(sx, sy)) = initial.get(id) {
+                (*x, *y) = (sx, sy);
+            }
+        }
+    }
+    solve_sketch(&seeded)
+}
+
+/// Solve many independent sketches concurrently on rayon's global pool.
+///
+/// Results are in the same order as `sketches` and match what
+/// [`solve_sketch`] returns for each one on its own.
+pub fn solve_sketches_parallel(sketches: &[Sketch]) -> Vec<SolvedSketch> {
+    sketches.par_iter().map(solve_sketch).collect()
+}
+
+/// Solve a sketch one independent subsystem at a time.
+///
+/// Returns the combined result along with the number of subsystems solved.
+/// DOF is summed across subsystems, as are the conflicting constraints of
+/// over-constrained ones; any failure takes precedence over an
+/// over-constraint, which takes precedence over a successful solve.
+pub fn solve_sketch_subsystems(sketch: &Sketch) -> (SolvedSketch, usize) {
+    let subsystems = partition(&sketch.entities, &sketch.constraints);
+    let solved = solve_partitioned(sketch, &subsystems, None)
+        .unwrap_or_else(|_| unreachable!("a solve without a deadline can't time out"));
+    (solved, subsystems.len())
+}
+
+/// Why [`solve_sketch_budgeted`] stopped short of solving the whole sketch.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum SolveError {
+    #[error("solve ran out of time after {solved} of {subsystems} subsystems")]
+    Timeout { solved: usize, subsystems: usize },
+}
+
+/// Solve `sketch` and store the result on it, giving up once `time_budget`
+/// has run out, and return the new status.
+///
+/// A single slvs solve can't be interrupted, so the budget is checked after
+/// each independent subsystem, and at least one is always solved. On
+/// timeout the sketch's solved positions hold the subsystems solved so far
+/// and the stored coordinates of the rest, its profiles are cleared and
+/// its status is `SolveFailed`.
+pub fn solve_sketch_budgeted(
+    sketch: &mut Sketch,
+    time_budget: Option<Duration>,
+) -> Result<SolveStatus, SolveError> {
+    let deadline = time_budget.map(|budget| Instant::now() + budget);
+    let subsystems = partition(&sketch.entities, &sketch.constraints);
+    match solve_partitioned(sketch, &subsystems, deadline) {
+        Ok(solved) => {
+            sketch.solve_status = solved.status.clone();
+            sketch.solved_positions = solved.positions;
+            sketch.solved_profiles = solved.profiles;
+            Ok(solved.status)
+        }
+        Err((mut positions, solved)) => {
+            let error = SolveError::Timeout {
+                solved,
+                subsystems: subsystems.len(),
+            };
+            for entity in &sketch.entities {
+                if let SketchEntity::Point { id, x, y, .. } = entity {
+                    positions.entry(*id).or_insert((*x, *y));
+                }
+            }
+            sketch.solve_status = SolveStatus::SolveFailed {
+                reason: error.to_string(),
+            };
+            sketch.solved_positions = positions;
+            sketch.solved_profiles = Vec::new();
+            Err(error)
+        }
+    }
+}
+
+/// Solve the subsystems in turn. If `deadline` passes with subsystems
+/// left, stop and return the positions solved so far along with how many
+/// subsystems they cover.
+fn solve_partitioned(
+    sketch: &Sketch,
+    subsystems: &[Subsystem],
+    deadline: Option<Instant>,
+) -> Result<SolvedSketch, (HashMap<u32, (f64, f64)>, usize)> {
+    let mut positions = HashMap::new();
+    let mut dof = 0;
+    let mut conflicts = Vec::new();
+    let mut over_constrained = false;
+    let mut failed = None;
+    for (i, subsystem) in subsystems.iter().enumerate() {
+        let (solved, status) = solve_system(&subsystem.entities, &subsystem.constraints);
+        positions.extend(solved);
+        match status {
+            SolveStatus::FullyConstrained => {}
+            SolveStatus::UnderConstrained { dof: d } => dof += d,
+            SolveStatus::OverConstrained { .. } => {
+                over_constrained = true;
+                let dependent = dependent_constraints(&subsystem.entities, &subsystem.constraints);
+                conflicts.extend(
+                    dependent
+                        .into_iter()
+                        .map(|i| subsystem.constraint_indices[i] as u32),
+                );
+            }
+            status @ SolveStatus::SolveFailed { .. } => {
+                failed.get_or_insert(status);
+            }
+        }
+        let more = i + 1 < subsystems.len();
+        if more && deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return Err((positions, i + 1));
+        }
+    }
 
-    let positions = extract_positions(&mapping);
+    conflicts.sort_unstable();
+    let status = failed.unwrap_or(if over_constrained {
+        SolveStatus::OverConstrained { conflicts }
+    } else if dof == 0 {
+        SolveStatus::FullyConstrained
+    } else {
+        SolveStatus::UnderConstrained { dof }
+    });
     let profiles = if matches!(
         status,
         SolveStatus::FullyConstrained | SolveStatus::UnderConstrained { .. }
@@ -25,11 +158,70 @@ pub fn solve_sketch(sketch: &Sketch) -> SolvedSketch {
         Vec::new()
     };
 
-    SolvedSketch {
+    Ok(SolvedSketch {
         positions,
         profiles,
         status,
+    })
+}
+
+/// Indices into `sketch.constraints` of the constraints that depend on the
+/// ones before them: candidates to remove from an over-constrained sketch.
+///
+/// slvs does not expose its Jacobian, so its rank is probed through the DOF
+/// slvs reports: constraints are added back in order, and one is dependent
+/// when it leaves the DOF unchanged (redundant) or makes the system fail
+/// (conflicting). Dependent constraints are left out of later checks, so
+/// removing all of them leaves a sketch that solves.
+pub fn diagnose_conflicts(sketch: &Sketch) -> Vec<usize> {
+    dependent_constraints(&sketch.entities, &sketch.constraints)
+}
+
+/// Indices into `constraints` of the ones that depend on those before them,
+/// see [`diagnose_conflicts`].
+fn dependent_constraints(
+    entities: &[SketchEntity],
+    constraints: &[SketchConstraint],
+) -> Vec<usize> {
+    let dof_of = |status: &SolveStatus| match status {
+        SolveStatus::FullyConstrained => Some(0),
+        SolveStatus::UnderConstrained { dof } => Some(*dof),
+        _ => None,
+    };
+    let mut kept = Vec::new();
+    let Some(mut dof) = dof_of(&solve_system(entities, &kept).1) else {
+        return Vec::new();
+    };
+    let mut dependent = Vec::new();
+    for (i, constraint) in constraints.iter().enumerate() {
+        kept.push(constraint.clone());
+        match dof_of(&solve_system(entities, &kept).1) {
+            Some(d) if d < dof => dof = d,
+            _ => {
+                kept.pop();
+                dependent.push(i);
+            }
+        }
+    }
+    dependent
+}
+
+/// Solve one set of entities and constraints as a single slvs system.
+pub(crate) fn solve_system(
+    entities: &[SketchEntity],
+    constraints: &[SketchConstraint],
+) -> (HashMap<u32, (f64, f64)>, SolveStatus) {
+    let mut mapping = SketchToSlvs::new();
+    mapping.add_entities(entities);
+    if let Err(reason) = mapping.add_constraints(constraints) {
+        return (
+            extract_positions(&mapping),
+            SolveStatus::SolveFailed { reason },
+        );
     }
+
+    let result = mapping.system.solve(&mapping.group);
+    (extract_positions(&mapping), classify_status(result))
 }
 
 /// Extract solved positions for all point entities.
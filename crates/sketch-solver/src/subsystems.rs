@@ -0,0 +1,151 @@
+//! Decomposition of a sketch into independent subsystems.
+//!
+//! Entities that share no constraint (directly or through another entity)
+//! cannot influence each other, so each connected group can be handed to
+//! the solver on its own instead of factoring one large Jacobian.
+
+use std::collections::HashMap;
+
+use crate::types::{SketchConstraint, SketchEntity};
+
+/// One independently solvable slice of a sketch.
+#[derive(Debug, Clone)]
+pub struct Subsystem {
+    pub entities: Vec<SketchEntity>,
+    pub constraints: Vec<SketchConstraint>,
+    /// Index in the sketch's constraint list of each of `constraints`.
+    pub constraint_indices: Vec<usize>,
+}
+
+/// Partition entities and constraints into connected components.
+///
+/// Entities are linked to the points they are built from, and every
+/// constraint links all the entities it references. Original ordering is
+/// kept within each component. An empty sketch yields no subsystems.
+pub fn partition(entities: &[SketchEntity], constraints: &[SketchConstraint]) -> Vec<Subsystem> {
+    let mut uf = UnionFind::default();
+    for entity in entities {
+        let id = entity.id();
+        uf.find(id);
+        for dep in entity_dependencies(entity) {
+            uf.union(id, dep);
+        }
+    }
+    for constraint in constraints {
+        let refs = constraint_references(constraint);
+        for pair in refs.windows(2) {
+            uf.union(pair[0], pair[1]);
+        }
+    }
+
+    let mut index_of_root: HashMap<u32, usize> = HashMap::new();
+    let mut subsystems: Vec<Subsystem> = Vec::new();
+    for entity in entities {
+        let root = uf.find(entity.id());
+        let idx = *index_of_root.entry(root).or_insert_with(|| {
+            subsystems.push(Subsystem {
+                entities: Vec::new(),
+                constraints: Vec::new(),
+                constraint_indices: Vec::new(),
+            });
+            subsystems.len() - 1
+        });
+        subsystems[idx].entities.push(entity.clone());
+    }
+    for (i, constraint) in constraints.iter().enumerate() {
+        let Some(&first) = constraint_references(constraint).first() else {
+            continue;
+        };
+        if let Some(&idx) = index_of_root.get(&uf.find(first)) {
+            subsystems[idx].constraints.push(constraint.clone());
+            subsystems[idx].constraint_indices.push(i);
+        }
+    }
+    subsystems
+}
+
+fn entity_dependencies(entity: &SketchEntity) -> Vec<u32> {
+    match entity {
+        SketchEntity::Point { .. } => Vec::new(),
+        SketchEntity::Line {
+            start_id, end_id, ..
+        } => vec![*start_id, *end_id],
+        SketchEntity::Circle { center_id, .. } => vec![*center_id],
+        SketchEntity::Arc {
+            center_id,
+            start_id,
+            end_id,
+            ..
+        } => vec![*center_id, *start_id, *end_id],
+    }
+}
+
+pub(crate) fn constraint_references(constraint: &SketchConstraint) -> Vec<u32> {
+    match constraint {
+        SketchConstraint::Horizontal { entity }
+        | SketchConstraint::Vertical { entity }
+        | SketchConstraint::Radius { entity, .. }
+        | SketchConstraint::Diameter { entity, .. } => vec![*entity],
+        SketchConstraint::Dragged { point } => vec![*point],
+        SketchConstraint::Coincident { point_a, point_b }
+        | SketchConstraint::SymmetricH { point_a, point_b }
+        | SketchConstraint::SymmetricV { point_a, point_b } => vec![*point_a, *point_b],
+        SketchConstraint::Parallel { line_a, line_b }
+        | SketchConstraint::Perpendicular { line_a, line_b }
+        | SketchConstraint::Angle { line_a, line_b, .. }
+        | SketchConstraint::LengthDifference { line_a, line_b, .. } => vec![*line_a, *line_b],
+        SketchConstraint::Tangent { line, curve } => vec![*line, *curve],
+        SketchConstraint::Equal { entity_a, entity_b }
+        | SketchConstraint::Distance {
+            entity_a, entity_b, ..
+        }
+        | SketchConstraint::Ratio {
+            entity_a, entity_b, ..
+        }
+        | SketchConstraint::SameOrientation { entity_a, entity_b }
+        | SketchConstraint::Concentric { entity_a, entity_b } => vec![*entity_a, *entity_b],
+        SketchConstraint::Symmetric {
+            entity_a,
+            entity_b,
+            symmetry_line,
+        } => vec![*entity_a, *entity_b, *symmetry_line],
+        SketchConstraint::Midpoint { point, line } => vec![*point, *line],
+        SketchConstraint::OnEntity { point, entity } => vec![*point, *entity],
+        SketchConstraint::EqualAngle {
+            line_a,
+            line_b,
+            line_c,
+            line_d,
+        } => vec![*line_a, *line_b, *line_c, *line_d],
+        SketchConstraint::EqualAngles { lines } => lines.clone(),
+        SketchConstraint::EqualPointToLine {
+            point_a,
+            point_b,
+            line,
+        } => vec![*point_a, *point_b, *line],
+    }
+}
+
+#[derive(Default)]
+struct UnionFind {
+    parent: HashMap<u32, u32>,
+}
+
+impl UnionFind {
+    fn find(&mut self, id: u32) -> u32 {
+        let parent = *self.parent.entry(id).or_insert(id);
+        if parent == id {
+            return id;
+        }
+        let root = self.find(parent);
+        self.parent.insert(id, root);
+        root
+    }
+
+    fn union(&mut self, a: u32, b: u32) {
+        let (ra, rb) = (self.find(a), self.find(b));
+        if ra != rb {
+            self.parent.insert(ra, rb);
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use crate::solver::solve_sketch;
+use crate::types::{Sketch, SketchConstraint, SketchEntity, SolveStatus};
+
+/// Lines within this many degrees of an axis are suggested as horizontal/vertical.
+const AXIS_ANGLE_TOLERANCE_DEG: f64 = 5.0;
+/// Line lengths within this relative difference are suggested as equal.
+const EQUAL_LENGTH_TOLERANCE: f64 = 0.02;
+/// Points closer than this fraction of the sketch extent are suggested as coincident.
+const COINCIDENT_TOLERANCE: f64 = 0.01;
+
+/// Propose geometric constraints that tidy up an under-constrained sketch.
+///
+/// Candidates are coincident (near-touching points), horizontal/vertical
+/// (near-axis lines) and equal (near-equal line lengths). Each candidate is
+/// trial-solved and kept only if it removes at least one degree of freedom,
+/// so the suggestions never over-constrain the sketch or duplicate an
+/// existing constraint. Dimensions are never suggested.
+pub fn suggest_constraints(sketch: &Sketch) -> Vec<SketchConstraint> {
+    let positions = current_positions(sketch);
+    let mut trial = sketch.clone();
+    let mut dof = match solve_sketch(&trial).status {
+        SolveStatus::UnderConstrained { dof } => dof,
+        _ => return Vec::new(),
+    };
+
+    let mut accepted = Vec::new();
+    for candidate in candidates(sketch, &positions) {
+        if dof == 0 {
+            break;
+        }
+        trial.constraints.push(candidate.clone());
+        let new_dof = match solve_sketch(&trial).status {
+            SolveStatus::FullyConstrained => Some(0),
+            SolveStatus::UnderConstrained { dof } => Some(dof),
+            _ => None,
+        };
+        match new_dof {
+            Some(d) if d < dof => {
+                dof = d;
+                accepted.push(candidate);
+            }
+            _ => {
+                trial.constraints.pop();
+            }
+        }
+    }
+    accepted
+}
+
+/// Point positions to judge "nearly" against: solved if available, else as drawn.
+fn current_positions(sketch: &Sketch) -> HashMap<u32, (f64, f64)> {
+    let mut positions = HashMap::new();
+    for entity in &sketch.entities {
+        if let SketchEntity::Point { id, x, y, .. } = entity {
+            let pos = sketch.solved_positions.get(id).copied().unwrap_or((*x, *y));
+            positions.insert(*id, pos);
+        }
+    }
+    positions
+}
+
+/// All candidate constraints, most fundamental first.
+fn candidates(sketch: &Sketch, positions: &HashMap<u32, (f64, f64)>) -> Vec<SketchConstraint> {
+    let mut out = Vec::new();
+
+    // Coincident: near-touching points, scaled to the sketch extent.
+    let mut ids: Vec<u32> = positions.keys().copied().collect();
+    ids.sort_unstable();
+    let tol = COINCIDENT_TOLERANCE * sketch_extent(positions).max(1e-9);
+    for (i, a) in ids.iter().enumerate() {
+        for b in &ids[i + 1..] {
+            let (pa, pb) = (positions[a], positions[b]);
+            if (pa.0 - pb.0).hypot(pa.1 - pb.1) < tol {
+                out.push(SketchConstraint::Coincident {
+                    point_a: *a,
+                    point_b: *b,
+                });
+            }
+        }
+    }
+
+    // Horizontal / vertical: near-axis lines.
+    let lines: Vec<(u32, f64, f64)> = sketch
+        .entities
+        .iter()
+        .filter_map(|e| match e {
+            SketchEntity::Line {
+                id,
+                start_id,
+                end_id,
+                ..
+            } => {
+                let s = positions.get(start_id)?;
+                let t = positions.get(end_id)?;
+                Some((*id, t.0 - s.0, t.1 - s.1))
+            }
+            _ => None,
+        })
+        .collect();
+    let axis_tol = AXIS_ANGLE_TOLERANCE_DEG.to_radians().tan();
+    for &(id, dx, dy) in &lines {
+        if dx.abs() > 1e-12 && (dy / dx).abs() < axis_tol {
+            out.push(SketchConstraint::Horizontal { entity: id });
+        } else if dy.abs() > 1e-12 && (dx / dy).abs() < axis_tol {
+            out.push(SketchConstraint::Vertical { entity: id });
+        }
+    }
+
+    // Equal: near-equal line lengths.
+    for (i, &(a, adx, ady)) in lines.iter().enumerate() {
+        let la = adx.hypot(ady);
+        for &(b, bdx, bdy) in &lines[i + 1..] {
+            let lb = bdx.hypot(bdy);
+            if la > 1e-12 && lb > 1e-12 && (la - lb).abs() / la.max(lb) < EQUAL_LENGTH_TOLERANCE {
+                out.push(SketchConstraint::Equal {
+                    entity_a: a,
+                    entity_b: b,
+                });
+            }
+        }
+    }
+
+    out
+}
+
+/// Diagonal of the points' bounding box.
+fn sketch_extent(positions: &HashMap<u32, (f64, f64)>) -> f64 {
+    let mut min = (f64::MAX, f64::MAX);
+    let mut max = (f64::MIN, f64::MIN);
+    for &(x, y) in positions.values() {
+        min = (min.0.min(x), min.1.min(y));
+        max = (max.0.max(x), max.1.max(y));
+    }
+    if positions.is_empty() {
+        return 0.0;
+    }
+    (max.0 - min.0).hypot(max.1 - min.1)
+}
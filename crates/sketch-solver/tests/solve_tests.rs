@@ -245,6 +245,128 @@ fn equilateral_triangle_equal_lengths() {
     );
 }
 
+#[test]
+fn regular_hexagon_from_equal_angles() {
+    // Six-line closed chain starting from a rough hexagon. Three equal
+    // lengths plus equal angles force the remaining sides equal, so one
+    // side length fully defines it.
+    let guesses = [
+        (1, 0.0, 0.0),
+        (2, 9.0, 0.5),
+        (3, 15.5, 8.0),
+        (4, 10.5, 18.0),
+        (5, -0.5, 17.0),
+        (6, -5.5, 9.0),
+    ];
+    let mut entities: Vec<SketchEntity> = guesses
+        .iter()
+        .map(|&(id, x, y)| SketchEntity::Point {
+            id,
+            x,
+            y,
+            construction: false,
+        })
+        .collect();
+    for i in 0..6u32 {
+        entities.push(SketchEntity::Line {
+            id: 10 + i,
+            start_id: 1 + i,
+            end_id: 1 + (i + 1) % 6,
+            construction: false,
+        });
+    }
+    let sketch = make_sketch(
+        entities,
+        vec![
+            SketchConstraint::Dragged { point: 1 },
+            SketchConstraint::Horizontal { entity: 10 },
+            SketchConstraint::Distance {
+                entity_a: 1,
+                entity_b: 2,
+                value: 10.0,
+            },
+            SketchConstraint::Equal {
+                entity_a: 10,
+                entity_b: 11,
+            },
+            SketchConstraint::Equal {
+                entity_a: 11,
+                entity_b: 12,
+            },
+            SketchConstraint::Equal {
+                entity_a: 12,
+                entity_b: 13,
+            },
+            SketchConstraint::EqualAngles {
+                lines: (10..16).collect(),
+            },
+        ],
+    );
+
+    let result = solve_sketch(&sketch);
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "status = {:?}",
+        result.status
+    );
+
+    let tol = 1e-4;
+    let corner = |i: u32| result.positions[&(1 + i % 6)];
+    for i in 0..6 {
+        let (a, b, c) = (corner(i), corner(i + 1), corner(i + 2));
+        let side = ((b.0 - a.0).powi(2) + (b.1 - a.1).powi(2)).sqrt();
+        assert!((side - 10.0).abs() < tol, "side {i} = {side}");
+
+        let (u, v) = ((a.0 - b.0, a.1 - b.1), (c.0 - b.0, c.1 - b.1));
+        let cos = (u.0 * v.0 + u.1 * v.1) / (u.0.hypot(u.1) * v.0.hypot(v.1));
+        let interior = cos.acos().to_degrees();
+        assert!(
+            (interior - 120.0).abs() < tol,
+            "angle at corner {} = {interior}",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn equal_angles_over_two_lines_fails_the_solve() {
+    let point = |id, x, y| SketchEntity::Point {
+        id,
+        x,
+        y,
+        construction: false,
+    };
+    let sketch = make_sketch(
+        vec![
+            point(1, 0.0, 0.0),
+            point(2, 10.0, 0.0),
+            point(3, 10.0, 10.0),
+            SketchEntity::Line {
+                id: 10,
+                start_id: 1,
+                end_id: 2,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 11,
+                start_id: 2,
+                end_id: 3,
+                construction: false,
+            },
+        ],
+        vec![SketchConstraint::EqualAngles {
+            lines: vec![10, 11],
+        }],
+    );
+
+    match solve_sketch(&sketch).status {
+        SolveStatus::SolveFailed { reason } => {
+            assert!(reason.contains("EqualAngles"), "reason = {reason}")
+        }
+        status => panic!("expected SolveFailed, got {:?}", status),
+    }
+}
+
 #[test]
 fn two_points_with_distance() {
     let sketch = make_sketch(
@@ -402,6 +524,73 @@ fn status_over_constrained() {
     );
 }
 
+#[test]
+fn diagnose_conflicts_flags_redundant_and_conflicting_constraints() {
+    let point = |id, x, y| SketchEntity::Point {
+        id,
+        x,
+        y,
+        construction: false,
+    };
+    let line = |id, start_id, end_id| SketchEntity::Line {
+        id,
+        start_id,
+        end_id,
+        construction: false,
+    };
+    let distance = |entity_a, entity_b, value| SketchConstraint::Distance {
+        entity_a,
+        entity_b,
+        value,
+    };
+    let sketch = make_sketch(
+        vec![
+            point(1, 0.0, 0.0),
+            point(2, 100.0, 0.0),
+            point(3, 100.0, 50.0),
+            point(4, 0.0, 50.0),
+            point(5, 5.0, 5.0),
+            line(10, 1, 2),
+            line(11, 2, 3),
+            line(12, 3, 4),
+            line(13, 4, 1),
+        ],
+        vec![
+            SketchConstraint::Dragged { point: 1 },
+            SketchConstraint::Horizontal { entity: 10 },
+            SketchConstraint::Vertical { entity: 11 },
+            SketchConstraint::Horizontal { entity: 12 },
+            SketchConstraint::Vertical { entity: 13 },
+            distance(1, 2, 100.0),
+            distance(2, 3, 50.0),
+            // Redundant: the opposite side already has this length.
+            distance(3, 4, 100.0),
+            distance(1, 5, 10.0),
+            // Conflicts with the distance just before it.
+            SketchConstraint::Coincident {
+                point_a: 1,
+                point_b: 5,
+            },
+        ],
+    );
+    assert!(matches!(
+        solve_sketch(&sketch).status,
+        SolveStatus::OverConstrained { .. } | SolveStatus::SolveFailed { .. }
+    ));
+
+    assert_eq!(diagnose_conflicts(&sketch), vec![7, 9]);
+
+    // Without them the sketch solves, leaving point 5 free to circle point 1.
+    let mut fixed = sketch.clone();
+    fixed.constraints.remove(9);
+    fixed.constraints.remove(7);
+    assert!(diagnose_conflicts(&fixed).is_empty());
+    assert!(matches!(
+        solve_sketch(&fixed).status,
+        SolveStatus::UnderConstrained { dof: 1 }
+    ));
+}
+
 #[test]
 fn status_rectangle_dof_count() {
     // Rectangle without position fix: 4 points (8 DOF) - 4 h/v constraints - 2 dimensions = 2 DOF
@@ -486,6 +675,69 @@ fn status_rectangle_dof_count() {
     }
 }
 
+#[test]
+fn status_dof_mixed_length_and_angle_units() {
+    // Two lines far from the origin joined at point 2, dimensioned with a
+    // large distance and an angle. Partials of the angle constraint are tiny
+    // compared to the distance ones; the rank test must still see every
+    // constraint as independent.
+    let sketch = make_sketch(
+        vec![
+            SketchEntity::Point {
+                id: 1,
+                x: 10000.0,
+                y: 10000.0,
+                construction: false,
+            },
+            SketchEntity::Point {
+                id: 2,
+                x: 15000.0,
+                y: 10000.0,
+                construction: false,
+            },
+            SketchEntity::Point {
+                id: 3,
+                x: 17500.0,
+                y: 11500.0,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 4,
+                start_id: 1,
+                end_id: 2,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 5,
+                start_id: 2,
+                end_id: 3,
+                construction: false,
+            },
+        ],
+        vec![
+            SketchConstraint::Horizontal { entity: 4 },
+            SketchConstraint::Distance {
+                entity_a: 1,
+                entity_b: 2,
+                value: 5000.0,
+            },
+            SketchConstraint::Angle {
+                line_a: 4,
+                line_b: 5,
+                value_degrees: 30.0,
+            },
+        ],
+    );
+
+    // 6 point params - 3 constraints = 3 DOF (line 5's length plus the
+    // translation of the whole figure).
+    let result = solve_sketch(&sketch);
+    match result.status {
+        SolveStatus::UnderConstrained { dof } => assert_eq!(dof, 3),
+        other => panic!("expected UnderConstrained, got {:?}", other),
+    }
+}
+
 // ── M6: Profile Extraction ─────────────────────────────────────────────────
 
 #[test]
@@ -1257,6 +1509,58 @@ fn reference_midpoint_constraint() {
     assert_point_near(&result.positions, 3, (50.0, 0.0), 1e-6);
 }
 
+#[test]
+fn midpoint_pulls_free_point_onto_slanted_line() {
+    let sketch = |constraints| {
+        make_sketch(
+            vec![
+                SketchEntity::Point {
+                    id: 1,
+                    x: 0.0,
+                    y: 0.0,
+                    construction: false,
+                },
+                SketchEntity::Point {
+                    id: 2,
+                    x: 10.0,
+                    y: 4.0,
+                    construction: false,
+                },
+                SketchEntity::Point {
+                    id: 3,
+                    x: 1.0,
+                    y: 7.0,
+                    construction: false,
+                },
+                SketchEntity::Line {
+                    id: 10,
+                    start_id: 1,
+                    end_id: 2,
+                    construction: false,
+                },
+            ],
+            constraints,
+        )
+    };
+    let pinned = vec![
+        SketchConstraint::Dragged { point: 1 },
+        SketchConstraint::Dragged { point: 2 },
+    ];
+
+    // The free point is worth 2 DOF, and the midpoint takes both.
+    let free = solve_sketch(&sketch(pinned.clone()));
+    assert!(matches!(
+        free.status,
+        SolveStatus::UnderConstrained { dof: 2 }
+    ));
+
+    let mut constraints = pinned;
+    constraints.push(SketchConstraint::Midpoint { point: 3, line: 10 });
+    let result = solve_sketch(&sketch(constraints));
+    assert!(matches!(result.status, SolveStatus::FullyConstrained));
+    assert_point_near(&result.positions, 3, (5.0, 2.0), 1e-6);
+}
+
 #[test]
 fn reference_symmetric_about_line() {
     // Two points symmetric about a vertical center line.
@@ -1324,6 +1628,80 @@ fn reference_symmetric_about_line() {
     );
 }
 
+/// Two anchored horizontal lines: line 10 from point 1, line 11 from point 3
+/// with a fixed length of 10. `relation` ties line 10's length to line 11.
+fn related_lines(relation: SketchConstraint) -> Sketch {
+    let mut entities: Vec<SketchEntity> = [
+        (1, 0.0, 0.0),
+        (2, 18.0, 0.0),
+        (3, 0.0, 5.0),
+        (4, 9.0, 5.0),
+    ]
+    .iter()
+    .map(|&(id, x, y)| SketchEntity::Point {
+        id,
+        x,
+        y,
+        construction: false,
+    })
+    .collect();
+    for (id, start_id, end_id) in [(10, 1, 2), (11, 3, 4)] {
+        entities.push(SketchEntity::Line {
+            id,
+            start_id,
+            end_id,
+            construction: false,
+        });
+    }
+    make_sketch(
+        entities,
+        vec![
+            SketchConstraint::Dragged { point: 1 },
+            SketchConstraint::Dragged { point: 3 },
+            SketchConstraint::Horizontal { entity: 10 },
+            SketchConstraint::Horizontal { entity: 11 },
+            SketchConstraint::Distance {
+                entity_a: 3,
+                entity_b: 4,
+                value: 10.0,
+            },
+            relation,
+        ],
+    )
+}
+
+#[test]
+fn reference_length_ratio_doubles_line() {
+    let result = solve_sketch(&related_lines(SketchConstraint::Ratio {
+        entity_a: 10,
+        entity_b: 11,
+        value: 2.0,
+    }));
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "status = {:?}",
+        result.status
+    );
+    assert_point_near(&result.positions, 2, (20.0, 0.0), 1e-6);
+    assert_point_near(&result.positions, 4, (10.0, 5.0), 1e-6);
+}
+
+#[test]
+fn reference_length_difference_adds_offset() {
+    let result = solve_sketch(&related_lines(SketchConstraint::LengthDifference {
+        line_a: 10,
+        line_b: 11,
+        value: 5.0,
+    }));
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "status = {:?}",
+        result.status
+    );
+    assert_point_near(&result.positions, 2, (15.0, 0.0), 1e-6);
+    assert_point_near(&result.positions, 4, (10.0, 5.0), 1e-6);
+}
+
 // ── M8: Dragged Constraint for Interactive Use ─────────────────────────────
 
 #[test]
@@ -1590,6 +1968,666 @@ fn on_entity_point_on_line() {
     assert!((y3).abs() < 1e-6, "point on line should have y=0, got {y3}");
 }
 
+/// A pinned radius-10 circle and a loose second circle, with point 3 on the
+/// second circle and level with its center so its radius shows in `positions`.
+fn circle_pair(constraints: Vec<SketchConstraint>) -> Sketch {
+    let mut all = vec![
+        SketchConstraint::Dragged { point: 1 },
+        SketchConstraint::Radius {
+            entity: 10,
+            value: 10.0,
+        },
+        SketchConstraint::OnEntity {
+            point: 3,
+            entity: 11,
+        },
+        SketchConstraint::Horizontal { entity: 20 },
+    ];
+    all.extend(constraints);
+    make_sketch(
+        vec![
+            SketchEntity::Point {
+                id: 1,
+                x: 0.0,
+                y: 0.0,
+                construction: false,
+            },
+            SketchEntity::Point {
+                id: 2,
+                x: 3.0,
+                y: 4.0,
+                construction: false,
+            },
+            SketchEntity::Point {
+                id: 3,
+                x: 15.0,
+                y: 2.0,
+                construction: true,
+            },
+            SketchEntity::Circle {
+                id: 10,
+                center_id: 1,
+                radius: 10.0,
+                construction: false,
+            },
+            SketchEntity::Circle {
+                id: 11,
+                center_id: 2,
+                radius: 6.0,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 20,
+                start_id: 2,
+                end_id: 3,
+                construction: true,
+            },
+        ],
+        all,
+    )
+}
+
+#[test]
+fn equal_radius_and_concentric_circles() {
+    // Loose, the second circle has its center and radius free.
+    let loose = solve_sketch(&circle_pair(vec![]));
+    assert!(
+        matches!(loose.status, SolveStatus::UnderConstrained { dof: 3 }),
+        "expected 3 DOF, got {:?}",
+        loose.status
+    );
+
+    let result = solve_sketch(&circle_pair(vec![
+        SketchConstraint::Equal {
+            entity_a: 10,
+            entity_b: 11,
+        },
+        SketchConstraint::Concentric {
+            entity_a: 10,
+            entity_b: 11,
+        },
+    ]));
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "expected fully constrained, got {:?}",
+        result.status
+    );
+    assert_point_near(&result.positions, 2, (0.0, 0.0), 1e-6);
+    let (cx, cy) = result.positions[&2];
+    let (px, py) = result.positions[&3];
+    let radius = (px - cx).hypot(py - cy);
+    assert!(
+        (radius - 10.0).abs() < 1e-6,
+        "radius = {radius}, expected 10"
+    );
+}
+
+#[test]
+fn rounded_slot_lines_tangent_to_both_arcs() {
+    // Two radius-5 caps 20 apart, joined by straight sides. Each side runs
+    // from the end of one arc to the start of the other, so one tangency
+    // per line sits at an arc's end point.
+    let point = |id, x, y| SketchEntity::Point {
+        id,
+        x,
+        y,
+        construction: false,
+    };
+    let sketch = make_sketch(
+        vec![
+            point(1, 0.0, 0.0),
+            point(2, 19.0, 0.5),
+            point(3, 0.3, 5.2),
+            point(4, -0.2, -4.8),
+            point(5, 19.5, -5.3),
+            point(6, 20.4, 4.7),
+            SketchEntity::Arc {
+                id: 10,
+                center_id: 1,
+                start_id: 3,
+                end_id: 4,
+                construction: false,
+            },
+            SketchEntity::Arc {
+                id: 11,
+                center_id: 2,
+                start_id: 5,
+                end_id: 6,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 20,
+                start_id: 4,
+                end_id: 5,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 21,
+                start_id: 6,
+                end_id: 3,
+                construction: false,
+            },
+        ],
+        vec![
+            SketchConstraint::Dragged { point: 1 },
+            SketchConstraint::Horizontal { entity: 20 },
+            SketchConstraint::Distance {
+                entity_a: 1,
+                entity_b: 2,
+                value: 20.0,
+            },
+            SketchConstraint::Radius {
+                entity: 10,
+                value: 5.0,
+            },
+            SketchConstraint::Radius {
+                entity: 11,
+                value: 5.0,
+            },
+            SketchConstraint::Tangent {
+                line: 20,
+                curve: 10,
+            },
+            SketchConstraint::Tangent {
+                line: 20,
+                curve: 11,
+            },
+            SketchConstraint::Tangent {
+                line: 21,
+                curve: 11,
+            },
+            SketchConstraint::Tangent {
+                line: 21,
+                curve: 10,
+            },
+        ],
+    );
+
+    let result = solve_sketch(&sketch);
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "expected fully constrained, got {:?}",
+        result.status
+    );
+    let tol = 1e-6;
+    assert_point_near(&result.positions, 2, (20.0, 0.0), tol);
+    assert_point_near(&result.positions, 3, (0.0, 5.0), tol);
+    assert_point_near(&result.positions, 4, (0.0, -5.0), tol);
+    assert_point_near(&result.positions, 5, (20.0, -5.0), tol);
+    assert_point_near(&result.positions, 6, (20.0, 5.0), tol);
+
+    // At every join the radius is perpendicular to the line.
+    let p = |id: u32| result.positions[&id];
+    for (center, at, other) in [(1, 4, 5), (2, 5, 4), (2, 6, 3), (1, 3, 6)] {
+        let radius = (p(at).0 - p(center).0, p(at).1 - p(center).1);
+        let side = (p(other).0 - p(at).0, p(other).1 - p(at).1);
+        let cos = (radius.0 * side.0 + radius.1 * side.1)
+            / (radius.0.hypot(radius.1) * side.0.hypot(side.1));
+        assert!(cos.abs() < tol, "join at point {}: cos = {}", at, cos);
+    }
+}
+
+// ── DOF Summary ────────────────────────────────────────────────────────────
+
+fn loose_rectangle(constraints: Vec<SketchConstraint>) -> Sketch {
+    let mut entities: Vec<SketchEntity> = [
+        (1, 0.0, 0.0),
+        (2, 100.0, 0.0),
+        (3, 100.0, 50.0),
+        (4, 0.0, 50.0),
+    ]
+    .iter()
+    .map(|&(id, x, y)| SketchEntity::Point {
+        id,
+        x,
+        y,
+        construction: false,
+    })
+    .collect();
+    for (i, (start_id, end_id)) in [(1, 2), (2, 3), (3, 4), (4, 1)].into_iter().enumerate() {
+        entities.push(SketchEntity::Line {
+            id: 10 + i as u32,
+            start_id,
+            end_id,
+            construction: false,
+        });
+    }
+    make_sketch(entities, constraints)
+}
+
+#[test]
+fn unanchored_rectangle_separates_placement_from_shape() {
+    // 4 free points = 8 DOF, 3 of which only move the rectangle as a whole.
+    let sketch = loose_rectangle(Vec::new());
+    let summary = dof_summary(&sketch);
+    assert_eq!(
+        summary,
+        DofSummary {
+            position: 3,
+            shape: 5
+        }
+    );
+    assert_eq!(summary.total(), 8);
+    assert_eq!(constraints_needed(&sketch), 5);
+}
+
+#[test]
+fn closed_loops_walks_rectangle() {
+    let sketch = loose_rectangle(Vec::new());
+    assert_eq!(sketch.closed_loops(), Ok(vec![vec![10, 11, 12, 13]]));
+}
+
+#[test]
+fn closed_loops_names_the_gap_in_an_open_rectangle() {
+    let mut sketch = loose_rectangle(Vec::new());
+    // Drop the closing line 4 -> 1.
+    sketch.entities.retain(|e| e.id() != 13);
+    assert_eq!(
+        sketch.closed_loops(),
+        Err(ProfileError::DanglingEndpoint {
+            point: 1,
+            entity: 10
+        })
+    );
+}
+
+#[test]
+fn parallel_solve_matches_serial_in_order() {
+    // Same rectangle at eight different widths so a reordering would show.
+    let sketches: Vec<Sketch> = (0..8)
+        .map(|i| {
+            loose_rectangle(vec![
+                SketchConstraint::Dragged { point: 1 },
+                SketchConstraint::Horizontal { entity: 10 },
+                SketchConstraint::Vertical { entity: 11 },
+                SketchConstraint::Horizontal { entity: 12 },
+                SketchConstraint::Vertical { entity: 13 },
+                SketchConstraint::Distance {
+                    entity_a: 1,
+                    entity_b: 2,
+                    value: 100.0 + 10.0 * i as f64,
+                },
+                SketchConstraint::Distance {
+                    entity_a: 2,
+                    entity_b: 3,
+                    value: 50.0,
+                },
+            ])
+        })
+        .collect();
+
+    let parallel = solve_sketches_parallel(&sketches);
+    assert_eq!(parallel.len(), 8);
+    for (i, (sketch, solved)) in sketches.iter().zip(&parallel).enumerate() {
+        let serial = solve_sketch(sketch);
+        assert!(matches!(solved.status, SolveStatus::FullyConstrained));
+        assert_eq!(solved.positions, serial.positions);
+        assert_eq!(solved.profiles.len(), serial.profiles.len());
+        let width = 100.0 + 10.0 * i as f64;
+        assert_point_near(&solved.positions, 3, (width, 50.0), 1e-6);
+    }
+}
+
+#[test]
+fn anchored_rectangle_needs_only_its_dimensions() {
+    let sketch = loose_rectangle(vec![
+        SketchConstraint::Dragged { point: 1 },
+        SketchConstraint::Horizontal { entity: 10 },
+        SketchConstraint::Vertical { entity: 11 },
+        SketchConstraint::Horizontal { entity: 12 },
+        SketchConstraint::Vertical { entity: 13 },
+    ]);
+    // Width and height remain.
+    assert_eq!(
+        dof_summary(&sketch),
+        DofSummary {
+            position: 0,
+            shape: 2
+        }
+    );
+    assert_eq!(constraints_needed(&sketch), 2);
+}
+
+#[test]
+fn checked_add_rejects_redundant_horizontal() {
+    let mut sketch = make_sketch(
+        vec![
+            SketchEntity::Point {
+                id: 1,
+                x: 0.0,
+                y: 0.0,
+                construction: false,
+            },
+            SketchEntity::Point {
+                id: 2,
+                x: 10.0,
+                y: 0.0,
+                construction: false,
+            },
+            SketchEntity::Line {
+                id: 3,
+                start_id: 1,
+                end_id: 2,
+                construction: false,
+            },
+        ],
+        Vec::new(),
+    );
+
+    // 4 free coordinates, each constraint takes one.
+    let dof = sketch
+        .add_constraint_checked(SketchConstraint::Horizontal { entity: 3 })
+        .unwrap();
+    assert_eq!(dof, 3);
+    let dof = sketch
+        .add_constraint_checked(SketchConstraint::Distance {
+            entity_a: 1,
+            entity_b: 2,
+            value: 10.0,
+        })
+        .unwrap();
+    assert_eq!(dof, 2);
+
+    let err = sketch
+        .add_constraint_checked(SketchConstraint::Horizontal { entity: 3 })
+        .unwrap_err();
+    assert!(
+        matches!(
+            err,
+            ConstraintError::OverConstrained { .. } | ConstraintError::Redundant { .. }
+        ),
+        "got {:?}",
+        err
+    );
+    assert!(err.to_string().contains("Horizontal"), "{}", err);
+    assert_eq!(sketch.constraints.len(), 2);
+
+    let err = sketch
+        .add_constraint_checked(SketchConstraint::Vertical { entity: 99 })
+        .unwrap_err();
+    assert!(matches!(err, ConstraintError::UnknownEntity { id: 99 }));
+}
+
+// ── Constraint Suggestions ─────────────────────────────────────────────────
+
+#[test]
+fn suggest_constraints_for_approximate_rectangle() {
+    let points = [
+        (1, 0.0, 0.0),
+        (2, 100.5, 1.2),
+        (3, 99.2, 50.8),
+        (4, -0.7, 49.5),
+    ];
+    let mut entities: Vec<SketchEntity> = points
+        .iter()
+        .map(|&(id, x, y)| SketchEntity::Point {
+            id,
+            x,
+            y,
+            construction: false,
+        })
+        .collect();
+    for (i, (start_id, end_id)) in [(1, 2), (2, 3), (3, 4), (4, 1)].into_iter().enumerate() {
+        entities.push(SketchEntity::Line {
+            id: 10 + i as u32,
+            start_id,
+            end_id,
+            construction: false,
+        });
+    }
+    let mut sketch = make_sketch(entities, vec![]);
+
+    let suggestions = suggest_constraints(&sketch);
+    let hv = suggestions
+        .iter()
+        .filter(|c| {
+            matches!(
+                c,
+                SketchConstraint::Horizontal { .. } | SketchConstraint::Vertical { .. }
+            )
+        })
+        .count();
+    assert_eq!(hv, 4, "suggestions: {:?}", suggestions);
+
+    // Applied, only position and the two side lengths remain free.
+    sketch.constraints.extend(suggestions);
+    match solve_sketch(&sketch).status {
+        SolveStatus::UnderConstrained { dof } => assert_eq!(dof, 4),
+        other => panic!("expected UnderConstrained, got {:?}", other),
+    }
+
+    // Nothing more to suggest once the sketch is tidy.
+    assert!(suggest_constraints(&sketch).is_empty());
+}
+
+// ── Subsystem Decomposition ────────────────────────────────────────────────
+
+/// A 100×50 rectangle pinned at `(x, 0)`, using point IDs `p..p+4` and line IDs `l..l+4`.
+fn pinned_rectangle(p: u32, l: u32, x: f64) -> (Vec<SketchEntity>, Vec<SketchConstraint>) {
+    let corners = [(x, 0.0), (x + 100.0, 0.0), (x + 100.0, 50.0), (x, 50.0)];
+    let mut entities: Vec<SketchEntity> = corners
+        .iter()
+        .enumerate()
+        .map(|(i, &(px, py))| SketchEntity::Point {
+            id: p + i as u32,
+            x: px,
+            y: py,
+            construction: false,
+        })
+        .collect();
+    for i in 0..4u32 {
+        entities.push(SketchEntity::Line {
+            id: l + i,
+            start_id: p + i,
+            end_id: p + (i + 1) % 4,
+            construction: false,
+        });
+    }
+    let constraints = vec![
+        SketchConstraint::Horizontal { entity: l },
+        SketchConstraint::Horizontal { entity: l + 2 },
+        SketchConstraint::Vertical { entity: l + 1 },
+        SketchConstraint::Vertical { entity: l + 3 },
+        SketchConstraint::Distance {
+            entity_a: p,
+            entity_b: p + 1,
+            value: 100.0,
+        },
+        SketchConstraint::Distance {
+            entity_a: p + 1,
+            entity_b: p + 2,
+            value: 50.0,
+        },
+        SketchConstraint::Dragged { point: p },
+    ];
+    (entities, constraints)
+}
+
+#[test]
+fn two_independent_rectangles_solve_as_two_subsystems() {
+    let (mut entities, mut constraints) = pinned_rectangle(1, 100, 0.0);
+    let (e2, c2) = pinned_rectangle(11, 200, 300.0);
+    entities.extend(e2);
+    constraints.extend(c2);
+    let sketch = make_sketch(entities, constraints);
+
+    let (result, subsystems) = solve_sketch_subsystems(&sketch);
+    assert_eq!(subsystems, 2);
+    assert!(
+        matches!(result.status, SolveStatus::FullyConstrained),
+        "status: {:?}",
+        result.status
+    );
+    assert_eq!(result.profiles.len(), 2);
+
+    let tol = 1e-6;
+    assert_point_near(&result.positions, 3, (100.0, 50.0), tol);
+    assert_point_near(&result.positions, 13, (400.0, 50.0), tol);
+}
+
+#[test]
+fn conflicts_are_summed_across_subsystems() {
+    let (mut entities, mut constraints) = pinned_rectangle(1, 100, 0.0);
+    // Redundant: the opposite side already has this length.
+    constraints.push(SketchConstraint::Distance {
+        entity_a: 3,
+        entity_b: 4,
+        value: 100.0,
+    });
+    let (e2, mut c2) = pinned_rectangle(11, 200, 300.0);
+    c2.push(SketchConstraint::Distance {
+        entity_a: 12,
+        entity_b: 13,
+        value: 50.0,
+    });
+    entities.extend(e2);
+    constraints.extend(c2);
+    let sketch = make_sketch(entities, constraints);
+
+    let (result, subsystems) = solve_sketch_subsystems(&sketch);
+    assert_eq!(subsystems, 2);
+    match result.status {
+        SolveStatus::OverConstrained { conflicts } => assert_eq!(conflicts, vec![7, 15]),
+        status => panic!("expected OverConstrained, got {:?}", status),
+    }
+}
+
+#[test]
+fn zero_time_budget_stops_after_first_subsystem() {
+    let mut entities = Vec::new();
+    let mut constraints = Vec::new();
+    for k in 0..20 {
+        let (e, c) = pinned_rectangle(1 + 10 * k, 1000 + 10 * k, 200.0 * k as f64);
+        entities.extend(e);
+        constraints.extend(c);
+    }
+    // Knock every rectangle's far corner out of place.
+    for entity in &mut entities {
+        if let SketchEntity::Point { id, y, .. } = entity {
+            if *id % 10 == 3 {
+                *y += 7.0;
+            }
+        }
+    }
+    let mut sketch = make_sketch(entities, constraints);
+
+    let err = solve_sketch_budgeted(&mut sketch, Some(std::time::Duration::ZERO)).unwrap_err();
+    assert!(matches!(
+        err,
+        SolveError::Timeout {
+            solved: 1,
+            subsystems: 20
+        }
+    ));
+    assert!(matches!(
+        sketch.solve_status,
+        SolveStatus::SolveFailed { .. }
+    ));
+    // The first rectangle is solved, the rest keep their stored positions.
+    let tol = 1e-6;
+    assert_point_near(&sketch.solved_positions, 3, (100.0, 50.0), tol);
+    assert_point_near(&sketch.solved_positions, 13, (300.0, 57.0), tol);
+    assert_eq!(sketch.solved_positions.len(), 80);
+
+    let status = solve_sketch_budgeted(&mut sketch, None).unwrap();
+    assert!(matches!(status, SolveStatus::FullyConstrained));
+    assert_point_near(&sketch.solved_positions, 13, (300.0, 50.0), tol);
+    assert_eq!(sketch.solved_profiles.len(), 20);
+}
+
+#[test]
+fn connected_rectangles_solve_as_one_subsystem() {
+    let (entities, constraints) = make_rectangle_chain(3);
+    let sketch = make_sketch(entities, constraints);
+    let (_, subsystems) = solve_sketch_subsystems(&sketch);
+    assert_eq!(subsystems, 1);
+}
+
+// ── Sensitivity ────────────────────────────────────────────────────────────
+
+#[test]
+fn sensitivity_of_rectangle_width() {
+    let (entities, constraints) = pinned_rectangle(1, 100, 0.0);
+    // Index 4 is the 100mm width between points 1 and 2.
+    assert!(matches!(
+        constraints[4],
+        SketchConstraint::Distance { value, .. } if value == 100.0
+    ));
+    let sketch = make_sketch(entities, constraints);
+
+    let (result, derivatives) = solve_sketch_with_sensitivity(&sketch, 4);
+    assert!(matches!(result.status, SolveStatus::FullyConstrained));
+    assert_eq!(derivatives.len(), 4);
+
+    let tol = 1e-4;
+    for (id, (dx, dy)) in derivatives {
+        let expected_dx = if id == 2 || id == 3 { 1.0 } else { 0.0 };
+        assert!(
+            (dx - expected_dx).abs() < tol && dy.abs() < tol,
+            "point {} moves at ({}, {})",
+            id,
+            dx,
+            dy
+        );
+    }
+}
+
+#[test]
+fn sensitivity_wrt_non_dimension_is_empty() {
+    let (entities, constraints) = pinned_rectangle(1, 100, 0.0);
+    let sketch = make_sketch(entities, constraints);
+    // Index 0 is a Horizontal constraint.
+    let (_, derivatives) = solve_sketch_with_sensitivity(&sketch, 0);
+    assert!(derivatives.is_empty());
+}
+
+#[test]
+fn warm_start_stays_on_the_previous_solution() {
+    // Point 2 lies on the horizontal through point 1 at the given distance,
+    // which it can meet on either side. Its stored position is to the right.
+    let sketch = |distance| {
+        make_sketch(
+            vec![
+                SketchEntity::Point {
+                    id: 1,
+                    x: 0.0,
+                    y: 0.0,
+                    construction: false,
+                },
+                SketchEntity::Point {
+                    id: 2,
+                    x: 8.0,
+                    y: 1.0,
+                    construction: false,
+                },
+                SketchEntity::Line {
+                    id: 10,
+                    start_id: 1,
+                    end_id: 2,
+                    construction: false,
+                },
+            ],
+            vec![
+                SketchConstraint::Dragged { point: 1 },
+                SketchConstraint::Horizontal { entity: 10 },
+                SketchConstraint::Distance {
+                    entity_a: 1,
+                    entity_b: 2,
+                    value: distance,
+                },
+            ],
+        )
+    };
+    let cold = solve_sketch(&sketch(10.5));
+    assert_point_near(&cold.positions, 2, (10.5, 0.0), 1e-6);
+
+    // Point 2 was last solved on the left; growing the distance keeps it there.
+    let previous = std::collections::HashMap::from([(1, (0.0, 0.0)), (2, (-10.0, 0.0))]);
+    let warm = solve_sketch_warm(&sketch(10.5), &previous);
+    assert!(matches!(warm.status, SolveStatus::FullyConstrained));
+    assert_point_near(&warm.positions, 2, (-10.5, 0.0), 1e-6);
+}
+
 // ── M9: Performance Benchmarking ─────────────────────────────────────────────
 
 /// Build a chain of N connected rectangles, each with h/v constraints and dimensions.
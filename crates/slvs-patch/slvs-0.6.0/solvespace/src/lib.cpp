@@ -8,8 +8,8 @@
 #define EXPORT_DLL
 #include <slvs.h>
 
-Sketch SolveSpace::SK = {};
-static System SYS;
+thread_local Sketch SolveSpace::SK = {};
+static thread_local System SYS;
 
 void SolveSpace::Platform::FatalError(const std::string &message) {
     fprintf(stderr, "%s", message.c_str());
@@ -223,8 +223,24 @@ void System::SolveBySubstitution() {
 int System::CalculateRank() {
     using namespace Eigen;
     if(mat.n == 0 || mat.m == 0) return 0;
+    // Normalize each column to unit length first. SparseQR's rank threshold
+    // is absolute, so without this a parameter whose partials are large
+    // (e.g. a point far from the origin in an angle constraint) swamps the
+    // small ones and the rank -- and so the DOF count -- comes out wrong.
+    SparseMatrix<double> scaled = mat.A.num;
+    for(int c = 0; c < scaled.outerSize(); c++) {
+        double norm = 0.0;
+        for(SparseMatrix<double>::InnerIterator it(scaled, c); it; ++it) {
+            norm += it.value() * it.value();
+        }
+        norm = sqrt(norm);
+        if(norm <= 0.0) continue;
+        for(SparseMatrix<double>::InnerIterator it(scaled, c); it; ++it) {
+            it.valueRef() /= norm;
+        }
+    }
     SparseQR <SparseMatrix<double>, COLAMDOrdering<int>> solver;
-    solver.compute(mat.A.num);
+    solver.compute(scaled);
     int result = solver.rank();
     return result;
 }
@@ -116,6 +116,8 @@ pub fn assert_tree_structure(
                 feature_engine::types::Operation::Chamfer { .. } => "Chamfer",
                 feature_engine::types::Operation::Shell { .. } => "Shell",
                 feature_engine::types::Operation::BooleanCombine { .. } => "Boolean",
+                feature_engine::types::Operation::AutoFillet { .. } => "AutoFillet",
+                feature_engine::types::Operation::StaticSolid { .. } => "StaticSolid",
             };
             (f.name.clone(), op_type.to_string())
         })
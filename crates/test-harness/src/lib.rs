@@ -7,6 +7,7 @@
 //!
 //! - [`ModelBuilder`] — Fluent API for building and verifying CAD models
 //! - [`oracle`] — Verification functions returning pass/fail verdicts
+//! - [`mesh_diff`] — Color-coded image of where two meshes differ
 //! - [`report`] — Structured text model descriptions
 //! - [`stl`] — STL export from RenderMesh
 //! - [`helpers`] — GeomRef constructors, profile builders, mesh math
@@ -14,6 +15,7 @@
 
 pub mod assertions;
 pub mod helpers;
+pub mod mesh_diff;
 pub mod oracle;
 pub mod report;
 pub mod stl;
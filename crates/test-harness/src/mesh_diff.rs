@@ -0,0 +1,175 @@
+//! Visual mesh diff for regression review.
+//!
+//! Renders two meshes from the same orthographic camera with a depth buffer
+//! each, then colors every pixel by which mesh owns the visible surface
+//! there. Reviewers see at a glance where a model gained or lost material.
+
+use kernel_fork::types::RenderMesh;
+
+/// Pixel color where neither mesh covers the pixel.
+pub const BACKGROUND_COLOR: [u8; 3] = [255, 255, 255];
+/// Pixel color where both meshes show the same surface.
+pub const SHARED_COLOR: [u8; 3] = [160, 160, 160];
+/// Pixel color where the visible surface belongs only to mesh A.
+pub const A_ONLY_COLOR: [u8; 3] = [0, 180, 0];
+/// Pixel color where the visible surface belongs only to mesh B.
+pub const B_ONLY_COLOR: [u8; 3] = [220, 0, 0];
+
+/// Fraction of the image left empty around the framed meshes.
+const MARGIN: f64 = 0.05;
+
+/// Surfaces closer than this fraction of the model extent count as the same.
+const DEPTH_TOLERANCE: f64 = 1e-4;
+
+/// Orthographic camera direction for [`mesh_diff_image`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ViewDirection {
+    /// Looking down -Z, X to the right.
+    Top,
+    /// Looking along +Y, X to the right, Z up.
+    Front,
+    /// Looking along -X, Y to the right, Z up.
+    Right,
+    /// Looking from (+X, -Y, +Z) toward the origin, Z up.
+    Isometric,
+}
+
+impl ViewDirection {
+    /// Camera basis as (right, up, forward) unit vectors.
+    fn basis(self) -> [[f64; 3]; 3] {
+        match self {
+            ViewDirection::Top => [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, -1.0]],
+            ViewDirection::Front => [[1.0, 0.0, 0.0], [0.0, 0.0, 1.0], [0.0, 1.0, 0.0]],
+            ViewDirection::Right => [[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [-1.0, 0.0, 0.0]],
+            ViewDirection::Isometric => {
+                let (s2, s3, s6) = (2f64.sqrt(), 3f64.sqrt(), 6f64.sqrt());
+                [
+                    [1.0 / s2, 1.0 / s2, 0.0],
+                    [-1.0 / s6, 1.0 / s6, 2.0 / s6],
+                    [-1.0 / s3, 1.0 / s3, -1.0 / s3],
+                ]
+            }
+        }
+    }
+}
+
+/// Render `a` and `b` from `view` and color each pixel by ownership.
+///
+/// Returns `width * height` RGB pixels, row-major from the top-left. Both
+/// meshes are framed by their combined extent so they line up exactly.
+/// Where both cover a pixel, matching depths are [`SHARED_COLOR`]; otherwise
+/// the nearer surface wins, so material added in front of the old surface
+/// shows up even when the silhouette is unchanged.
+pub fn mesh_diff_image(
+    a: &RenderMesh,
+    b: &RenderMesh,
+    width: usize,
+    height: usize,
+    view: ViewDirection,
+) -> Vec<u8> {
+    let basis = view.basis();
+    let (pa, pb) = (project(a, &basis), project(b, &basis));
+
+    let mut image = BACKGROUND_COLOR.repeat(width * height);
+    let Some((min, max)) = pa.iter().chain(&pb).fold(None, |acc, p| {
+        let (lo, hi) = acc.unwrap_or((*p, *p));
+        Some((
+            [lo[0].min(p[0]), lo[1].min(p[1]), lo[2].min(p[2])],
+            [hi[0].max(p[0]), hi[1].max(p[1]), hi[2].max(p[2])],
+        ))
+    }) else {
+        return image;
+    };
+
+    let span = (max[0] - min[0]).max(max[1] - min[1]).max(f64::EPSILON);
+    let usable = (1.0 - 2.0 * MARGIN) * width.min(height) as f64;
+    let frame = Frame {
+        scale: usable / span,
+        center: [(min[0] + max[0]) / 2.0, (min[1] + max[1]) / 2.0],
+        width,
+        height,
+    };
+    let tolerance = DEPTH_TOLERANCE * span.max(max[2] - min[2]);
+
+    let depth_a = rasterize(&pa, &a.indices, &frame);
+    let depth_b = rasterize(&pb, &b.indices, &frame);
+    for (i, pixel) in image.chunks_exact_mut(3).enumerate() {
+        let color = match (depth_a[i], depth_b[i]) {
+            (None, None) => continue,
+            (Some(_), None) => A_ONLY_COLOR,
+            (None, Some(_)) => B_ONLY_COLOR,
+            (Some(da), Some(db)) if (da - db).abs() <= tolerance => SHARED_COLOR,
+            (Some(da), Some(db)) if da < db => A_ONLY_COLOR,
+            _ => B_ONLY_COLOR,
+        };
+        pixel.copy_from_slice(&color);
+    }
+    image
+}
+
+/// Maps camera-plane coordinates onto the pixel grid.
+struct Frame {
+    scale: f64,
+    center: [f64; 2],
+    width: usize,
+    height: usize,
+}
+
+impl Frame {
+    fn to_pixel(&self, p: [f64; 3]) -> [f64; 3] {
+        [
+            (p[0] - self.center[0]) * self.scale + self.width as f64 / 2.0,
+            self.height as f64 / 2.0 - (p[1] - self.center[1]) * self.scale,
+            p[2],
+        ]
+    }
+}
+
+/// Vertices in camera coordinates: (right, up, depth along the view).
+fn project(mesh: &RenderMesh, basis: &[[f64; 3]; 3]) -> Vec<[f64; 3]> {
+    mesh.vertices
+        .chunks_exact(3)
+        .map(|v| {
+            let p = [v[0] as f64, v[1] as f64, v[2] as f64];
+            basis.map(|axis| axis[0] * p[0] + axis[1] * p[1] + axis[2] * p[2])
+        })
+        .collect()
+}
+
+/// Nearest depth per pixel, sampled at pixel centers. Triangles of either
+/// winding are drawn, so the result does not depend on mesh orientation.
+fn rasterize(points: &[[f64; 3]], indices: &[u32], frame: &Frame) -> Vec<Option<f64>> {
+    let mut depth = vec![None; frame.width * frame.height];
+    for tri in indices.chunks_exact(3) {
+        let [p0, p1, p2] = [tri[0], tri[1], tri[2]].map(|i| frame.to_pixel(points[i as usize]));
+        let area = edge(p0, p1, p2);
+        if area.abs() < f64::EPSILON {
+            continue;
+        }
+
+        let lo_x = p0[0].min(p1[0]).min(p2[0]).floor().max(0.0) as usize;
+        let lo_y = p0[1].min(p1[1]).min(p2[1]).floor().max(0.0) as usize;
+        let hi_x = (p0[0].max(p1[0]).max(p2[0]).ceil() as usize).min(frame.width);
+        let hi_y = (p0[1].max(p1[1]).max(p2[1]).ceil() as usize).min(frame.height);
+        for y in lo_y..hi_y {
+            for x in lo_x..hi_x {
+                let c = [x as f64 + 0.5, y as f64 + 0.5, 0.0];
+                let w = [edge(p1, p2, c), edge(p2, p0, c), edge(p0, p1, c)].map(|e| e / area);
+                if w.iter().any(|&wi| wi < 0.0) {
+                    continue;
+                }
+                let z = w[0] * p0[2] + w[1] * p1[2] + w[2] * p2[2];
+                let slot = &mut depth[y * frame.width + x];
+                if !slot.is_some_and(|d| d <= z) {
+                    *slot = Some(z);
+                }
+            }
+        }
+    }
+    depth
+}
+
+/// Twice the signed area of triangle (a, b, c) in the pixel plane.
+fn edge(a: [f64; 3], b: [f64; 3], c: [f64; 3]) -> f64 {
+    (b[0] - a[0]) * (c[1] - a[1]) - (b[1] - a[1]) * (c[0] - a[0])
+}
@@ -6,9 +6,10 @@
 use std::collections::HashMap;
 
 use kernel_fork::types::RenderMesh;
-use kernel_fork::{KernelIntrospect, KernelSolidHandle};
+use kernel_fork::{KernelError, KernelIntrospect, KernelSolidHandle};
 use modeling_ops::types::OpResult;
-use waffle_types::Role;
+use modeling_ops::KernelBundle;
+use waffle_types::{ClosedProfile, Role};
 
 /// The result of a single oracle check.
 #[derive(Debug, Clone)]
@@ -490,6 +491,53 @@ pub fn check_bounding_box(
     )
 }
 
+/// Check a constant-section sweep encloses `cross_section_area * path_length`
+/// within a relative tolerance.
+///
+/// Only meaningful for closed meshes swept along a path that doesn't
+/// self-intersect, where the volume is independent of the path's curvature.
+/// It is a coarse oracle for loft and sweep output: it catches missing caps,
+/// doubled walls and wrong path lengths, not small shape errors.
+pub fn check_swept_volume(
+    mesh: &RenderMesh,
+    cross_section_area: f64,
+    path_length: f64,
+    rel_tol: f64,
+) -> OracleVerdict {
+    let expected = cross_section_area * path_length;
+    let actual = crate::helpers::mesh_volume(mesh);
+    let rel_error = (actual - expected).abs() / expected.abs().max(f64::MIN_POSITIVE);
+
+    if rel_error <= rel_tol {
+        OracleVerdict::pass_val(
+            "swept_volume",
+            format!(
+                "volume {:.4} vs {:.4} x {:.4} = {:.4} ({:.2}% off)",
+                actual,
+                cross_section_area,
+                path_length,
+                expected,
+                rel_error * 100.0
+            ),
+            actual,
+        )
+    } else {
+        OracleVerdict::fail_val(
+            "swept_volume",
+            format!(
+                "volume {:.4}, expected {:.4} x {:.4} = {:.4} ({:.2}% off, tol {:.2}%)",
+                actual,
+                cross_section_area,
+                path_length,
+                expected,
+                rel_error * 100.0,
+                rel_tol * 100.0
+            ),
+            actual,
+        )
+    }
+}
+
 // ── Provenance Oracles ──────────────────────────────────────────────────────
 
 /// Check that a specific role exists in the OpResult provenance with at least min_count entries.
@@ -554,3 +602,212 @@ pub fn run_topology_checks(
         check_face_validity(introspect, solid),
     ]
 }
+
+// ── Operation Matrix ────────────────────────────────────────────────────────
+
+/// Size and tessellation tolerance for one pass over the operation matrix.
+#[derive(Debug, Clone, Copy)]
+pub struct SeedConfig {
+    /// Characteristic size of each primitive (edge length, diameter, height).
+    pub size: f64,
+    /// Tessellation tolerance.
+    pub tolerance: f64,
+}
+
+/// A primitive solid in the watertightness matrix.
+pub struct PrimitiveCase {
+    pub name: &'static str,
+    /// Build the primitive with its reference corner/base at `origin`.
+    pub build:
+        fn(&mut dyn KernelBundle, &SeedConfig, [f64; 3]) -> Result<KernelSolidHandle, KernelError>,
+}
+
+/// A single operation applied to each primitive, producing the mesh to check.
+pub struct OperationCase {
+    pub name: &'static str,
+    pub apply: fn(
+        &mut dyn KernelBundle,
+        &PrimitiveCase,
+        &KernelSolidHandle,
+        &SeedConfig,
+    ) -> Result<RenderMesh, KernelError>,
+}
+
+/// Primitives covered by [`assert_all_operations_watertight`].
+///
+/// Add a primitive here and every operation is exercised against it. The
+/// cylinder is a 24-sided prism. Spheres and cones are left out: truck
+/// doesn't tessellate revolved surfaces watertight.
+pub fn default_primitives() -> Vec<PrimitiveCase> {
+    vec![
+        PrimitiveCase {
+            name: "box",
+            build: build_box,
+        },
+        PrimitiveCase {
+            name: "cylinder",
+            build: build_cylinder,
+        },
+    ]
+}
+
+/// Operations covered by [`assert_all_operations_watertight`].
+///
+/// Fillets and chamfers are left out until the truck kernel implements
+/// them; it reports both as `NotSupported`.
+pub fn default_operations() -> Vec<OperationCase> {
+    vec![
+        OperationCase {
+            name: "none",
+            apply: |kb, _, solid, seed| kb.tessellate(solid, seed.tolerance),
+        },
+        OperationCase {
+            name: "shell",
+            apply: |kb, _, solid, seed| {
+                let faces = first_of(kb.as_introspect().list_faces(solid));
+                let result = kb.shell(solid, &faces, seed.size * 0.1)?;
+                kb.tessellate(&result, seed.tolerance)
+            },
+        },
+        OperationCase {
+            name: "boolean_union",
+            apply: |kb, primitive, solid, seed| {
+                let offset = seed.size * 0.5;
+                let tool = (primitive.build)(kb, seed, [offset, offset, offset])?;
+                let result = kb.boolean_union(solid, &tool)?;
+                kb.tessellate(&result, seed.tolerance)
+            },
+        },
+    ]
+}
+
+/// Build every default primitive, apply every default operation, and check
+/// each resulting mesh is watertight.
+///
+/// Every combination must run: an operation the kernel reports as
+/// `NotSupported` fails the verdict like any other error. Returns a single
+/// verdict whose detail lists every failing (primitive, operation) pair.
+pub fn assert_all_operations_watertight(
+    kb: &mut dyn KernelBundle,
+    seed_configs: &[SeedConfig],
+) -> OracleVerdict {
+    check_operations_watertight(
+        kb,
+        &default_primitives(),
+        &default_operations(),
+        seed_configs,
+    )
+}
+
+/// Run an explicit primitive × operation matrix. See [`assert_all_operations_watertight`].
+pub fn check_operations_watertight(
+    kb: &mut dyn KernelBundle,
+    primitives: &[PrimitiveCase],
+    operations: &[OperationCase],
+    seed_configs: &[SeedConfig],
+) -> OracleVerdict {
+    let mut failures = Vec::new();
+    let mut checked = 0usize;
+
+    for seed in seed_configs {
+        for primitive in primitives {
+            for operation in operations {
+                let label = format!("{}/{} (size {})", primitive.name, operation.name, seed.size);
+                let outcome = (primitive.build)(kb, seed, [0.0, 0.0, 0.0])
+                    .and_then(|solid| (operation.apply)(kb, primitive, &solid, seed));
+                let problem = match outcome {
+                    Ok(mesh) => {
+                        checked += 1;
+                        let verdict = check_watertight_mesh(&mesh);
+                        if verdict.passed {
+                            continue;
+                        }
+                        verdict.detail
+                    }
+                    Err(e) => format!("kernel error: {}", e),
+                };
+                failures.push(format!("{}: {}", label, problem));
+            }
+        }
+    }
+
+    let summary = format!("{} meshes checked", checked);
+    if failures.is_empty() {
+        OracleVerdict::pass_val("operations_watertight", summary, checked as f64)
+    } else {
+        OracleVerdict::fail_val(
+            "operations_watertight",
+            format!(
+                "{} failures ({}): {}",
+                failures.len(),
+                summary,
+                failures.join("; ")
+            ),
+            failures.len() as f64,
+        )
+    }
+}
+
+fn first_of(ids: Vec<kernel_fork::KernelId>) -> Vec<kernel_fork::KernelId> {
+    ids.into_iter().take(1).collect()
+}
+
+/// Make a planar face on the XY plane (offset by `origin`) from a closed
+/// polygon, returning its kernel face ID.
+fn polygon_face(
+    kb: &mut dyn KernelBundle,
+    origin: [f64; 3],
+    points: &[(f64, f64)],
+) -> Result<kernel_fork::KernelId, KernelError> {
+    let positions: HashMap<u32, (f64, f64)> = points
+        .iter()
+        .enumerate()
+        .map(|(i, &p)| (i as u32 + 1, p))
+        .collect();
+    let profile = ClosedProfile {
+        entity_ids: (1..=points.len() as u32).collect(),
+        is_outer: true,
+    };
+    let faces = kb.make_faces_from_profiles(
+        &[profile],
+        origin,
+        [0.0, 0.0, 1.0],
+        [1.0, 0.0, 0.0],
+        &positions,
+    )?;
+    faces.first().copied().ok_or_else(|| KernelError::Other {
+        message: "profile produced no face".to_string(),
+    })
+}
+
+/// Points of a polygonal arc of radius `r` from `start` to `end` radians.
+fn arc_points(r: f64, start: f64, end: f64, segments: u32) -> Vec<(f64, f64)> {
+    (0..=segments)
+        .map(|i| {
+            let a = start + (end - start) * i as f64 / segments as f64;
+            (r * a.cos(), r * a.sin())
+        })
+        .collect()
+}
+
+fn build_box(
+    kb: &mut dyn KernelBundle,
+    seed: &SeedConfig,
+    origin: [f64; 3],
+) -> Result<KernelSolidHandle, KernelError> {
+    let s = seed.size;
+    let face = polygon_face(kb, origin, &[(0.0, 0.0), (s, 0.0), (s, s), (0.0, s)])?;
+    kb.extrude_face(face, [0.0, 0.0, 1.0], s)
+}
+
+fn build_cylinder(
+    kb: &mut dyn KernelBundle,
+    seed: &SeedConfig,
+    origin: [f64; 3],
+) -> Result<KernelSolidHandle, KernelError> {
+    let tau = std::f64::consts::TAU;
+    let mut points = arc_points(seed.size / 2.0, 0.0, tau, 24);
+    points.pop(); // last point repeats the first
+    let face = polygon_face(kb, origin, &points)?;
+    kb.extrude_face(face, [0.0, 0.0, 1.0], seed.size)
+}
@@ -6,7 +6,8 @@
 use std::fmt;
 
 use feature_engine::types::*;
-use waffle_types::SketchEntity;
+use kernel_fork::{KernelId, KernelIntrospect, KernelSolidHandle};
+use waffle_types::{SketchEntity, TopoKind};
 
 use crate::helpers::HarnessError;
 use crate::oracle::OracleVerdict;
@@ -153,6 +154,8 @@ impl ModelBuilder {
                 Operation::Chamfer { .. } => "Chamfer",
                 Operation::Shell { .. } => "Shell",
                 Operation::BooleanCombine { .. } => "Boolean",
+                Operation::AutoFillet { .. } => "AutoFillet",
+                Operation::StaticSolid { .. } => "StaticSolid",
             };
 
             let detail = describe_operation(&feature.operation);
@@ -327,5 +330,103 @@ fn describe_operation(op: &Operation) -> String {
             };
             format!("Params: {}", op_name)
         }
+        Operation::AutoFillet { params } => {
+            let edges = if params.only_convex {
+                "convex edges"
+            } else {
+                "sharp edges"
+            };
+            match params.max_edges {
+                Some(max) => format!(
+                    "Params: radius={:.3}, up to {} {}",
+                    params.radius, max, edges
+                ),
+                None => format!("Params: radius={:.3}, all {}", params.radius, edges),
+            }
+        }
+        Operation::StaticSolid { source, .. } => {
+            format!("Params: baked from {} features", source.len())
+        }
+    }
+}
+
+/// Dump the full topology of a solid as text, for root-causing kernel bugs.
+///
+/// Lists every face (surface type, area, normal, bounding edges), edge
+/// (curve type, length, endpoints) and vertex (position). Entities are
+/// numbered `F0`, `E0`, `V0`, ... in the kernel's listing order, so two dumps
+/// of the same solid compare cleanly; kernel IDs are shown in brackets.
+pub fn dump_brep(introspect: &dyn KernelIntrospect, solid: &KernelSolidHandle) -> String {
+    let faces = introspect.list_faces(solid);
+    let edges = introspect.list_edges(solid);
+    let vertices = introspect.list_vertices(solid);
+    let label = |ids: &[KernelId], prefix: char, id: KernelId| {
+        match ids.iter().position(|&x| x == id) {
+            Some(i) => format!("{}{}", prefix, i),
+            None => format!("{}?[{}]", prefix, id.0),
+        }
+    };
+    let fmt_point = |p: [f64; 3]| format!("({:.3}, {:.3}, {:.3})", p[0], p[1], p[2]);
+
+    let mut out = format!(
+        "B-Rep: {} faces, {} edges, {} vertices\n",
+        faces.len(),
+        edges.len(),
+        vertices.len(),
+    );
+
+    out.push_str("Faces:\n");
+    for (i, &face) in faces.iter().enumerate() {
+        let sig = introspect.compute_signature(face, TopoKind::Face);
+        let loop_edges: Vec<String> = introspect
+            .face_edges(face)
+            .into_iter()
+            .map(|e| label(&edges, 'E', e))
+            .collect();
+        out.push_str(&format!(
+            "  F{} [{}] {}",
+            i,
+            face.0,
+            sig.surface_type.as_deref().unwrap_or("unknown"),
+        ));
+        if let Some(area) = sig.area {
+            out.push_str(&format!(" area={:.3}", area));
+        }
+        if let Some(normal) = sig.normal {
+            out.push_str(&format!(" normal={}", fmt_point(normal)));
+        }
+        out.push_str(&format!(" edges: {}\n", loop_edges.join(" ")));
     }
+
+    out.push_str("Edges:\n");
+    for (i, &edge) in edges.iter().enumerate() {
+        let sig = introspect.compute_signature(edge, TopoKind::Edge);
+        let (start, end) = introspect.edge_vertices(edge);
+        out.push_str(&format!(
+            "  E{} [{}] {}",
+            i,
+            edge.0,
+            sig.surface_type.as_deref().unwrap_or("unknown"),
+        ));
+        if let Some(length) = sig.length {
+            out.push_str(&format!(" length={:.3}", length));
+        }
+        out.push_str(&format!(
+            " {} -> {}\n",
+            label(&vertices, 'V', start),
+            label(&vertices, 'V', end),
+        ));
+    }
+
+    out.push_str("Vertices:\n");
+    for (i, &vertex) in vertices.iter().enumerate() {
+        let sig = introspect.compute_signature(vertex, TopoKind::Vertex);
+        let position = sig
+            .centroid
+            .map(fmt_point)
+            .unwrap_or_else(|| "(unknown)".to_string());
+        out.push_str(&format!("  V{} [{}] {}\n", i, vertex.0, position));
+    }
+
+    out
 }
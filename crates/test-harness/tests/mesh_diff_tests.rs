@@ -0,0 +1,81 @@
+//! Tests for the visual mesh diff.
+
+use kernel_fork::types::RenderMesh;
+use test_harness::mesh_diff::{
+    mesh_diff_image, A_ONLY_COLOR, B_ONLY_COLOR, SHARED_COLOR, ViewDirection,
+};
+
+const SIZE: usize = 100;
+
+/// Prism of height 1 over a convex CCW polygon in the XY plane.
+fn prism(outline: &[(f32, f32)]) -> RenderMesh {
+    let n = outline.len() as u32;
+    let mut vertices = Vec::new();
+    for z in [0.0, 1.0] {
+        for &(x, y) in outline {
+            vertices.extend_from_slice(&[x, y, z]);
+        }
+    }
+    let mut indices = Vec::new();
+    for i in 1..n - 1 {
+        indices.extend_from_slice(&[0, i + 1, i]);
+        indices.extend_from_slice(&[n, n + i, n + i + 1]);
+    }
+    for i in 0..n {
+        let j = (i + 1) % n;
+        indices.extend_from_slice(&[i, j, n + j, i, n + j, n + i]);
+    }
+    RenderMesh {
+        normals: vec![0.0; vertices.len()],
+        vertices,
+        indices,
+        face_ranges: Vec::new(),
+    }
+}
+
+fn unit_box() -> RenderMesh {
+    prism(&[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)])
+}
+
+/// Unit box with the vertical edge at (1, 1) chamfered back by 0.3.
+fn chamfered_box() -> RenderMesh {
+    prism(&[(0.0, 0.0), (1.0, 0.0), (1.0, 0.7), (0.7, 1.0), (0.0, 1.0)])
+}
+
+/// (row, column) of every pixel with the given color.
+fn pixels_of(image: &[u8], color: [u8; 3]) -> Vec<(usize, usize)> {
+    image
+        .chunks_exact(3)
+        .enumerate()
+        .filter(|(_, px)| *px == color)
+        .map(|(i, _)| (i / SIZE, i % SIZE))
+        .collect()
+}
+
+#[test]
+fn identical_meshes_have_no_diff() {
+    let image = mesh_diff_image(&unit_box(), &unit_box(), SIZE, SIZE, ViewDirection::Isometric);
+    assert_eq!(image.len(), SIZE * SIZE * 3);
+    assert!(pixels_of(&image, A_ONLY_COLOR).is_empty());
+    assert!(pixels_of(&image, B_ONLY_COLOR).is_empty());
+    assert!(!pixels_of(&image, SHARED_COLOR).is_empty());
+}
+
+#[test]
+fn chamfered_corner_is_highlighted() {
+    let image = mesh_diff_image(&unit_box(), &chamfered_box(), SIZE, SIZE, ViewDirection::Top);
+
+    // The box is framed into 90x90 pixels; the removed corner is a right
+    // triangle with 0.3 legs, so about 0.045 * 8100 pixels.
+    let removed = pixels_of(&image, A_ONLY_COLOR);
+    assert!((300..430).contains(&removed.len()), "{}", removed.len());
+    // +X is right and +Y is up, so the (1, 1) corner is the top-right.
+    assert!(removed.iter().all(|&(row, col)| row < 40 && col > 60));
+
+    assert!(pixels_of(&image, B_ONLY_COLOR).is_empty());
+    assert!(pixels_of(&image, SHARED_COLOR).len() > 7000);
+
+    // Swapping the inputs swaps the colors.
+    let swapped = mesh_diff_image(&chamfered_box(), &unit_box(), SIZE, SIZE, ViewDirection::Top);
+    assert_eq!(pixels_of(&swapped, B_ONLY_COLOR), removed);
+}
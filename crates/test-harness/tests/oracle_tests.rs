@@ -145,6 +145,22 @@ fn valid_indices_passes_for_mock_box() {
     );
 }
 
+#[test]
+fn swept_volume_matches_square_along_straight_path() {
+    let mut m = ModelBuilder::mock();
+    m.rect_sketch("sk", [0., 0., 0.], [0., 0., 1.], 0., 0., 1., 1.)
+        .unwrap();
+    m.extrude("bar", "sk", 10.0).unwrap();
+    let mesh = m.tessellate("bar").unwrap();
+
+    let result = check_swept_volume(&mesh, 1.0, 10.0, 0.02);
+    assert!(result.passed, "{}", result.detail);
+    assert!((result.value.unwrap() - 10.0).abs() < 0.2);
+
+    let result = check_swept_volume(&mesh, 1.0, 12.0, 0.02);
+    assert!(!result.passed, "a 12-unit path should not match");
+}
+
 // ── Failing Oracle Tests (deliberately broken meshes) ───────────────────
 
 #[test]
@@ -208,3 +224,76 @@ fn run_all_mesh_checks_returns_multiple_verdicts() {
         );
     }
 }
+
+// ── Operation Matrix Tests ──────────────────────────────────────────────
+
+#[test]
+fn all_operations_watertight_for_current_primitives() {
+    let mut kernel = kernel_fork::TruckKernel::new();
+    let seeds = [SeedConfig {
+        size: 10.0,
+        tolerance: 0.1,
+    }];
+    let verdict = assert_all_operations_watertight(&mut kernel, &seeds);
+    assert!(verdict.passed, "{}", verdict.detail);
+    // Every primitive × operation pair produced a mesh.
+    let pairs = default_primitives().len() * default_operations().len();
+    assert_eq!(verdict.value, Some(pairs as f64), "{}", verdict.detail);
+}
+
+#[test]
+fn operation_matrix_reports_broken_operation() {
+    let mut kernel = kernel_fork::TruckKernel::new();
+    let seeds = [SeedConfig {
+        size: 10.0,
+        tolerance: 0.1,
+    }];
+    let primitives = default_primitives();
+    let broken = OperationCase {
+        name: "drop_triangle",
+        apply: |kb, _, solid, seed| {
+            let mut mesh = kb.tessellate(solid, seed.tolerance)?;
+            let len = mesh.indices.len();
+            mesh.indices.truncate(len - 3);
+            Ok(mesh)
+        },
+    };
+
+    let verdict = check_operations_watertight(&mut kernel, &primitives, &[broken], &seeds);
+    assert!(
+        !verdict.passed,
+        "dropping a triangle must break watertightness"
+    );
+    assert!(
+        verdict.detail.contains("box/drop_triangle"),
+        "{}",
+        verdict.detail
+    );
+    assert!(
+        verdict.detail.contains("cylinder/drop_triangle"),
+        "{}",
+        verdict.detail
+    );
+}
+
+#[test]
+fn operation_matrix_fails_on_unsupported_operation() {
+    let mut kernel = kernel_fork::TruckKernel::new();
+    let seeds = [SeedConfig {
+        size: 10.0,
+        tolerance: 0.1,
+    }];
+    let unsupported = OperationCase {
+        name: "fillet",
+        apply: |kb, _, solid, seed| {
+            let edges = kb.as_introspect().list_edges(solid);
+            let result = kb.fillet_edges(solid, &edges[..1], seed.size * 0.1)?;
+            kb.tessellate(&result, seed.tolerance)
+        },
+    };
+
+    let verdict =
+        check_operations_watertight(&mut kernel, &default_primitives(), &[unsupported], &seeds);
+    assert!(!verdict.passed, "a skipped operation must not pass");
+    assert!(verdict.detail.contains("box/fillet"), "{}", verdict.detail);
+}
@@ -1,5 +1,7 @@
 //! Tests for the report module.
 
+use modeling_ops::KernelBundle;
+use test_harness::report::dump_brep;
 use test_harness::ModelBuilder;
 
 #[test]
@@ -112,3 +114,25 @@ fn report_shows_sketch_details() {
     assert!(text.contains("points"), "Should describe sketch entities");
     assert!(text.contains("lines"), "Should describe sketch entities");
 }
+
+#[test]
+fn dump_brep_lists_box_topology() {
+    let mut m = ModelBuilder::mock();
+    m.rect_sketch("sk", [0., 0., 0.], [0., 0., 1.], 0., 0., 10., 10.)
+        .unwrap();
+    m.extrude("box", "sk", 10.0).unwrap();
+
+    let handle = m.solid_handle("box").unwrap();
+    let text = dump_brep(m.kernel().as_introspect(), &handle);
+    assert!(
+        text.contains("6 faces, 12 edges, 8 vertices"),
+        "Should summarize box topology: {}",
+        text
+    );
+    assert_eq!(text.lines().filter(|l| l.starts_with("  F")).count(), 6);
+    assert_eq!(text.lines().filter(|l| l.starts_with("  E")).count(), 12);
+    assert_eq!(text.lines().filter(|l| l.starts_with("  V")).count(), 8);
+    assert!(text.contains("(0.000, 0.000, 0.000)"), "{}", text);
+    assert!(text.contains("(10.000, 10.000, 10.000)"), "{}", text);
+    assert!(!text.contains("?["), "Every reference should resolve: {}", text);
+}
@@ -29,4 +29,23 @@ pub enum Role {
     BooleanBodyAFace { index: usize },
     /// A face from the second body in a boolean operation.
     BooleanBodyBFace { index: usize },
+    /// An edge of the original profile (on the sketch-plane cap of an extrude,
+    /// or the start face of a revolve).
+    ProfileEdge,
+    /// An edge traced out by a profile vertex moving along the sweep.
+    SweepEdge,
+    /// An edge of the far end cap of an extrude or revolve.
+    CapEdge,
+}
+
+/// Which operand of a boolean a result face came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum FaceOrigin {
+    /// Inherited from the first body (the target of a subtract).
+    FromToolA,
+    /// Inherited from the second body (the tool of a subtract).
+    FromToolB,
+    /// Matches neither operand; created where the bodies intersect.
+    FromIntersection,
 }
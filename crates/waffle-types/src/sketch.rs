@@ -200,11 +200,23 @@ pub enum SketchConstraint {
         line_c: u32,
         line_d: u32,
     },
+    /// Equal angles at every joint of a closed chain of lines, each line
+    /// meeting the next and the last meeting the first.
+    EqualAngles {
+        lines: Vec<u32>,
+    },
+    /// `len(entity_a) = value * len(entity_b)` for two lines.
     Ratio {
         entity_a: u32,
         entity_b: u32,
         value: f64,
     },
+    /// `len(line_a) - len(line_b) = value`, e.g. "always 5 mm longer".
+    LengthDifference {
+        line_a: u32,
+        line_b: u32,
+        value: f64,
+    },
     EqualPointToLine {
         point_a: u32,
         point_b: u32,
@@ -214,6 +226,11 @@ pub enum SketchConstraint {
         entity_a: u32,
         entity_b: u32,
     },
+    /// Two circles or arcs share a center.
+    Concentric {
+        entity_a: u32,
+        entity_b: u32,
+    },
 }
 
 /// Result of running the constraint solver.
@@ -224,7 +241,8 @@ pub enum SolveStatus {
     FullyConstrained,
     /// All constraints satisfied, but geometry can still move.
     UnderConstrained { dof: u32 },
-    /// Constraints are contradictory.
+    /// Constraints are contradictory. `conflicts` holds the indices of the
+    /// redundant or conflicting constraints, across every subsystem.
     OverConstrained { conflicts: Vec<u32> },
     /// Solver failed to converge.
     SolveFailed { reason: String },
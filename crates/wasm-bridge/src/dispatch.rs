@@ -19,6 +19,7 @@ pub fn dispatch(state: &mut EngineState, msg: UiToEngine, kb: &mut dyn KernelBun
         Err(e) => EngineToUi::Error {
             message: e.to_string(),
             feature_id: None,
+            kind: e.kind(),
         },
     }
 }
@@ -48,11 +49,7 @@ fn handle_message(
         UiToEngine::SolveSketch => {
             #[cfg(feature = "native-solver")]
             {
-                let sketch = state.build_sketch()?;
-                let solved = sketch_solver::solve_sketch(&sketch);
-                if let Some(active) = state.active_sketch.as_mut() {
-                    active.solve_status = solved.status.clone();
-                }
+                let solved = state.solve_active_sketch(sketch_solver::solve_sketch)?;
                 Ok(EngineToUi::SketchSolved { solved })
             }
             #[cfg(not(feature = "native-solver"))]
@@ -177,13 +174,17 @@ fn handle_message(
             operation: "ExportStep (requires TruckKernel)".to_string(),
         }),
 
-        UiToEngine::ExportStl => {
+        UiToEngine::ExportStl {
+            auto_repair,
+            solidity,
+        } => {
             let mesh = find_last_mesh(state);
             match mesh {
                 Some(mesh) => {
-                    let bytes = crate::stl_export::render_mesh_to_stl(&mesh);
+                    let (bytes, warnings) =
+                        crate::stl_export::export_stl(mesh, auto_repair, solidity);
                     let stl_data = base64::engine::general_purpose::STANDARD.encode(&bytes);
-                    Ok(EngineToUi::StlExportReady { stl_data })
+                    Ok(EngineToUi::StlExportReady { stl_data, warnings })
                 }
                 None => Err(BridgeError::NoMeshData),
             }
@@ -231,5 +232,7 @@ fn operation_name(op: &Operation) -> String {
         Operation::Chamfer { .. } => "Chamfer".to_string(),
         Operation::Shell { .. } => "Shell".to_string(),
         Operation::BooleanCombine { .. } => "Boolean Combine".to_string(),
+        Operation::AutoFillet { .. } => "Auto Fillet".to_string(),
+        Operation::StaticSolid { .. } => "Static Solid".to_string(),
     }
 }
@@ -1,7 +1,15 @@
 use std::collections::HashMap;
 
+use feature_engine::types::EngineError;
 use feature_engine::Engine;
-use waffle_types::{ClosedProfile, GeomRef, Sketch, SketchConstraint, SketchEntity, SolveStatus};
+use kernel_fork::{KernelError, KernelId, RenderMesh};
+use modeling_ops::OpError;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use waffle_types::{
+    ClosedProfile, GeomRef, OutputKey, Sketch, SketchConstraint, SketchEntity, SolveStatus,
+    SolvedSketch,
+};
 
 /// The engine state wrapper for the WASM bridge.
 ///
@@ -17,6 +25,14 @@ pub struct EngineState {
     pub hover: Option<GeomRef>,
     /// Project name for save operations.
     pub project_name: String,
+    /// The last solver result for the active sketch, with the
+    /// [`solver_inputs`] it was solved from.
+    last_solve: Option<(String, SolvedSketch)>,
+    /// Number of times the solver has actually run.
+    solve_count: u64,
+    /// Display/print colors assigned to faces, by feature and the face's
+    /// id in that feature's mesh.
+    face_colors: HashMap<Uuid, HashMap<KernelId, [f32; 4]>>,
 }
 
 /// An active sketch editing session.
@@ -40,6 +56,9 @@ impl EngineState {
             selection: Vec::new(),
             hover: None,
             project_name: "Untitled".to_string(),
+            last_solve: None,
+            solve_count: 0,
+            face_colors: HashMap::new(),
         }
     }
 
@@ -51,6 +70,7 @@ impl EngineState {
             constraints: Vec::new(),
             solve_status: SolveStatus::UnderConstrained { dof: 0 },
         });
+        self.last_solve = None;
     }
 
     /// Add an entity to the active sketch.
@@ -96,6 +116,130 @@ impl EngineState {
         })
     }
 
+    /// Solve the active sketch with `solve`, reusing the previous result when
+    /// its plane, entities and constraints are unchanged.
+    ///
+    /// The active sketch's solve status is updated either way.
+    pub fn solve_active_sketch(
+        &mut self,
+        solve: impl FnOnce(&Sketch) -> SolvedSketch,
+    ) -> Result<SolvedSketch, BridgeError> {
+        let sketch = self.build_sketch()?;
+        let inputs = solver_inputs(&sketch)?;
+        let solved = match &self.last_solve {
+            Some((cached_inputs, cached)) if *cached_inputs == inputs => cached.clone(),
+            _ => {
+                let solved = solve(&sketch);
+                self.solve_count += 1;
+                self.last_solve = Some((inputs, solved.clone()));
+                solved
+            }
+        };
+        if let Some(active) = self.active_sketch.as_mut() {
+            active.solve_status = solved.status.clone();
+        }
+        Ok(solved)
+    }
+
+    /// Number of times [`solve_active_sketch`](Self::solve_active_sketch)
+    /// has run the solver rather than answering from its cache.
+    pub fn solve_count(&self) -> u64 {
+        self.solve_count
+    }
+
+    /// Export the listed faces of a feature's main body as a standalone OBJ
+    /// patch, see [`render_faces_to_obj`](crate::stl_export::render_faces_to_obj).
+    pub fn export_faces_obj(
+        &self,
+        feature_id: Uuid,
+        face_indices: &[usize],
+    ) -> Result<String, BridgeError> {
+        let mesh = self.main_mesh(feature_id)?;
+        crate::stl_export::render_faces_to_obj(mesh, face_indices).ok_or_else(|| {
+            BridgeError::FaceOutOfRange {
+                indices: face_indices.to_vec(),
+                count: mesh.face_ranges.len(),
+            }
+        })
+    }
+
+    /// Assign an RGBA color to a face of a feature's main body, replacing
+    /// any color it already had.
+    ///
+    /// The color follows the face's id in the current mesh, not its index:
+    /// rebuilding the feature re-tessellates it with fresh ids, so its
+    /// colors are dropped instead of landing on whichever face now has the
+    /// same index.
+    pub fn set_face_color(
+        &mut self,
+        feature_id: Uuid,
+        face_index: usize,
+        rgba: [f32; 4],
+    ) -> Result<(), BridgeError> {
+        let mesh = self.main_mesh(feature_id)?;
+        let face_id = mesh
+            .face_ranges
+            .get(face_index)
+            .ok_or_else(|| BridgeError::FaceOutOfRange {
+                indices: vec![face_index],
+                count: mesh.face_ranges.len(),
+            })?
+            .face_id;
+        let current: Vec<KernelId> = mesh.face_ranges.iter().map(|r| r.face_id).collect();
+        let colors = self.face_colors.entry(feature_id).or_default();
+        // Colors left over from before a rebuild can never match again
+        colors.retain(|id, _| current.contains(id));
+        colors.insert(face_id, rgba);
+        Ok(())
+    }
+
+    /// Export a feature's main body as a grouped OBJ and its MTL library,
+    /// with the colors set by [`set_face_color`](Self::set_face_color).
+    pub fn export_obj_with_colors(
+        &self,
+        feature_id: Uuid,
+        mtl_file: &str,
+    ) -> Result<(String, String), BridgeError> {
+        let mesh = self.main_mesh(feature_id)?;
+        let colors: HashMap<usize, [f32; 4]> = match self.face_colors.get(&feature_id) {
+            Some(by_id) => mesh
+                .face_ranges
+                .iter()
+                .enumerate()
+                .filter_map(|(index, range)| by_id.get(&range.face_id).map(|c| (index, *c)))
+                .collect(),
+            None => HashMap::new(),
+        };
+        Ok(crate::stl_export::render_mesh_to_obj_mtl(
+            mesh, &colors, mtl_file,
+        ))
+    }
+
+    /// A feature's main-body mesh split into self-contained chunks of at
+    /// most `max_triangles` triangles, for incremental upload to the GPU.
+    pub fn mesh_chunks(
+        &self,
+        feature_id: Uuid,
+        max_triangles: usize,
+    ) -> Result<Vec<RenderMesh>, BridgeError> {
+        let mesh = self.main_mesh(feature_id)?;
+        Ok(kernel_fork::mesh::mesh_chunks(mesh, max_triangles))
+    }
+
+    fn main_mesh(&self, feature_id: Uuid) -> Result<&RenderMesh, BridgeError> {
+        self.engine
+            .feature_results
+            .get(&feature_id)
+            .and_then(|result| {
+                result
+                    .outputs
+                    .iter()
+                    .find(|(key, _)| *key == OutputKey::Main)
+            })
+            .and_then(|(_, body)| body.mesh.as_ref())
+            .ok_or(BridgeError::NoMeshData)
+    }
+
     /// Finish the active sketch and commit it as a feature.
     /// Accepts solved positions, profiles, and plane geometry from the JS-side solver.
     pub fn finish_sketch(
@@ -115,6 +259,16 @@ impl EngineState {
     }
 }
 
+/// Everything that affects a sketch's solution, serialized: its plane,
+/// entities and constraints (including their dimension values).
+fn solver_inputs(sketch: &Sketch) -> Result<String, BridgeError> {
+    serde_json::to_string(&(&sketch.plane, &sketch.entities, &sketch.constraints)).map_err(|e| {
+        BridgeError::Serialization {
+            reason: e.to_string(),
+        }
+    })
+}
+
 impl Default for EngineState {
     fn default() -> Self {
         Self::new()
@@ -128,7 +282,7 @@ pub enum BridgeError {
     NoActiveSketch,
 
     #[error("engine error: {0}")]
-    Engine(#[from] feature_engine::types::EngineError),
+    Engine(#[from] EngineError),
 
     #[error("serialization error: {reason}")]
     Serialization { reason: String },
@@ -138,4 +292,92 @@ pub enum BridgeError {
 
     #[error("no mesh data available for export")]
     NoMeshData,
+
+    #[error("face indices {indices:?} out of range ({count} faces)")]
+    FaceOutOfRange { indices: Vec<usize>, count: usize },
+}
+
+impl From<KernelError> for BridgeError {
+    fn from(e: KernelError) -> Self {
+        BridgeError::Engine(e.into())
+    }
+}
+
+impl From<OpError> for BridgeError {
+    fn from(e: OpError) -> Self {
+        BridgeError::Engine(e.into())
+    }
+}
+
+impl BridgeError {
+    /// The category the UI should treat this error as.
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            BridgeError::NoActiveSketch => ErrorKind::Solver,
+            BridgeError::Engine(e) => engine_error_kind(e),
+            BridgeError::Serialization { .. } => ErrorKind::Serialization,
+            BridgeError::NotImplemented { .. } => ErrorKind::NotSupported,
+            BridgeError::NoMeshData => ErrorKind::Internal,
+            BridgeError::FaceOutOfRange { .. } => ErrorKind::Index,
+        }
+    }
+}
+
+/// Stable category of an engine error, sent with every `EngineToUi::Error`
+/// so the UI can react to a failure without parsing its message.
+///
+/// Serialized in snake_case; existing tags must not be renamed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// A dimension or other parameter value is invalid.
+    Dimension,
+    /// A feature, sketch or topological reference could not be found or
+    /// resolved, or the kernel could not build the requested shape.
+    Topology,
+    /// A boolean operation failed.
+    Boolean,
+    /// There is no sketch to edit or it yielded nothing usable.
+    Solver,
+    /// An index is out of range.
+    Index,
+    /// A message or project file could not be read or written.
+    Serialization,
+    /// The operation is not available yet.
+    NotSupported,
+    /// Anything else, including undo/redo with nothing to do.
+    #[default]
+    Internal,
+}
+
+fn engine_error_kind(e: &EngineError) -> ErrorKind {
+    match e {
+        EngineError::FeatureNotFound { .. }
+        | EngineError::SketchNotFound { .. }
+        | EngineError::ResolutionFailed { .. }
+        | EngineError::NothingToBake { .. } => ErrorKind::Topology,
+        EngineError::ProfileOutOfRange { .. } => ErrorKind::Index,
+        EngineError::KernelError(k) | EngineError::OpError(OpError::Kernel(k)) => {
+            kernel_error_kind(k)
+        }
+        EngineError::OpError(OpError::NoProfiles) => ErrorKind::Solver,
+        EngineError::OpError(OpError::InvalidParameter { .. })
+        | EngineError::InvalidParameter { .. } => ErrorKind::Dimension,
+        EngineError::RebuildFailed { .. }
+        | EngineError::NothingToUndo
+        | EngineError::NothingToRedo => ErrorKind::Internal,
+        EngineError::ParseError { .. } => ErrorKind::Serialization,
+    }
+}
+
+fn kernel_error_kind(e: &KernelError) -> ErrorKind {
+    match e {
+        KernelError::BooleanFailed { .. } => ErrorKind::Boolean,
+        KernelError::FilletFailed { .. }
+        | KernelError::ShellFailed { .. }
+        | KernelError::ProfileMismatch { .. }
+        | KernelError::EntityNotFound { .. } => ErrorKind::Topology,
+        KernelError::NotSupported { .. } => ErrorKind::NotSupported,
+        KernelError::TessellationFailed { .. } | KernelError::Other { .. } => ErrorKind::Internal,
+    }
 }
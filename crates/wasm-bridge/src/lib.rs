@@ -7,5 +7,5 @@ pub mod stl_export;
 pub mod wasm_api;
 
 pub use dispatch::dispatch;
-pub use engine_state::{BridgeError, EngineState};
+pub use engine_state::{BridgeError, EngineState, ErrorKind};
 pub use messages::{EngineToUi, UiToEngine};
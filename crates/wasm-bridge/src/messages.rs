@@ -4,9 +4,11 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use feature_engine::types::{FeatureTree, Operation};
-use kernel_fork::{EdgeRenderData, RenderMesh};
+use kernel_fork::{EdgeRenderData, RenderMesh, Solidity};
 use waffle_types::{ClosedProfile, GeomRef, SketchConstraint, SketchEntity, SolvedSketch};
 
+use crate::engine_state::ErrorKind;
+
 /// Serde helper for HashMap<u32, (f64, f64)> — JSON string keys ↔ u32.
 mod u32_key_map {
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
@@ -131,7 +133,14 @@ pub enum UiToEngine {
         data: String,
     },
     ExportStep,
-    ExportStl,
+    ExportStl {
+        /// Repair the mesh (winding, holes, duplicate faces) before writing.
+        #[serde(default)]
+        auto_repair: bool,
+        /// Export as an open sheet: borders are kept rather than capped.
+        #[serde(default)]
+        solidity: Solidity,
+    },
 }
 
 /// Messages from the engine (WASM Worker) to the UI (JavaScript main thread).
@@ -158,6 +167,9 @@ pub enum EngineToUi {
     Error {
         message: String,
         feature_id: Option<Uuid>,
+        /// Stable category of the failure; `internal` when absent.
+        #[serde(default)]
+        kind: ErrorKind,
     },
 
     /// Save project is ready.
@@ -169,6 +181,11 @@ pub enum EngineToUi {
     /// STEP export is ready.
     ExportReady { step_data: String },
 
-    /// STL export is ready (base64-encoded binary STL).
-    StlExportReady { stl_data: String },
+    /// STL export is ready (base64-encoded binary STL), with a note for each
+    /// kind of fix applied when the export was auto-repaired.
+    StlExportReady {
+        stl_data: String,
+        #[serde(default)]
+        warnings: Vec<String>,
+    },
 }
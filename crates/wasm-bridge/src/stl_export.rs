@@ -1,4 +1,69 @@
-use kernel_fork::RenderMesh;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+
+use kernel_fork::mesh::RepairReport;
+use kernel_fork::{mesh, FaceRange, RenderMesh, Solidity};
+
+/// Mesh download formats the UI can request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Binary STL, as produced by [`render_mesh_to_stl`].
+    Stl,
+    /// Wavefront OBJ with per-vertex normals.
+    Obj,
+    /// Binary glTF (`.glb`) with positions, normals and indices, u16 for
+    /// meshes of up to 65535 vertices and u32 above.
+    Gltf,
+}
+
+/// Typical length of an OBJ `v`/`vn` line with six significant digits.
+const OBJ_VECTOR_LINE_BYTES: usize = 30;
+/// Allowance for the glTF JSON chunk describing a single mesh.
+const GLTF_JSON_BYTES: usize = 1024;
+/// Largest vertex count `file_format::mesh_to_glb` writes u16 indices for.
+const GLTF_MAX_SHORT_VERTICES: usize = 65535;
+
+/// Expected byte size of exporting `mesh` as `format`.
+///
+/// Lets the UI warn before committing WASM memory to a huge download.
+/// Exact for STL (`84 + 50 * triangles`) and for the glTF binary chunk, as
+/// laid out by `file_format::mesh_to_glb`; OBJ text and the glTF JSON chunk
+/// are estimated.
+pub fn export_size_estimate(mesh: &RenderMesh, format: ExportFormat) -> usize {
+    let vertex_count = mesh.vertices.len() / 3;
+    let tri_count = mesh.indices.len() / 3;
+    match format {
+        ExportFormat::Stl => 84 + tri_count * 50,
+        ExportFormat::Obj => {
+            // `f a//a b//b c//c\n`, each index written twice
+            let index_digits = vertex_count.max(1).to_string().len();
+            let face_line = 2 + 3 * (2 * index_digits + 3);
+            2 * vertex_count * OBJ_VECTOR_LINE_BYTES + tri_count * face_line
+        }
+        ExportFormat::Gltf => {
+            // GLB header + JSON chunk; a mesh with no triangles has no
+            // binary chunk
+            let header = 12 + 8 + GLTF_JSON_BYTES;
+            if vertex_count == 0 || tri_count == 0 {
+                return header;
+            }
+            // Positions, normals when there is one per vertex, then indices
+            // padded to four bytes
+            let normals = if mesh.normals.len() == mesh.vertices.len() {
+                vertex_count * 12
+            } else {
+                0
+            };
+            let index_bytes = if vertex_count > GLTF_MAX_SHORT_VERTICES {
+                4
+            } else {
+                2
+            };
+            let binary = vertex_count * 12 + normals + tri_count * 3 * index_bytes;
+            header + 8 + binary.next_multiple_of(4)
+        }
+    }
+}
 
 /// Convert a `RenderMesh` to binary STL format.
 ///
@@ -11,8 +76,7 @@ use kernel_fork::RenderMesh;
 ///   - 2 bytes: attribute byte count (0u16)
 pub fn render_mesh_to_stl(mesh: &RenderMesh) -> Vec<u8> {
     let tri_count = mesh.indices.len() / 3;
-    let size = 84 + tri_count * 50;
-    let mut buf = Vec::with_capacity(size);
+    let mut buf = Vec::with_capacity(export_size_estimate(mesh, ExportFormat::Stl));
 
     // 80-byte header
     let header = b"Waffle Iron STL Export";
@@ -73,6 +137,171 @@ pub fn render_mesh_to_stl(mesh: &RenderMesh) -> Vec<u8> {
     buf
 }
 
+/// Binary STL for `mesh`, optionally repaired first.
+///
+/// With `auto_repair` a solid goes through [`mesh::repair_mesh`] so a print
+/// doesn't ship with holes or flipped triangles. A sheet's borders are
+/// intentional, so it only has duplicate faces merged, and a warning is
+/// added if it has non-manifold or flipped edges. Returns the STL bytes and
+/// a warning for each kind of fix that was applied.
+pub fn export_stl(
+    mut mesh: RenderMesh,
+    auto_repair: bool,
+    solidity: Solidity,
+) -> (Vec<u8>, Vec<String>) {
+    let mut warnings = Vec::new();
+    if auto_repair {
+        let report = match solidity {
+            Solidity::Solid => mesh::repair_mesh(&mut mesh),
+            Solidity::Sheet => RepairReport {
+                merged_duplicates: mesh::merge_duplicate_faces(&mut mesh),
+                ..RepairReport::default()
+            },
+        };
+        if report.merged_duplicates > 0 {
+            warnings.push(format!(
+                "merged {} duplicate triangle pair(s)",
+                report.merged_duplicates
+            ));
+        }
+        if report.rewound_triangles > 0 {
+            warnings.push(format!(
+                "reversed the winding of {} triangle(s)",
+                report.rewound_triangles
+            ));
+        }
+        if report.filled_holes > 0 {
+            warnings.push(format!("filled {} hole(s)", report.filled_holes));
+        }
+    }
+    if solidity == Solidity::Sheet {
+        let validation = mesh::validate_mesh(&mesh);
+        if !validation.is_valid(Solidity::Sheet) {
+            warnings.push(format!(
+                "sheet has {} non-manifold and {} flipped edge(s)",
+                validation.non_manifold_edges, validation.flipped_edges
+            ));
+        }
+    }
+    (render_mesh_to_stl(&mesh), warnings)
+}
+
+/// Write the triangles of the listed faces of `mesh` as Wavefront OBJ text.
+///
+/// Each face becomes its own `g face_<index>` group, and only the vertices
+/// those triangles reference are written. Vertices are taken as they are in
+/// the mesh, so the patch is not welded to (or across) neighbouring faces.
+/// Normals are written when the mesh has them. Returns `None` if any index
+/// is not a face of the mesh.
+pub fn render_faces_to_obj(mesh: &RenderMesh, face_indices: &[usize]) -> Option<String> {
+    let ranges = face_indices
+        .iter()
+        .map(|&i| mesh.face_ranges.get(i).map(|range| (i, range)))
+        .collect::<Option<Vec<_>>>()?;
+    Some(write_obj(mesh, &ranges, None))
+}
+
+/// Write every face of `mesh` as a grouped OBJ plus the MTL library it
+/// references as `mtl_file`.
+///
+/// Faces with an entry in `face_colors` (face index -> RGBA) use a material
+/// of that color; faces with the same color share one material. Every other
+/// face uses [`DEFAULT_MATERIAL`], so no group inherits the material of the
+/// group before it. Returns `(obj, mtl)`.
+pub fn render_mesh_to_obj_mtl(
+    mesh: &RenderMesh,
+    face_colors: &HashMap<usize, [f32; 4]>,
+    mtl_file: &str,
+) -> (String, String) {
+    let ranges: Vec<_> = mesh.face_ranges.iter().enumerate().collect();
+    let obj = write_obj(mesh, &ranges, Some((mtl_file, face_colors)));
+
+    let mut materials: BTreeMap<String, [f32; 4]> = face_colors
+        .values()
+        .map(|color| (material_name(color), *color))
+        .collect();
+    if (0..mesh.face_ranges.len()).any(|face| !face_colors.contains_key(&face)) {
+        materials.insert(DEFAULT_MATERIAL.to_string(), DEFAULT_COLOR);
+    }
+    let mut mtl = String::from("# Waffle Iron MTL Export\n");
+    for (name, color) in &materials {
+        let _ = writeln!(mtl, "newmtl {}", name);
+        let _ = writeln!(mtl, "Kd {} {} {}", color[0], color[1], color[2]);
+        let _ = writeln!(mtl, "d {}", color[3]);
+    }
+    (obj, mtl)
+}
+
+/// Material for faces without a color of their own.
+pub const DEFAULT_MATERIAL: &str = "default";
+
+/// Light grey, opaque: the color of [`DEFAULT_MATERIAL`].
+const DEFAULT_COLOR: [f32; 4] = [0.8, 0.8, 0.8, 1.0];
+
+/// Material name for an RGBA color, e.g. `color_ff0000ff` for opaque red.
+fn material_name(color: &[f32; 4]) -> String {
+    let mut name = String::from("color_");
+    for c in color {
+        let _ = write!(name, "{:02x}", (c.clamp(0.0, 1.0) * 255.0).round() as u8);
+    }
+    name
+}
+
+fn write_obj(
+    mesh: &RenderMesh,
+    ranges: &[(usize, &FaceRange)],
+    materials: Option<(&str, &HashMap<usize, [f32; 4]>)>,
+) -> String {
+    let has_normals = mesh.normals.len() == mesh.vertices.len();
+
+    // Mesh vertex index -> 1-based OBJ index, in order of first use
+    let mut local = HashMap::new();
+    let mut order = Vec::new();
+    for (_, range) in ranges {
+        for &v in &mesh.indices[range.start_index as usize..range.end_index as usize] {
+            local.entry(v).or_insert_with(|| {
+                order.push(v as usize);
+                order.len()
+            });
+        }
+    }
+
+    let mut obj = String::with_capacity(export_size_estimate(mesh, ExportFormat::Obj));
+    obj.push_str("# Waffle Iron OBJ Export\n");
+    if let Some((mtl_file, _)) = materials {
+        let _ = writeln!(obj, "mtllib {}", mtl_file);
+    }
+    for &v in &order {
+        let p = &mesh.vertices[v * 3..v * 3 + 3];
+        let _ = writeln!(obj, "v {} {} {}", p[0], p[1], p[2]);
+    }
+    if has_normals {
+        for &v in &order {
+            let n = &mesh.normals[v * 3..v * 3 + 3];
+            let _ = writeln!(obj, "vn {} {} {}", n[0], n[1], n[2]);
+        }
+    }
+    for (face, range) in ranges {
+        let _ = writeln!(obj, "g face_{}", face);
+        if let Some((_, colors)) = materials {
+            let name = colors
+                .get(face)
+                .map_or_else(|| DEFAULT_MATERIAL.to_string(), material_name);
+            let _ = writeln!(obj, "usemtl {}", name);
+        }
+        let tri_indices = &mesh.indices[range.start_index as usize..range.end_index as usize];
+        for tri in tri_indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|k| local[&tri[k]]);
+            if has_normals {
+                let _ = writeln!(obj, "f {a}//{a} {b}//{b} {c}//{c}");
+            } else {
+                let _ = writeln!(obj, "f {a} {b} {c}");
+            }
+        }
+    }
+    obj
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,4 +364,303 @@ mod tests {
         assert_eq!(stl.len(), 184);
         assert_eq!(u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]), 2);
     }
+
+    #[test]
+    fn stl_size_estimate_matches_export() {
+        use kernel_fork::{mesh_primitives, primitives, tessellation};
+
+        let mut next_id = 0;
+        let mut meshes = vec![
+            tessellation::tessellate_solid(
+                &primitives::make_box(2.0, 1.0, 3.0),
+                0.01,
+                &mut next_id,
+            )
+            .unwrap(),
+            tessellation::tessellate_solid(
+                &primitives::make_cylinder(0.5, 2.0),
+                0.01,
+                &mut next_id,
+            )
+            .unwrap(),
+            tessellation::tessellate_solid(&primitives::make_sphere(1.0), 0.05, &mut next_id)
+                .unwrap(),
+        ];
+        meshes.push(mesh_primitives::spring(0.2, 1.0, 0.6, 3.0, 24).unwrap());
+
+        for mesh in &meshes {
+            assert!(!mesh.indices.is_empty());
+            let stl = render_mesh_to_stl(mesh);
+            assert_eq!(stl.len(), export_size_estimate(mesh, ExportFormat::Stl));
+        }
+    }
+
+    /// Unit cube with four vertices and a face range per side, as a kernel
+    /// tessellation splits them, and outward face normals.
+    fn split_box() -> RenderMesh {
+        let corner = |i: u32| [(i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32];
+        let faces: [([u32; 4], [f32; 3]); 6] = [
+            ([0, 2, 3, 1], [0.0, 0.0, -1.0]),
+            ([4, 5, 7, 6], [0.0, 0.0, 1.0]),
+            ([0, 1, 5, 4], [0.0, -1.0, 0.0]),
+            ([2, 6, 7, 3], [0.0, 1.0, 0.0]),
+            ([0, 4, 6, 2], [-1.0, 0.0, 0.0]),
+            ([1, 3, 7, 5], [1.0, 0.0, 0.0]),
+        ];
+        let mut mesh = RenderMesh {
+            vertices: vec![],
+            normals: vec![],
+            indices: vec![],
+            face_ranges: vec![],
+        };
+        for (f, (quad, normal)) in faces.iter().enumerate() {
+            let base = f as u32 * 4;
+            mesh.vertices.extend(quad.iter().flat_map(|&i| corner(i)));
+            mesh.normals.extend(normal.repeat(4));
+            mesh.indices
+                .extend([base, base + 1, base + 2, base, base + 2, base + 3]);
+            mesh.face_ranges.push(FaceRange {
+                face_id: kernel_fork::KernelId(f as u64),
+                start_index: f as u32 * 6,
+                end_index: f as u32 * 6 + 6,
+            });
+        }
+        mesh
+    }
+
+    #[test]
+    fn stl_round_trip_rewelds_box() {
+        let mesh = split_box();
+        let imported = file_format::stl_to_mesh(&render_mesh_to_stl(&mesh)).unwrap();
+        assert_eq!(imported.vertices.len() / 3, 8);
+        assert_eq!(imported.indices.len() / 3, 12);
+        assert_eq!(imported.normals.len(), imported.vertices.len());
+        assert!(kernel_fork::mesh::validate_mesh(&imported).is_closed_manifold());
+        assert!((kernel_fork::mesh::signed_volume(&imported) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn obj_round_trip_of_box() {
+        let mesh = split_box();
+        let obj = render_faces_to_obj(&mesh, &[0, 1, 2, 3, 4, 5]).unwrap();
+
+        let imported = file_format::obj_to_mesh(&obj).unwrap();
+        assert_eq!(imported.indices.len() / 3, 12);
+        // Each corner keeps one vertex per face normal.
+        assert_eq!(imported.vertices.len() / 3, 24);
+        assert_eq!(imported.normals, mesh.normals);
+        assert!(kernel_fork::mesh::validate_mesh(&imported).is_closed_manifold());
+    }
+
+    #[test]
+    fn gltf_estimate_matches_binary_chunk() {
+        let mut triangle = split_box();
+        triangle.vertices.truncate(9);
+        triangle.normals.truncate(9);
+        triangle.indices.truncate(3);
+        triangle.face_ranges.truncate(1);
+        triangle.face_ranges[0].end_index = 3;
+
+        for mesh in [split_box(), triangle] {
+            let glb = file_format::mesh_to_glb(&mesh);
+            let word = |at: usize| u32::from_le_bytes(glb[at..at + 4].try_into().unwrap()) as usize;
+            let json_len = word(12);
+            let bin_len = word(20 + json_len);
+
+            let estimate = export_size_estimate(&mesh, ExportFormat::Gltf);
+            assert_eq!(estimate - (12 + 8 + GLTF_JSON_BYTES + 8), bin_len);
+            assert_eq!(estimate - GLTF_JSON_BYTES + json_len, glb.len());
+        }
+    }
+
+    #[test]
+    fn size_estimates_grow_with_triangle_count() {
+        let quad = RenderMesh {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0],
+            indices: vec![0, 1, 2, 0, 2, 3],
+            face_ranges: vec![],
+        };
+        let mut triangle = quad.clone();
+        triangle.indices.truncate(3);
+
+        for format in [ExportFormat::Stl, ExportFormat::Obj, ExportFormat::Gltf] {
+            assert!(export_size_estimate(&quad, format) > export_size_estimate(&triangle, format));
+        }
+    }
+
+    #[test]
+    fn obj_export_of_two_adjacent_box_faces() {
+        use kernel_fork::{primitives, tessellation};
+
+        let mut next_id = 0;
+        let mesh = tessellation::tessellate_solid(
+            &primitives::make_box(2.0, 1.0, 3.0),
+            0.01,
+            &mut next_id,
+        )
+        .unwrap();
+        let corners = |face: usize| -> Vec<String> {
+            let range = &mesh.face_ranges[face];
+            mesh.indices[range.start_index as usize..range.end_index as usize]
+                .iter()
+                .map(|&v| {
+                    let p = &mesh.vertices[v as usize * 3..v as usize * 3 + 3];
+                    format!("v {} {} {}", p[0], p[1], p[2])
+                })
+                .collect()
+        };
+        let shared = |a: &[String], b: &[String]| {
+            let mut common: Vec<&String> = a.iter().filter(|p| b.contains(p)).collect();
+            common.sort();
+            common.dedup();
+            common.len()
+        };
+        let first = corners(0);
+        let neighbour = (1..mesh.face_ranges.len())
+            .find(|&f| shared(&first, &corners(f)) >= 2)
+            .expect("box face 0 has an adjacent face");
+
+        let obj = render_faces_to_obj(&mesh, &[0, neighbour]).unwrap();
+        let lines: Vec<&str> = obj.lines().collect();
+
+        // Only the two faces' triangles are written, in two groups
+        let tri_count = |f: usize| {
+            let range = &mesh.face_ranges[f];
+            (range.end_index - range.start_index) as usize / 3
+        };
+        let faces = lines.iter().filter(|l| l.starts_with("f ")).count();
+        assert_eq!(faces, tri_count(0) + tri_count(neighbour));
+        assert_eq!(lines.iter().filter(|l| l.starts_with("g ")).count(), 2);
+
+        // Each face keeps its own copy of the shared edge's endpoints, at the
+        // same coordinates
+        let vertex_lines: Vec<&str> = lines
+            .iter()
+            .copied()
+            .filter(|l| l.starts_with("v "))
+            .collect();
+        let first = corners(0);
+        let second = corners(neighbour);
+        for corner in first.iter().filter(|p| second.contains(p)) {
+            let copies = vertex_lines.iter().filter(|l| **l == corner).count();
+            assert_eq!(copies, 2, "shared corner {} should appear per face", corner);
+        }
+        for line in &vertex_lines {
+            assert!(first.iter().chain(&second).any(|p| p == line));
+        }
+    }
+
+    #[test]
+    fn obj_export_rejects_unknown_face() {
+        let mesh = RenderMesh {
+            vertices: vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0],
+            normals: vec![],
+            indices: vec![0, 1, 2],
+            face_ranges: vec![],
+        };
+        assert!(render_faces_to_obj(&mesh, &[0]).is_none());
+    }
+
+    #[test]
+    fn auto_repaired_export_of_open_box_is_watertight() {
+        use kernel_fork::{primitives, tessellation};
+
+        let mut next_id = 0;
+        let mut open = tessellation::tessellate_solid(
+            &primitives::make_box(2.0, 1.0, 3.0),
+            0.01,
+            &mut next_id,
+        )
+        .unwrap();
+        let last = open.face_ranges.pop().unwrap();
+        open.indices.truncate(last.start_index as usize);
+        assert!(!mesh::validate_mesh(&open).is_closed_manifold());
+
+        let (stl, warnings) = export_stl(open.clone(), true, Solidity::Solid);
+        assert!(!warnings.is_empty());
+
+        // Read the triangles back and check every edge is shared
+        let tri_count = u32::from_le_bytes([stl[80], stl[81], stl[82], stl[83]]) as usize;
+        let mut vertices = Vec::new();
+        for t in 0..tri_count {
+            let record = &stl[84 + t * 50..84 + (t + 1) * 50];
+            for k in 0..9 {
+                let at = 12 + k * 4;
+                vertices.push(f32::from_le_bytes([
+                    record[at],
+                    record[at + 1],
+                    record[at + 2],
+                    record[at + 3],
+                ]));
+            }
+        }
+        let written = RenderMesh {
+            normals: vec![0.0; vertices.len()],
+            indices: (0..tri_count as u32 * 3).collect(),
+            vertices,
+            face_ranges: vec![],
+        };
+        assert!(mesh::validate_mesh(&written).is_closed_manifold());
+
+        // Without the flag the mesh is written as is
+        let (stl, warnings) = export_stl(open.clone(), false, Solidity::Solid);
+        assert!(warnings.is_empty());
+        assert_eq!(stl, render_mesh_to_stl(&open));
+
+        // As a sheet the open box is valid and exported without capping.
+        let (stl, warnings) = export_stl(open.clone(), true, Solidity::Sheet);
+        assert!(warnings.is_empty(), "{:?}", warnings);
+        assert_eq!(stl, render_mesh_to_stl(&open));
+    }
+
+    #[test]
+    fn obj_mtl_export_colors_top_face() {
+        use kernel_fork::{primitives, tessellation};
+
+        let mut next_id = 0;
+        let mesh = tessellation::tessellate_solid(
+            &primitives::make_box(2.0, 1.0, 3.0),
+            0.01,
+            &mut next_id,
+        )
+        .unwrap();
+        // The top face is the one whose vertices all sit at z = 3
+        let top = (0..mesh.face_ranges.len())
+            .find(|&f| {
+                let range = &mesh.face_ranges[f];
+                mesh.indices[range.start_index as usize..range.end_index as usize]
+                    .iter()
+                    .all(|&v| (mesh.vertices[v as usize * 3 + 2] - 3.0).abs() < 1e-6)
+            })
+            .unwrap();
+        let colors = HashMap::from([(top, [1.0, 0.0, 0.0, 1.0])]);
+
+        let (obj, mtl) = render_mesh_to_obj_mtl(&mesh, &colors, "box.mtl");
+        assert!(obj.contains("mtllib box.mtl\n"));
+        let lines: Vec<&str> = obj.lines().collect();
+        let group = lines
+            .iter()
+            .position(|l| *l == format!("g face_{}", top))
+            .unwrap();
+        assert_eq!(lines[group + 1], "usemtl color_ff0000ff");
+        assert_eq!(
+            lines.iter().filter(|l| l.starts_with("g ")).count(),
+            mesh.face_ranges.len()
+        );
+        // Every group sets its material, the uncolored ones to the default
+        assert_eq!(obj.matches("usemtl").count(), mesh.face_ranges.len());
+        assert_eq!(
+            obj.matches("usemtl default\n").count(),
+            mesh.face_ranges.len() - 1
+        );
+        for (i, line) in lines.iter().enumerate() {
+            if line.starts_with("g ") {
+                assert!(lines[i + 1].starts_with("usemtl "), "{}", line);
+            }
+        }
+
+        assert!(mtl.contains("newmtl color_ff0000ff\nKd 1 0 0\nd 1\n"));
+        assert!(mtl.contains("newmtl default\nKd 0.8 0.8 0.8\nd 1\n"));
+    }
 }
@@ -6,7 +6,7 @@
 use wasm_bindgen::prelude::*;
 
 use crate::dispatch;
-use crate::engine_state::EngineState;
+use crate::engine_state::{EngineState, ErrorKind};
 use crate::messages::{EngineToUi, UiToEngine};
 use kernel_fork::RenderMesh;
 use modeling_ops::KernelBundle;
@@ -57,6 +57,7 @@ pub fn process_message(json_input: &str) -> String {
                 return EngineToUi::Error {
                     message: format!("Failed to parse message: {}", e),
                     feature_id: None,
+                    kind: ErrorKind::Serialization,
                 };
             }
         };
@@ -73,7 +74,7 @@ pub fn process_message(json_input: &str) -> String {
 
     serde_json::to_string(&response).unwrap_or_else(|e| {
         format!(
-            r#"{{"type":"Error","message":"Serialization failed: {}","feature_id":null}}"#,
+            r#"{{"type":"Error","message":"Serialization failed: {}","feature_id":null,"kind":"serialization"}}"#,
             e
         )
     })
@@ -183,6 +184,41 @@ pub fn get_mesh_count() -> usize {
     })
 }
 
+/// Get the number of chunks a feature's mesh splits into at
+/// `max_triangles` triangles per chunk. Returns 0 if it has no mesh.
+#[wasm_bindgen]
+pub fn get_mesh_chunk_count(feature_index: usize, max_triangles: usize) -> usize {
+    with_mesh(feature_index, |mesh| {
+        (mesh.indices.len() / 3).div_ceil(max_triangles.max(1))
+    })
+    .unwrap_or(0)
+}
+
+/// Get one chunk of a feature's mesh as JSON.
+///
+/// Large models serialize to one huge string through `get_mesh_json`.
+/// Chunks hold at most `max_triangles` triangles and only the vertices
+/// those triangles use, so the web worker can fetch and upload them one at
+/// a time. Use `get_mesh_chunk_count` with the same `max_triangles` to find
+/// how many there are.
+#[wasm_bindgen]
+pub fn get_mesh_chunk_json(
+    feature_index: usize,
+    max_triangles: usize,
+    chunk_index: usize,
+) -> String {
+    let step = max_triangles.max(1);
+    with_mesh(feature_index, |mesh| {
+        let first = match chunk_index.checked_mul(step) {
+            Some(first) if first < mesh.indices.len() / 3 => first,
+            _ => return r#"{"error":"Chunk index out of range"}"#.to_string(),
+        };
+        let chunk = kernel_fork::mesh::mesh_chunk(mesh, first, first.saturating_add(step));
+        serde_json::to_string(&chunk).unwrap_or_default()
+    })
+    .unwrap_or_else(|| r#"{"error":"No mesh for this feature"}"#.to_string())
+}
+
 /// Get face data for a specific feature by index.
 ///
 /// Returns a JSON array of face ranges enriched with GeomRef data.
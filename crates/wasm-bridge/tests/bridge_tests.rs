@@ -148,10 +148,18 @@ fn serde_roundtrip_engine_error() {
     let msg = EngineToUi::Error {
         message: "something went wrong".to_string(),
         feature_id: Some(Uuid::new_v4()),
+        kind: ErrorKind::Boolean,
     };
     let json = serde_json::to_string(&msg).unwrap();
+    assert!(json.contains(r#""kind":"boolean""#), "{}", json);
     let deserialized: EngineToUi = serde_json::from_str(&json).unwrap();
-    assert!(matches!(deserialized, EngineToUi::Error { .. }));
+    assert!(matches!(
+        deserialized,
+        EngineToUi::Error {
+            kind: ErrorKind::Boolean,
+            ..
+        }
+    ));
 }
 
 #[test]
@@ -283,6 +291,109 @@ fn dispatch_delete_nonexistent_feature_returns_error() {
     assert!(matches!(response, EngineToUi::Error { .. }));
 }
 
+#[test]
+fn dispatch_errors_carry_their_kind() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let mut kind_of = |msg| match wasm_bridge::dispatch(&mut state, msg, &mut kernel) {
+        EngineToUi::Error { kind, .. } => kind,
+        other => panic!("expected an error, got {:?}", other),
+    };
+
+    let missing = UiToEngine::DeleteFeature {
+        feature_id: Uuid::new_v4(),
+    };
+    assert_eq!(kind_of(missing), ErrorKind::Topology);
+    let entity = SketchEntity::Point {
+        id: 1,
+        x: 0.0,
+        y: 0.0,
+        construction: false,
+    };
+    assert_eq!(
+        kind_of(UiToEngine::AddSketchEntity { entity }),
+        ErrorKind::Solver
+    );
+    assert_eq!(kind_of(UiToEngine::ExportStep), ErrorKind::NotSupported);
+    assert_eq!(kind_of(UiToEngine::Undo), ErrorKind::Internal);
+}
+
+#[test]
+fn bridge_error_kinds_cover_every_category() {
+    use kernel_fork::KernelError;
+    use modeling_ops::OpError;
+
+    let cases = [
+        (
+            BridgeError::from(OpError::InvalidParameter {
+                reason: "negative depth".into(),
+            }),
+            ErrorKind::Dimension,
+            "dimension",
+        ),
+        (
+            BridgeError::from(KernelError::EntityNotFound {
+                id: kernel_fork::KernelId(7),
+            }),
+            ErrorKind::Topology,
+            "topology",
+        ),
+        (
+            BridgeError::from(KernelError::BooleanFailed {
+                reason: "no overlap".into(),
+            }),
+            ErrorKind::Boolean,
+            "boolean",
+        ),
+        (
+            BridgeError::from(OpError::NoProfiles),
+            ErrorKind::Solver,
+            "solver",
+        ),
+        (
+            BridgeError::FaceOutOfRange {
+                indices: vec![9],
+                count: 6,
+            },
+            ErrorKind::Index,
+            "index",
+        ),
+        (
+            BridgeError::Serialization {
+                reason: "bad json".into(),
+            },
+            ErrorKind::Serialization,
+            "serialization",
+        ),
+        (
+            BridgeError::NotImplemented {
+                operation: "STEP export".into(),
+            },
+            ErrorKind::NotSupported,
+            "not_supported",
+        ),
+        (BridgeError::NoMeshData, ErrorKind::Internal, "internal"),
+    ];
+    for (error, kind, tag) in cases {
+        assert_eq!(error.kind(), kind, "{}", error);
+        assert_eq!(
+            serde_json::to_string(&kind).unwrap(),
+            format!("\"{}\"", tag)
+        );
+    }
+
+    // Errors from before the kind existed still parse.
+    let old = r#"{"type":"Error","message":"boom","feature_id":null}"#;
+    let parsed: EngineToUi = serde_json::from_str(old).unwrap();
+    assert!(matches!(
+        parsed,
+        EngineToUi::Error {
+            kind: ErrorKind::Internal,
+            ..
+        }
+    ));
+}
+
 // ── Engine State Tests ───────────────────────────────────────────────────
 
 #[test]
@@ -675,18 +786,106 @@ fn dispatch_sketch_then_extrude_produces_solid() {
     }
 }
 
+#[test]
+fn dispatch_solve_sketch_reuses_cached_solution() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+
+    wasm_bridge::dispatch(
+        &mut state,
+        UiToEngine::BeginSketch {
+            plane: make_geom_ref(),
+        },
+        &mut kernel,
+    );
+    for (id, x, y) in [
+        (1, 0.0, 0.0),
+        (2, 10.0, 0.0),
+        (3, 10.0, 10.0),
+        (4, 0.0, 10.0),
+    ] {
+        wasm_bridge::dispatch(
+            &mut state,
+            UiToEngine::AddSketchEntity {
+                entity: SketchEntity::Point {
+                    id,
+                    x,
+                    y,
+                    construction: false,
+                },
+            },
+            &mut kernel,
+        );
+    }
+    for (id, start, end) in [(10, 1, 2), (11, 2, 3), (12, 3, 4), (13, 4, 1)] {
+        wasm_bridge::dispatch(
+            &mut state,
+            UiToEngine::AddSketchEntity {
+                entity: SketchEntity::Line {
+                    id,
+                    start_id: start,
+                    end_id: end,
+                    construction: false,
+                },
+            },
+            &mut kernel,
+        );
+    }
+
+    // Solving an unchanged sketch twice runs the solver once
+    wasm_bridge::dispatch(&mut state, UiToEngine::SolveSketch, &mut kernel);
+    wasm_bridge::dispatch(&mut state, UiToEngine::SolveSketch, &mut kernel);
+    assert_eq!(state.solve_count(), 1);
+
+    // Editing the sketch invalidates the cached solution
+    wasm_bridge::dispatch(
+        &mut state,
+        UiToEngine::AddConstraint {
+            constraint: SketchConstraint::Horizontal { entity: 10 },
+        },
+        &mut kernel,
+    );
+    let response = wasm_bridge::dispatch(&mut state, UiToEngine::SolveSketch, &mut kernel);
+    assert_eq!(state.solve_count(), 2);
+    assert!(matches!(response, EngineToUi::SketchSolved { .. }));
+
+    // The edited sketch's solution replaces the cached one
+    wasm_bridge::dispatch(&mut state, UiToEngine::SolveSketch, &mut kernel);
+    assert_eq!(state.solve_count(), 2);
+}
+
 // ── STL Export Tests ────────────────────────────────────────────────────
 
 #[test]
 fn serde_roundtrip_export_stl() {
-    let msg = UiToEngine::ExportStl;
+    let msg = UiToEngine::ExportStl {
+        auto_repair: true,
+        solidity: kernel_fork::Solidity::Sheet,
+    };
     let json = serde_json::to_string(&msg).unwrap();
     assert!(json.contains("\"type\":\"ExportStl\""));
     let deserialized: UiToEngine = serde_json::from_str(&json).unwrap();
-    assert!(matches!(deserialized, UiToEngine::ExportStl));
+    assert!(matches!(
+        deserialized,
+        UiToEngine::ExportStl {
+            auto_repair: true,
+            solidity: kernel_fork::Solidity::Sheet,
+        }
+    ));
+
+    // Both fields are optional
+    let deserialized: UiToEngine = serde_json::from_str(r#"{"type":"ExportStl"}"#).unwrap();
+    assert!(matches!(
+        deserialized,
+        UiToEngine::ExportStl {
+            auto_repair: false,
+            solidity: kernel_fork::Solidity::Solid,
+        }
+    ));
 
     let response = EngineToUi::StlExportReady {
         stl_data: "AAAA".to_string(),
+        warnings: vec!["filled 1 hole(s)".to_string()],
     };
     let json = serde_json::to_string(&response).unwrap();
     assert!(json.contains("\"type\":\"StlExportReady\""));
@@ -699,7 +898,14 @@ fn dispatch_export_stl_no_features() {
     let mut state = EngineState::new();
     let mut kernel = MockKernel::new();
 
-    let response = wasm_bridge::dispatch(&mut state, UiToEngine::ExportStl, &mut kernel);
+    let response = wasm_bridge::dispatch(
+        &mut state,
+        UiToEngine::ExportStl {
+            auto_repair: false,
+            solidity: Default::default(),
+        },
+        &mut kernel,
+    );
 
     assert!(matches!(response, EngineToUi::Error { .. }));
     if let EngineToUi::Error { message, .. } = &response {
@@ -728,3 +934,190 @@ fn make_sketch_operation() -> Operation {
         },
     }
 }
+
+// ── Face Export Tests ───────────────────────────────────────────────────
+
+/// Helper: add a sketch and an extrude of it, and tessellate the extrude the
+/// way the WASM layer does after a rebuild. Returns the extrude's id.
+fn add_meshed_extrude(state: &mut EngineState, kernel: &mut MockKernel) -> Uuid {
+    use kernel_fork::Kernel;
+
+    let sketch = state
+        .engine
+        .add_feature("Sketch".to_string(), make_sketch_op(), kernel)
+        .unwrap();
+    let extrude = state
+        .engine
+        .add_feature("Extrude".to_string(), make_extrude_op(sketch), kernel)
+        .unwrap();
+    let result = state.engine.feature_results.get_mut(&extrude).unwrap();
+    for (_, body) in &mut result.outputs {
+        body.mesh = Some(kernel.tessellate(&body.handle, 0.1).unwrap());
+    }
+    extrude
+}
+
+#[test]
+fn engine_state_exports_selected_faces_as_obj() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let extrude = add_meshed_extrude(&mut state, &mut kernel);
+    let mesh = state.engine.feature_results[&extrude].outputs[0]
+        .1
+        .mesh
+        .clone()
+        .unwrap();
+    assert!(mesh.face_ranges.len() > 2);
+
+    let obj = state.export_faces_obj(extrude, &[0, 2]).unwrap();
+    let groups: Vec<&str> = obj.lines().filter(|l| l.starts_with("g ")).collect();
+    assert_eq!(groups, ["g face_0", "g face_2"]);
+    let triangles = [0, 2]
+        .iter()
+        .map(|&f| {
+            let range = &mesh.face_ranges[f];
+            (range.end_index - range.start_index) as usize / 3
+        })
+        .sum::<usize>();
+    assert_eq!(
+        obj.lines().filter(|l| l.starts_with("f ")).count(),
+        triangles
+    );
+}
+
+#[test]
+fn engine_state_face_export_errors() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let extrude = add_meshed_extrude(&mut state, &mut kernel);
+    let count = state.engine.feature_results[&extrude].outputs[0]
+        .1
+        .mesh
+        .as_ref()
+        .unwrap()
+        .face_ranges
+        .len();
+
+    match state.export_faces_obj(extrude, &[0, count]) {
+        Err(BridgeError::FaceOutOfRange { indices, count: c }) => {
+            assert_eq!((indices, c), (vec![0, count], count));
+        }
+        other => panic!("expected FaceOutOfRange, got {:?}", other),
+    }
+
+    // A sketch has no body, and an unknown feature has no result
+    let sketch = state.engine.tree.features[0].id;
+    assert!(matches!(
+        state.export_faces_obj(sketch, &[0]),
+        Err(BridgeError::NoMeshData)
+    ));
+    assert!(matches!(
+        state.export_faces_obj(Uuid::new_v4(), &[0]),
+        Err(BridgeError::NoMeshData)
+    ));
+}
+
+#[test]
+fn engine_state_exports_face_colors_as_materials() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let extrude = add_meshed_extrude(&mut state, &mut kernel);
+
+    state
+        .set_face_color(extrude, 1, [1.0, 0.0, 0.0, 1.0])
+        .unwrap();
+    let (obj, mtl) = state.export_obj_with_colors(extrude, "part.mtl").unwrap();
+    assert!(obj.starts_with("# Waffle Iron OBJ Export\nmtllib part.mtl\n"));
+    let lines: Vec<&str> = obj.lines().collect();
+    let group = lines.iter().position(|l| *l == "g face_1").unwrap();
+    assert_eq!(lines[group + 1], "usemtl color_ff0000ff");
+    assert!(mtl.contains("newmtl color_ff0000ff\n"));
+
+    // Setting a face again replaces its color
+    state
+        .set_face_color(extrude, 1, [0.0, 0.0, 1.0, 1.0])
+        .unwrap();
+    let (obj, mtl) = state.export_obj_with_colors(extrude, "part.mtl").unwrap();
+    assert!(obj.contains("g face_1\nusemtl color_0000ffff\n"));
+    assert!(!mtl.contains("color_ff0000ff"));
+
+    let count = state.engine.feature_results[&extrude].outputs[0]
+        .1
+        .mesh
+        .as_ref()
+        .unwrap()
+        .face_ranges
+        .len();
+    assert!(matches!(
+        state.set_face_color(extrude, count, [0.0; 4]),
+        Err(BridgeError::FaceOutOfRange { .. })
+    ));
+}
+
+#[test]
+fn engine_state_drops_face_colors_when_the_feature_rebuilds() {
+    use kernel_fork::Kernel;
+
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let extrude = add_meshed_extrude(&mut state, &mut kernel);
+    state
+        .set_face_color(extrude, 1, [1.0, 0.0, 0.0, 1.0])
+        .unwrap();
+
+    let sketch = state.engine.tree.features[0].id;
+    let mut deeper = make_extrude_op(sketch);
+    if let Operation::Extrude { params } = &mut deeper {
+        params.depth = 8.0;
+    }
+    state
+        .engine
+        .edit_feature(extrude, deeper, &mut kernel)
+        .unwrap();
+    let result = state.engine.feature_results.get_mut(&extrude).unwrap();
+    for (_, body) in &mut result.outputs {
+        body.mesh = Some(kernel.tessellate(&body.handle, 0.1).unwrap());
+    }
+
+    // The rebuilt faces are new faces, so face 1 is not red any more
+    let (obj, _) = state.export_obj_with_colors(extrude, "part.mtl").unwrap();
+    assert!(!obj.contains("color_ff0000ff"));
+    assert_eq!(
+        obj.matches("usemtl default\n").count(),
+        obj.lines().filter(|l| l.starts_with("g ")).count()
+    );
+}
+
+#[test]
+fn engine_state_splits_a_body_into_mesh_chunks() {
+    let mut state = EngineState::new();
+    let mut kernel = MockKernel::new();
+    let extrude = add_meshed_extrude(&mut state, &mut kernel);
+    let triangles = state.engine.feature_results[&extrude].outputs[0]
+        .1
+        .mesh
+        .as_ref()
+        .unwrap()
+        .indices
+        .len()
+        / 3;
+
+    let chunks = state.mesh_chunks(extrude, 5).unwrap();
+    assert_eq!(chunks.len(), triangles.div_ceil(5));
+    assert!(chunks.iter().all(|c| c.indices.len() / 3 <= 5));
+    assert_eq!(
+        chunks.iter().map(|c| c.indices.len() / 3).sum::<usize>(),
+        triangles
+    );
+    // Each chunk indexes only its own vertices
+    for chunk in &chunks {
+        let vertex_count = chunk.vertices.len() / 3;
+        assert!(chunk.indices.iter().all(|&i| (i as usize) < vertex_count));
+    }
+
+    let sketch = state.engine.tree.features[0].id;
+    assert!(matches!(
+        state.mesh_chunks(sketch, 5),
+        Err(BridgeError::NoMeshData)
+    ));
+}